@@ -0,0 +1,485 @@
+use std::fs;
+use std::iter::Peekable;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::a2l_keywords::{is_repeated, keyword_spec, Field};
+use crate::export_options::{render_a2l, ExportOptions};
+use crate::{build_metadata, diagnostics, parse_a2l, A2lMetadata, AppState};
+
+/// One `/begin <block> ... /end <block>` section. Items are the A2L tokens in
+/// file order (quoted strings keep their quotes, numbers their notation) and
+/// nested blocks, so the tree maps back to A2L without loss.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct Block {
+    pub(crate) block: String,
+    pub(crate) items: Vec<Item>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Item {
+    Token(String),
+    Block(Block),
+}
+
+pub(crate) fn is_yaml(path: &str) -> bool {
+    let extension = Path::new(path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    matches!(extension.as_str(), "yaml" | "yml")
+}
+
+/// Splits A2L text into tokens; comments are dropped and the A2ML block body is
+/// kept as a single raw token because its grammar is not keyword based.
+fn tokenize(text: &str) -> Vec<&str> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let c = bytes[pos];
+        if c.is_ascii_whitespace() {
+            pos += 1;
+        } else if text[pos..].starts_with("/*") {
+            pos = text[pos + 2..].find("*/").map_or(bytes.len(), |end| pos + 2 + end + 2);
+        } else if text[pos..].starts_with("//") {
+            pos = text[pos..].find('\n').map_or(bytes.len(), |end| pos + end);
+        } else if c == b'"' {
+            let start = pos;
+            pos += 1;
+            while pos < bytes.len() {
+                match bytes[pos] {
+                    b'\\' => pos += 2,
+                    // A doubled quote is an escaped quote inside the string.
+                    b'"' if bytes.get(pos + 1) == Some(&b'"') => pos += 2,
+                    b'"' => {
+                        pos += 1;
+                        break;
+                    }
+                    _ => pos += 1,
+                }
+            }
+            tokens.push(&text[start..pos.min(bytes.len())]);
+        } else {
+            let start = pos;
+            while pos < bytes.len() && !bytes[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            tokens.push(&text[start..pos]);
+            if tokens.ends_with(&["/begin", "A2ML"]) {
+                let end = text[pos..].find("/end A2ML").map_or(bytes.len(), |end| pos + end);
+                let body = text[pos..end].trim();
+                if !body.is_empty() {
+                    tokens.push(body);
+                }
+                pos = end;
+            }
+        }
+    }
+    tokens
+}
+
+fn parse_items<'a>(tokens: &mut impl Iterator<Item = &'a str>, block: Option<&str>) -> Result<Vec<Item>, String> {
+    let mut items = Vec::new();
+    while let Some(token) = tokens.next() {
+        match token {
+            "/begin" => {
+                let name = tokens.next().ok_or("Unexpected end of file after /begin")?;
+                items.push(Item::Block(Block {
+                    block: name.to_string(),
+                    items: parse_items(tokens, Some(name))?,
+                }));
+            }
+            "/end" => {
+                let name = tokens.next().ok_or("Unexpected end of file after /end")?;
+                return match block {
+                    Some(block) if block == name => Ok(items),
+                    _ => Err(format!("Unexpected /end {name}")),
+                };
+            }
+            _ => items.push(Item::Token(token.to_string())),
+        }
+    }
+    match block {
+        Some(block) => Err(format!("Missing /end {block}")),
+        None => Ok(items),
+    }
+}
+
+pub(crate) fn render_items(items: &[Item], depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    let mut line_open = false;
+    for item in items {
+        match item {
+            Item::Token(token) => {
+                if line_open {
+                    out.push(' ');
+                } else {
+                    out.push_str(&indent);
+                    line_open = true;
+                }
+                out.push_str(token);
+            }
+            Item::Block(block) => {
+                if line_open {
+                    out.push('\n');
+                    line_open = false;
+                }
+                out.push_str(&format!("{indent}/begin {}\n", block.block));
+                render_items(&block.items, depth + 1, out);
+                out.push_str(&format!("{indent}/end {}\n", block.block));
+            }
+        }
+    }
+    if line_open {
+        out.push('\n');
+    }
+}
+
+/// `KEYWORD name` of a block item, e.g. `MEASUREMENT engine_speed`; blocks
+/// without a name are keyed by their first token.
+pub(crate) fn object_key(item: &Item) -> Option<String> {
+    let Item::Block(block) = item else { return None };
+    let first = match block.items.first() {
+        Some(Item::Token(token)) => token.as_str(),
+        _ => "",
+    };
+    Some(format!("{} {}", block.block, first))
+}
+
+/// Block tree of the whole document, with includes merged.
+pub(crate) fn block_tree(a2l: &a2lfile::A2lFile) -> Result<Vec<Item>, String> {
+    let text = render_a2l(a2l, &ExportOptions::default());
+    parse_items(&mut tokenize(&text).into_iter(), None)
+}
+
+fn is_number(token: &str) -> bool {
+    token.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '-' | '+' | '.'))
+}
+
+/// Content of a quoted A2L string.
+fn unquote(token: &str) -> String {
+    let Some(inner) = token.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')) else {
+        return token.to_string();
+    };
+    let mut text = String::with_capacity(inner.len());
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some(&escaped)) | ('"', Some(&escaped @ '"')) => {
+                chars.next();
+                text.push(match escaped {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    other => other,
+                });
+            }
+            _ => text.push(c),
+        }
+    }
+    text
+}
+
+fn quote(text: &str) -> String {
+    let mut token = String::with_capacity(text.len() + 2);
+    token.push('"');
+    for c in text.chars() {
+        match c {
+            '\\' => token.push_str("\\\\"),
+            '"' => token.push_str("\\\""),
+            '\n' => token.push_str("\\n"),
+            '\t' => token.push_str("\\t"),
+            '\r' => token.push_str("\\r"),
+            _ => token.push(c),
+        }
+    }
+    token.push('"');
+    token
+}
+
+fn scalar_value(field: &Field, token: &str) -> Value {
+    match field {
+        Field::Text(_) => Value::String(unquote(token)),
+        Field::Number(_) => token
+            .parse::<serde_json::Number>()
+            .map(Value::Number)
+            .unwrap_or_else(|_| Value::String(token.to_string())),
+        _ => Value::String(token.to_string()),
+    }
+}
+
+/// Whether the next item starts another row of a list whose rows begin with
+/// `field`. Lists end at the next keyword, block or value of another type.
+fn starts_row(item: Option<&&Item>, field: &Field) -> bool {
+    let Some(Item::Token(token)) = item else {
+        return false;
+    };
+    if keyword_spec(token).is_some() {
+        return false;
+    }
+    match field {
+        Field::Number(_) => is_number(token),
+        Field::Text(_) => token.starts_with('"'),
+        _ => true,
+    }
+}
+
+fn read_fields<'a, I: Iterator<Item = &'a Item>>(
+    items: &mut Peekable<I>,
+    fields: &[Field],
+    object: &mut Map<String, Value>,
+) {
+    for field in fields {
+        match field {
+            Field::List(name, row) => {
+                let mut rows = Vec::new();
+                while starts_row(items.peek(), &row[0]) {
+                    let mut values = Map::new();
+                    read_fields(items, row, &mut values);
+                    rows.push(match row {
+                        [single] => values.remove(single.name()).unwrap_or(Value::Null),
+                        _ => Value::Object(values),
+                    });
+                }
+                object.insert(name.to_string(), Value::Array(rows));
+            }
+            scalar => {
+                let Some(Item::Token(token)) = items.peek() else {
+                    return;
+                };
+                object.insert(scalar.name().to_string(), scalar_value(scalar, token));
+                items.next();
+            }
+        }
+    }
+}
+
+/// Adds a keyword to its parent object. Repeatable keywords, and any keyword
+/// that does occur twice, become arrays.
+fn insert_keyword(object: &mut Map<String, Value>, keyword: &str, value: Value) {
+    let key = keyword.to_lowercase();
+    match object.get_mut(&key) {
+        Some(Value::Array(values)) => values.push(value),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = Value::Array(vec![first, value]);
+        }
+        None if is_repeated(keyword) => {
+            object.insert(key, Value::Array(vec![value]));
+        }
+        None => {
+            object.insert(key, value);
+        }
+    }
+}
+
+/// Typed form of the items of a block: its positional values by name, then
+/// one entry per optional keyword or sub-block. Keywords that ASAP2 does not
+/// define (e.g. IF_DATA) keep their tokens under `items` or `values`.
+fn typed_block(items: &[Item], fields: &[Field]) -> Map<String, Value> {
+    let mut object = Map::new();
+    let mut items = items.iter().peekable();
+    read_fields(&mut items, fields, &mut object);
+    while let Some(item) = items.next() {
+        let (keyword, value) = match item {
+            Item::Block(block) => match keyword_spec(&block.block) {
+                Some(spec) if spec.block => (&block.block, Value::Object(typed_block(&block.items, spec.fields))),
+                _ => {
+                    let raw = serde_json::to_value(&block.items).unwrap_or_default();
+                    (
+                        &block.block,
+                        Value::Object(Map::from_iter([("items".to_string(), raw)])),
+                    )
+                }
+            },
+            Item::Token(token) => match keyword_spec(token) {
+                Some(spec) if !spec.block && spec.fields.is_empty() => (token, Value::Bool(true)),
+                Some(spec) if !spec.block => {
+                    let mut values = Map::new();
+                    read_fields(&mut items, spec.fields, &mut values);
+                    (token, Value::Object(values))
+                }
+                _ => {
+                    let mut values = Vec::new();
+                    while let Some(Item::Token(value)) = items.peek() {
+                        if keyword_spec(value).is_some() {
+                            break;
+                        }
+                        values.push(Value::String(value.clone()));
+                        items.next();
+                    }
+                    (
+                        token,
+                        Value::Object(Map::from_iter([("values".to_string(), Value::Array(values))])),
+                    )
+                }
+            },
+        };
+        insert_keyword(&mut object, keyword, value);
+    }
+    object
+}
+
+fn scalar_token(field: &Field, value: &Value) -> Result<String, String> {
+    match (field, value) {
+        (Field::Text(_), Value::String(text)) => Ok(quote(text)),
+        (_, Value::String(text)) => Ok(text.clone()),
+        (_, Value::Number(number)) => Ok(number.to_string()),
+        (_, Value::Bool(flag)) => Ok(flag.to_string()),
+        _ => Err(format!("Invalid value for {}: {value}", field.name())),
+    }
+}
+
+fn field_tokens(field: &Field, value: &Value, items: &mut Vec<Item>) -> Result<(), String> {
+    let Field::List(name, row) = field else {
+        items.push(Item::Token(scalar_token(field, value)?));
+        return Ok(());
+    };
+    let rows = value.as_array().ok_or_else(|| format!("{name} must be an array"))?;
+    for value in rows {
+        match (row, value) {
+            ([single], _) => items.push(Item::Token(scalar_token(single, value)?)),
+            (_, Value::Object(values)) => {
+                for field in row.iter() {
+                    if let Some(value) = values.get(field.name()) {
+                        field_tokens(field, value, items)?;
+                    }
+                }
+            }
+            _ => return Err(format!("Rows of {name} must be objects")),
+        }
+    }
+    Ok(())
+}
+
+/// Order in which optional keywords are written back. The version keywords
+/// lead the file and the A2ML precedes the IF_DATA it describes.
+fn keyword_rank(key: &str) -> usize {
+    match key {
+        "asap2_version" => 0,
+        "a2ml_version" => 1,
+        "a2ml" => 2,
+        _ => 3,
+    }
+}
+
+/// Inverse of `typed_block`.
+fn block_items(object: &Map<String, Value>, fields: &[Field]) -> Result<Vec<Item>, String> {
+    let mut items = Vec::new();
+    for field in fields {
+        if let Some(value) = object.get(field.name()) {
+            field_tokens(field, value, &mut items)?;
+        }
+    }
+    let mut keywords: Vec<(&String, &Value)> = object
+        .iter()
+        .filter(|(key, _)| !fields.iter().any(|field| field.name() == key.as_str()))
+        .collect();
+    keywords.sort_by_key(|(key, _)| keyword_rank(key));
+    for (key, value) in keywords {
+        keyword_items(&key.to_uppercase(), value, &mut items)?;
+    }
+    Ok(items)
+}
+
+fn keyword_items(keyword: &str, value: &Value, items: &mut Vec<Item>) -> Result<(), String> {
+    let object = match value {
+        Value::Array(values) => {
+            for value in values {
+                keyword_items(keyword, value, items)?;
+            }
+            return Ok(());
+        }
+        Value::Bool(true) => {
+            items.push(Item::Token(keyword.to_string()));
+            return Ok(());
+        }
+        Value::Bool(false) | Value::Null => return Ok(()),
+        Value::Object(object) => object,
+        _ => return Err(format!("Invalid value for {keyword}: {value}")),
+    };
+    match keyword_spec(keyword) {
+        Some(spec) if spec.block => items.push(Item::Block(Block {
+            block: keyword.to_string(),
+            items: block_items(object, spec.fields)?,
+        })),
+        Some(spec) => {
+            items.push(Item::Token(keyword.to_string()));
+            for field in spec.fields {
+                if let Some(value) = object.get(field.name()) {
+                    field_tokens(field, value, items)?;
+                }
+            }
+        }
+        None => match (object.get("items"), object.get("values")) {
+            (Some(raw), _) => items.push(Item::Block(Block {
+                block: keyword.to_string(),
+                items: serde_json::from_value(raw.clone()).map_err(|e| format!("{keyword}: {e}"))?,
+            })),
+            (None, Some(Value::Array(values))) => {
+                items.push(Item::Token(keyword.to_string()));
+                for value in values {
+                    items.push(Item::Token(scalar_token(&Field::Ident(""), value)?));
+                }
+            }
+            _ => return Err(format!("Unknown keyword {keyword}")),
+        },
+    }
+    Ok(())
+}
+
+/// JSON text of the document, or YAML for `yaml`. Each ASAP2 keyword is an
+/// object with its values under their ASAP2 names; repeatable keywords are
+/// arrays.
+pub(crate) fn serialize_document(a2l: &a2lfile::A2lFile, yaml: bool) -> Result<String, String> {
+    let document = Value::Object(typed_block(&block_tree(a2l)?, &[]));
+    if yaml {
+        serde_yaml::to_string(&document).map_err(|e| e.to_string())
+    } else {
+        serde_json::to_string_pretty(&document).map_err(|e| e.to_string())
+    }
+}
+
+/// A2L text of a document written by `serialize_document`.
+pub(crate) fn deserialize_document(content: &str, yaml: bool) -> Result<String, String> {
+    let document: Value = if yaml {
+        serde_yaml::from_str(content).map_err(|e| e.to_string())?
+    } else {
+        serde_json::from_str(content).map_err(|e| e.to_string())?
+    };
+    let object = document.as_object().ok_or("The document must be an object")?;
+    let mut text = String::new();
+    render_items(&block_items(object, &[])?, 0, &mut text);
+    Ok(text)
+}
+
+/// Writes the whole document as JSON (or, for .yaml/.yml, YAML).
+#[tauri::command]
+pub fn export_a2l_json(path: String, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<(), String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let content = serialize_document(a2l, is_yaml(&path))?;
+    fs::write(&path, content).map_err(|e| e.to_string())
+}
+
+/// Loads a file written by `export_a2l_json` into the given document slot.
+#[tauri::command]
+pub fn import_a2l_json(
+    path: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<A2lMetadata, String> {
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let text = deserialize_document(&content, is_yaml(&path))?;
+    let (a2l, warnings) = parse_a2l(&text, None)?;
+
+    let metadata = build_metadata(&a2l, warnings.len());
+    let mut documents = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let id = documents.replace(doc_id.as_deref(), None, a2l)?;
+    documents.document_mut(Some(&id))?.diagnostics = diagnostics::from_warnings(&warnings);
+    Ok(metadata)
+}
@@ -0,0 +1,434 @@
+use Field::{Ident as I, List as L, Number as N, Text as S};
+
+/// Value of an ASAP2 keyword, in the order the keyword takes them.
+#[derive(Clone, Copy)]
+pub(crate) enum Field {
+    /// Identifier or enum value, written without quotes.
+    Ident(&'static str),
+    /// Quoted string.
+    Text(&'static str),
+    Number(&'static str),
+    /// Group of values repeated up to the next keyword or the end of the block.
+    List(&'static str, &'static [Field]),
+}
+
+impl Field {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Field::Ident(name) | Field::Text(name) | Field::Number(name) | Field::List(name, _) => name,
+        }
+    }
+}
+
+pub(crate) struct KeywordSpec {
+    /// `/begin KEYWORD ... /end KEYWORD` rather than a single-line keyword.
+    pub(crate) block: bool,
+    pub(crate) fields: &'static [Field],
+}
+
+const BLOCK: bool = true;
+const KEYWORD: bool = false;
+
+const IDENTIFIERS: &[Field] = &[L("identifier_list", &[I("")])];
+const POSITION_DATATYPE: &[Field] = &[N("position"), I("datatype")];
+const AXIS_PTS_LAYOUT: &[Field] = &[N("position"), I("datatype"), I("index_incr"), I("addressing")];
+const AXIS_RESCALE_LAYOUT: &[Field] = &[
+    N("position"),
+    I("datatype"),
+    N("max_number_of_rescale_pairs"),
+    I("index_incr"),
+    I("addressing"),
+];
+const MEASUREMENT_FIELDS: &[Field] = &[
+    I("name"),
+    S("long_identifier"),
+    I("datatype"),
+    I("conversion"),
+    N("resolution"),
+    N("accuracy"),
+    N("lower_limit"),
+    N("upper_limit"),
+];
+
+/// Positional values of the ASAP2 1.71 keywords. IF_DATA is not listed: its
+/// content is defined by the module's A2ML, not by ASAP2.
+pub(crate) fn keyword_spec(keyword: &str) -> Option<KeywordSpec> {
+    let (block, fields): (bool, &'static [Field]) = match keyword {
+        "ASAP2_VERSION" | "A2ML_VERSION" => (KEYWORD, &[N("version_no"), N("upgrade_no")]),
+        "PROJECT" | "MODULE" => (BLOCK, &[I("name"), S("long_identifier")]),
+        "HEADER" | "MOD_PAR" | "MOD_COMMON" => (BLOCK, &[S("comment")]),
+        "A2ML" => (BLOCK, &[I("a2ml_text")]),
+        "PROJECT_NO" => (KEYWORD, &[I("project_number")]),
+        "VERSION" => (KEYWORD, &[S("version_identifier")]),
+
+        // MOD_PAR
+        "ADDR_EPK" => (KEYWORD, &[N("address")]),
+        "EPK" => (KEYWORD, &[S("identifier")]),
+        "SUPPLIER" => (KEYWORD, &[S("manufacturer")]),
+        "CUSTOMER" => (KEYWORD, &[S("customer")]),
+        "CUSTOMER_NO" => (KEYWORD, &[S("number")]),
+        "USER" => (KEYWORD, &[S("user")]),
+        "PHONE_NO" => (KEYWORD, &[S("telnum")]),
+        "ECU" => (KEYWORD, &[S("control_unit")]),
+        "CPU_TYPE" => (KEYWORD, &[S("cpu")]),
+        "NO_OF_INTERFACES" => (KEYWORD, &[N("num")]),
+        "ECU_CALIBRATION_OFFSET" => (KEYWORD, &[N("offset")]),
+        "CALIBRATION_METHOD" => (BLOCK, &[S("method"), N("version")]),
+        "CALIBRATION_HANDLE" => (BLOCK, &[L("handle_list", &[N("")])]),
+        "CALIBRATION_HANDLE_TEXT" => (KEYWORD, &[S("text")]),
+        "MEMORY_LAYOUT" => (
+            BLOCK,
+            &[
+                I("prg_type"),
+                N("address"),
+                N("size"),
+                N("offset_1"),
+                N("offset_2"),
+                N("offset_3"),
+                N("offset_4"),
+                N("offset_5"),
+            ],
+        ),
+        "MEMORY_SEGMENT" => (
+            BLOCK,
+            &[
+                I("name"),
+                S("long_identifier"),
+                I("prg_type"),
+                I("memory_type"),
+                I("attribute"),
+                N("address"),
+                N("size"),
+                N("offset_1"),
+                N("offset_2"),
+                N("offset_3"),
+                N("offset_4"),
+                N("offset_5"),
+            ],
+        ),
+        "SYSTEM_CONSTANT" => (KEYWORD, &[S("name"), S("value")]),
+
+        // MOD_COMMON
+        "S_REC_LAYOUT" => (KEYWORD, &[I("name")]),
+        "DEPOSIT" => (KEYWORD, &[I("mode")]),
+        "BYTE_ORDER" => (KEYWORD, &[I("byte_order")]),
+        "DATA_SIZE" => (KEYWORD, &[N("size")]),
+        "ALIGNMENT_BYTE"
+        | "ALIGNMENT_WORD"
+        | "ALIGNMENT_LONG"
+        | "ALIGNMENT_INT64"
+        | "ALIGNMENT_FLOAT16_IEEE"
+        | "ALIGNMENT_FLOAT32_IEEE"
+        | "ALIGNMENT_FLOAT64_IEEE" => (KEYWORD, &[N("alignment_border")]),
+
+        // Measurement and calibration objects
+        "MEASUREMENT" | "TYPEDEF_MEASUREMENT" => (BLOCK, MEASUREMENT_FIELDS),
+        "CHARACTERISTIC" => (
+            BLOCK,
+            &[
+                I("name"),
+                S("long_identifier"),
+                I("characteristic_type"),
+                N("address"),
+                I("deposit"),
+                N("max_diff"),
+                I("conversion"),
+                N("lower_limit"),
+                N("upper_limit"),
+            ],
+        ),
+        "TYPEDEF_CHARACTERISTIC" => (
+            BLOCK,
+            &[
+                I("name"),
+                S("long_identifier"),
+                I("characteristic_type"),
+                I("record_layout"),
+                N("max_diff"),
+                I("conversion"),
+                N("lower_limit"),
+                N("upper_limit"),
+            ],
+        ),
+        "AXIS_PTS" => (
+            BLOCK,
+            &[
+                I("name"),
+                S("long_identifier"),
+                N("address"),
+                I("input_quantity"),
+                I("deposit_record"),
+                N("max_diff"),
+                I("conversion"),
+                N("max_axis_points"),
+                N("lower_limit"),
+                N("upper_limit"),
+            ],
+        ),
+        "TYPEDEF_AXIS" => (
+            BLOCK,
+            &[
+                I("name"),
+                S("long_identifier"),
+                I("input_quantity"),
+                I("record_layout"),
+                N("max_diff"),
+                I("conversion"),
+                N("max_axis_points"),
+                N("lower_limit"),
+                N("upper_limit"),
+            ],
+        ),
+        "AXIS_DESCR" => (
+            BLOCK,
+            &[
+                I("attribute"),
+                I("input_quantity"),
+                I("conversion"),
+                N("max_axis_points"),
+                N("lower_limit"),
+                N("upper_limit"),
+            ],
+        ),
+        "BLOB" => (BLOCK, &[I("name"), S("long_identifier"), N("start_address"), N("size")]),
+        "TYPEDEF_BLOB" => (BLOCK, &[I("name"), S("long_identifier"), N("size")]),
+        "INSTANCE" => (
+            BLOCK,
+            &[I("name"), S("long_identifier"), I("type_ref"), N("start_address")],
+        ),
+        "TYPEDEF_STRUCTURE" => (BLOCK, &[I("name"), S("long_identifier"), N("total_size")]),
+        "STRUCTURE_COMPONENT" => (BLOCK, &[I("name"), I("component_type"), N("address_offset")]),
+        "OVERWRITE" => (BLOCK, &[I("name"), N("axis_number")]),
+
+        // Optional attributes of objects
+        "ADDRESS_TYPE" => (KEYWORD, &[I("address_type")]),
+        "ANNOTATION" => (BLOCK, &[]),
+        "ANNOTATION_LABEL" => (KEYWORD, &[S("label")]),
+        "ANNOTATION_ORIGIN" => (KEYWORD, &[S("origin")]),
+        "ANNOTATION_TEXT" => (BLOCK, &[L("annotation_text_list", &[S("")])]),
+        "ARRAY_SIZE" => (KEYWORD, &[N("number")]),
+        "AXIS_PTS_REF" => (KEYWORD, &[I("axis_points")]),
+        "BIT_MASK" | "ERROR_MASK" => (KEYWORD, &[N("mask")]),
+        "BIT_OPERATION" => (BLOCK, &[]),
+        "LEFT_SHIFT" | "RIGHT_SHIFT" => (KEYWORD, &[N("bitcount")]),
+        "CALIBRATION_ACCESS" => (KEYWORD, &[I("calibration_access")]),
+        "COMPARISON_QUANTITY"
+        | "CONVERSION"
+        | "INPUT_QUANTITY"
+        | "REF_MEMORY_SEGMENT"
+        | "VAR_MEASUREMENT"
+        | "VAR_SELECTION_CHARACTERISTIC" => (KEYWORD, &[I("name")]),
+        "CURVE_AXIS_REF" => (KEYWORD, &[I("curve_axis")]),
+        "DEPENDENT_CHARACTERISTIC" | "VIRTUAL_CHARACTERISTIC" => {
+            (BLOCK, &[S("formula"), L("characteristic_id", &[I("")])])
+        }
+        "DISPLAY_IDENTIFIER" => (KEYWORD, &[I("display_name")]),
+        "ECU_ADDRESS" => (KEYWORD, &[N("address")]),
+        "ECU_ADDRESS_EXTENSION" => (KEYWORD, &[N("extension")]),
+        "ENCODING" => (KEYWORD, &[I("encoding")]),
+        "EXTENDED_LIMITS" | "LIMITS" => (KEYWORD, &[N("lower_limit"), N("upper_limit")]),
+        "FIX_AXIS_PAR" => (KEYWORD, &[N("offset"), N("shift"), N("number_apo")]),
+        "FIX_AXIS_PAR_DIST" => (KEYWORD, &[N("offset"), N("distance"), N("number_apo")]),
+        "FIX_AXIS_PAR_LIST" => (BLOCK, &[L("axis_pts_value_list", &[N("")])]),
+        "FORMAT" => (KEYWORD, &[S("format_string")]),
+        "LAYOUT" => (KEYWORD, &[I("index_mode")]),
+        "MATRIX_DIM" => (KEYWORD, &[L("dim_list", &[N("")])]),
+        "MAX_GRAD" => (KEYWORD, &[N("max_gradient")]),
+        "MAX_REFRESH" => (KEYWORD, &[N("scaling_unit"), N("rate")]),
+        "MODEL_LINK" => (KEYWORD, &[S("model_link")]),
+        "MONOTONY" => (KEYWORD, &[I("monotony")]),
+        "NUMBER" => (KEYWORD, &[N("number")]),
+        "PHYS_UNIT" => (KEYWORD, &[S("unit")]),
+        "STEP_SIZE" => (KEYWORD, &[N("step_size")]),
+        "SYMBOL_LINK" => (KEYWORD, &[S("symbol_name"), N("offset")]),
+        "SYMBOL_TYPE_LINK" => (KEYWORD, &[S("symbol_type")]),
+        "DISCRETE"
+        | "GUARD_RAILS"
+        | "READ_ONLY"
+        | "READ_WRITE"
+        | "ROOT"
+        | "SIGN_EXTEND"
+        | "CONSISTENT_EXCHANGE"
+        | "STATIC_RECORD_LAYOUT"
+        | "STATIC_ADDRESS_OFFSETS" => (KEYWORD, &[]),
+
+        // Reference lists
+        "FUNCTION_LIST" | "MAP_LIST" => (BLOCK, &[L("name_list", &[I("")])]),
+        "DEF_CHARACTERISTIC"
+        | "IN_MEASUREMENT"
+        | "LOC_MEASUREMENT"
+        | "OUT_MEASUREMENT"
+        | "REF_CHARACTERISTIC"
+        | "REF_MEASUREMENT"
+        | "REF_GROUP"
+        | "SUB_FUNCTION"
+        | "SUB_GROUP"
+        | "TRANSFORMER_IN_OBJECTS"
+        | "TRANSFORMER_OUT_OBJECTS" => (BLOCK, IDENTIFIERS),
+        "FRAME_MEASUREMENT" => (KEYWORD, IDENTIFIERS),
+        "VIRTUAL" => (BLOCK, &[L("measuring_channel_list", &[I("")])]),
+
+        // Conversions and units
+        "COMPU_METHOD" => (
+            BLOCK,
+            &[
+                I("name"),
+                S("long_identifier"),
+                I("conversion_type"),
+                S("format"),
+                S("unit"),
+            ],
+        ),
+        "COEFFS" => (KEYWORD, &[N("a"), N("b"), N("c"), N("d"), N("e"), N("f")]),
+        "COEFFS_LINEAR" => (KEYWORD, &[N("a"), N("b")]),
+        "COMPU_TAB_REF" | "STATUS_STRING_REF" => (KEYWORD, &[I("conversion_table")]),
+        "FORMULA" => (BLOCK, &[S("fx")]),
+        "FORMULA_INV" => (KEYWORD, &[S("gx")]),
+        "REF_UNIT" => (KEYWORD, &[I("unit")]),
+        "COMPU_TAB" => (
+            BLOCK,
+            &[
+                I("name"),
+                S("long_identifier"),
+                I("conversion_type"),
+                N("number_value_pairs"),
+                L("tab_entry", &[N("in_val"), N("out_val")]),
+            ],
+        ),
+        "COMPU_VTAB" => (
+            BLOCK,
+            &[
+                I("name"),
+                S("long_identifier"),
+                I("conversion_type"),
+                N("number_value_pairs"),
+                L("value_pairs", &[N("in_val"), S("out_val")]),
+            ],
+        ),
+        "COMPU_VTAB_RANGE" => (
+            BLOCK,
+            &[
+                I("name"),
+                S("long_identifier"),
+                N("number_value_triples"),
+                L("value_triples", &[N("in_val_min"), N("in_val_max"), S("out_val")]),
+            ],
+        ),
+        "DEFAULT_VALUE" => (KEYWORD, &[S("display_string")]),
+        "DEFAULT_VALUE_NUMERIC" => (KEYWORD, &[N("display_value")]),
+        "UNIT" => (BLOCK, &[I("name"), S("long_identifier"), S("display"), I("unit_type")]),
+        "SI_EXPONENTS" => (
+            KEYWORD,
+            &[
+                N("length"),
+                N("mass"),
+                N("time"),
+                N("electric_current"),
+                N("temperature"),
+                N("amount_of_substance"),
+                N("luminous_intensity"),
+            ],
+        ),
+        "UNIT_CONVERSION" => (KEYWORD, &[N("gradient"), N("offset")]),
+
+        // Record layouts
+        "RECORD_LAYOUT" => (BLOCK, &[I("name")]),
+        "FNC_VALUES" => (
+            KEYWORD,
+            &[N("position"), I("datatype"), I("index_mode"), I("address_type")],
+        ),
+        "IDENTIFICATION" => (KEYWORD, POSITION_DATATYPE),
+        "AXIS_PTS_X" | "AXIS_PTS_Y" | "AXIS_PTS_Z" | "AXIS_PTS_4" | "AXIS_PTS_5" => (KEYWORD, AXIS_PTS_LAYOUT),
+        "AXIS_RESCALE_X" | "AXIS_RESCALE_Y" | "AXIS_RESCALE_Z" | "AXIS_RESCALE_4" | "AXIS_RESCALE_5" => {
+            (KEYWORD, AXIS_RESCALE_LAYOUT)
+        }
+        "NO_AXIS_PTS_X" | "NO_AXIS_PTS_Y" | "NO_AXIS_PTS_Z" | "NO_AXIS_PTS_4" | "NO_AXIS_PTS_5" | "NO_RESCALE_X"
+        | "NO_RESCALE_Y" | "NO_RESCALE_Z" | "NO_RESCALE_4" | "NO_RESCALE_5" | "SRC_ADDR_X" | "SRC_ADDR_Y"
+        | "SRC_ADDR_Z" | "SRC_ADDR_4" | "SRC_ADDR_5" | "RIP_ADDR_W" | "RIP_ADDR_X" | "RIP_ADDR_Y" | "RIP_ADDR_Z"
+        | "RIP_ADDR_4" | "RIP_ADDR_5" | "SHIFT_OP_X" | "SHIFT_OP_Y" | "SHIFT_OP_Z" | "SHIFT_OP_4" | "SHIFT_OP_5"
+        | "OFFSET_X" | "OFFSET_Y" | "OFFSET_Z" | "OFFSET_4" | "OFFSET_5" | "DIST_OP_X" | "DIST_OP_Y" | "DIST_OP_Z"
+        | "DIST_OP_4" | "DIST_OP_5" => (KEYWORD, POSITION_DATATYPE),
+        "FIX_NO_AXIS_PTS_X" | "FIX_NO_AXIS_PTS_Y" | "FIX_NO_AXIS_PTS_Z" | "FIX_NO_AXIS_PTS_4" | "FIX_NO_AXIS_PTS_5" => {
+            (KEYWORD, &[N("number_of_axis_points")])
+        }
+        "RESERVED" => (KEYWORD, &[N("position"), I("data_size")]),
+
+        // Functions, groups, frames, user rights
+        "FUNCTION" => (BLOCK, &[I("name"), S("long_identifier")]),
+        "FUNCTION_VERSION" => (KEYWORD, &[S("version_identifier")]),
+        "AR_COMPONENT" => (BLOCK, &[S("component_type")]),
+        "AR_PROTOTYPE_OF" => (KEYWORD, &[I("name")]),
+        "GROUP" => (BLOCK, &[I("group_name"), S("group_long_identifier")]),
+        "FRAME" => (BLOCK, &[I("name"), S("long_identifier"), N("scaling_unit"), N("rate")]),
+        "USER_RIGHTS" => (BLOCK, &[I("user_level_id")]),
+
+        // Variant coding
+        "VARIANT_CODING" => (BLOCK, &[]),
+        "VAR_CHARACTERISTIC" => (BLOCK, &[I("name"), L("criterion_name_list", &[I("")])]),
+        "VAR_ADDRESS" => (BLOCK, &[L("address_list", &[N("")])]),
+        "VAR_CRITERION" => (BLOCK, &[I("name"), S("long_identifier"), L("value_list", &[I("")])]),
+        "VAR_FORBIDDEN_COMB" => (BLOCK, &[L("combination", &[I("criterion_name"), I("criterion_value")])]),
+        "VAR_NAMING" => (KEYWORD, &[I("tag")]),
+        "VAR_SEPARATOR" => (KEYWORD, &[S("separator")]),
+
+        "TRANSFORMER" => (
+            BLOCK,
+            &[
+                I("name"),
+                S("version"),
+                S("dllname_32"),
+                S("dllname_64"),
+                N("timeout"),
+                I("trigger"),
+                I("inverse_transformer"),
+            ],
+        ),
+        _ => return None,
+    };
+    Some(KeywordSpec { block, fields })
+}
+
+/// Keywords that may occur several times in their parent; JSON keeps them as
+/// arrays even when there is only one.
+pub(crate) fn is_repeated(keyword: &str) -> bool {
+    matches!(
+        keyword,
+        "MODULE"
+            | "MEASUREMENT"
+            | "CHARACTERISTIC"
+            | "AXIS_PTS"
+            | "BLOB"
+            | "INSTANCE"
+            | "COMPU_METHOD"
+            | "COMPU_TAB"
+            | "COMPU_VTAB"
+            | "COMPU_VTAB_RANGE"
+            | "FUNCTION"
+            | "GROUP"
+            | "RECORD_LAYOUT"
+            | "UNIT"
+            | "FRAME"
+            | "USER_RIGHTS"
+            | "TRANSFORMER"
+            | "TYPEDEF_AXIS"
+            | "TYPEDEF_BLOB"
+            | "TYPEDEF_CHARACTERISTIC"
+            | "TYPEDEF_MEASUREMENT"
+            | "TYPEDEF_STRUCTURE"
+            | "IF_DATA"
+            | "ANNOTATION"
+            | "AXIS_DESCR"
+            | "STRUCTURE_COMPONENT"
+            | "OVERWRITE"
+            | "MEMORY_LAYOUT"
+            | "MEMORY_SEGMENT"
+            | "SYSTEM_CONSTANT"
+            | "ADDR_EPK"
+            | "CALIBRATION_METHOD"
+            | "CALIBRATION_HANDLE"
+            | "VAR_CHARACTERISTIC"
+            | "VAR_CRITERION"
+            | "VAR_FORBIDDEN_COMB"
+            | "RESERVED"
+    )
+}
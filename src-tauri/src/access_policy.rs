@@ -0,0 +1,279 @@
+use std::collections::HashSet;
+
+use a2lfile::A2lObjectName;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::subset::group_members;
+use crate::AppState;
+
+/// Selects objects by name pattern, group membership (including sub groups)
+/// and/or memory segment; all given selectors must match. Rules are applied
+/// in order, so later rules override earlier ones.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct AccessRule {
+    pattern: Option<String>,
+    group: Option<String>,
+    /// Name of a MOD_PAR MEMORY_SEGMENT that must contain the object address.
+    memory_segment: Option<String>,
+    /// CALIBRATION, NO_CALIBRATION, NOT_IN_MCD_SYSTEM or OFFLINE_CALIBRATION.
+    calibration_access: Option<String>,
+    read_only: Option<bool>,
+    guard_rails: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct PolicyChange {
+    module: String,
+    kind: String,
+    name: String,
+    field: String,
+    old: String,
+    new: String,
+}
+
+/// Flags an object must carry, as resolved from the rules.
+#[derive(Default, Clone)]
+struct Access {
+    calibration_access: Option<a2lfile::CalibrationAccessEnum>,
+    read_only: Option<bool>,
+    guard_rails: Option<bool>,
+}
+
+fn calibration_access_to_string(access: Option<a2lfile::CalibrationAccessEnum>) -> String {
+    match access {
+        Some(a2lfile::CalibrationAccessEnum::Calibration) => "CALIBRATION",
+        Some(a2lfile::CalibrationAccessEnum::NoCalibration) => "NO_CALIBRATION",
+        Some(a2lfile::CalibrationAccessEnum::NotInMcdSystem) => "NOT_IN_MCD_SYSTEM",
+        Some(a2lfile::CalibrationAccessEnum::OfflineCalibration) => "OFFLINE_CALIBRATION",
+        None => "",
+    }
+    .to_string()
+}
+
+fn string_to_calibration_access(s: &str) -> Option<a2lfile::CalibrationAccessEnum> {
+    match s.to_uppercase().as_str() {
+        "CALIBRATION" => Some(a2lfile::CalibrationAccessEnum::Calibration),
+        "NO_CALIBRATION" => Some(a2lfile::CalibrationAccessEnum::NoCalibration),
+        "NOT_IN_MCD_SYSTEM" => Some(a2lfile::CalibrationAccessEnum::NotInMcdSystem),
+        "OFFLINE_CALIBRATION" => Some(a2lfile::CalibrationAccessEnum::OfflineCalibration),
+        _ => None,
+    }
+}
+
+struct CompiledRule {
+    pattern: Option<Regex>,
+    /// Characteristics and axis points of `group`.
+    members: Option<HashSet<String>>,
+    segment: Option<(u32, u32)>,
+    access: Access,
+}
+
+fn compile(module: &a2lfile::Module, rules: &[AccessRule]) -> Result<Vec<CompiledRule>, String> {
+    rules
+        .iter()
+        .map(|rule| {
+            let pattern = rule
+                .pattern
+                .as_deref()
+                .filter(|p| !p.is_empty())
+                .map(|p| Regex::new(p).map_err(|e| format!("Invalid pattern '{p}': {e}")))
+                .transpose()?;
+            let members = match rule.group.as_deref().filter(|g| !g.is_empty()) {
+                Some(group) => {
+                    let mut seeds = Vec::new();
+                    group_members(module, group, &mut seeds)?;
+                    Some(seeds.into_iter().map(|(_, name)| name).collect())
+                }
+                None => None,
+            };
+            let segment = match rule.memory_segment.as_deref().filter(|s| !s.is_empty()) {
+                Some(name) => {
+                    let segment = module
+                        .mod_par
+                        .as_ref()
+                        .and_then(|mod_par| mod_par.memory_segment.get(name))
+                        .ok_or_else(|| format!("Memory segment '{name}' not found"))?;
+                    Some((segment.address, segment.size))
+                }
+                None => None,
+            };
+            let calibration_access = rule
+                .calibration_access
+                .as_deref()
+                .filter(|a| !a.is_empty())
+                .map(|a| string_to_calibration_access(a).ok_or_else(|| format!("Invalid calibration access: {a}")))
+                .transpose()?;
+            Ok(CompiledRule {
+                pattern,
+                members,
+                segment,
+                access: Access {
+                    calibration_access,
+                    read_only: rule.read_only,
+                    guard_rails: rule.guard_rails,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Access required for the object `name` at `address` by the last matching
+/// rules, field by field.
+fn required_access(rules: &[CompiledRule], name: &str, address: u32) -> Access {
+    let mut access = Access::default();
+    for rule in rules {
+        let matches = rule.pattern.as_ref().is_none_or(|p| p.is_match(name))
+            && rule.members.as_ref().is_none_or(|m| m.contains(name))
+            && rule
+                .segment
+                .is_none_or(|(start, size)| address >= start && u64::from(address) < u64::from(start) + u64::from(size));
+        if !matches {
+            continue;
+        }
+        if rule.access.calibration_access.is_some() {
+            access.calibration_access = rule.access.calibration_access;
+        }
+        if rule.access.read_only.is_some() {
+            access.read_only = rule.access.read_only;
+        }
+        if rule.access.guard_rails.is_some() {
+            access.guard_rails = rule.access.guard_rails;
+        }
+    }
+    access
+}
+
+/// (field, old, new) for every flag of `item` that differs from `required`.
+macro_rules! differences {
+    ($item:expr, $required:expr) => {{
+        let item = $item;
+        let required: &Access = $required;
+        let mut differences = Vec::new();
+        let current = item.calibration_access.as_ref().map(|a| a.calibration_access);
+        if required.calibration_access.is_some() && current != required.calibration_access {
+            differences.push((
+                "calibration_access",
+                calibration_access_to_string(current),
+                calibration_access_to_string(required.calibration_access),
+            ));
+        }
+        if let Some(read_only) = required.read_only.filter(|&r| r != item.read_only.is_some()) {
+            differences.push(("read_only", (!read_only).to_string(), read_only.to_string()));
+        }
+        if let Some(guard_rails) = required.guard_rails.filter(|&g| g != item.guard_rails.is_some()) {
+            differences.push(("guard_rails", (!guard_rails).to_string(), guard_rails.to_string()));
+        }
+        differences
+    }};
+}
+
+macro_rules! enforce {
+    ($item:expr, $required:expr) => {{
+        let item = $item;
+        let required: &Access = $required;
+        if required.calibration_access.is_some() {
+            item.calibration_access = required.calibration_access.map(a2lfile::CalibrationAccess::new);
+        }
+        if let Some(read_only) = required.read_only {
+            item.read_only = read_only.then(a2lfile::ReadOnly::new);
+        }
+        if let Some(guard_rails) = required.guard_rails {
+            item.guard_rails = guard_rails.then(a2lfile::GuardRails::new);
+        }
+    }};
+}
+
+/// Objects whose flags differ from `rules`, with the required access of each.
+fn evaluate(
+    a2l: &a2lfile::A2lFile,
+    rules: &[AccessRule],
+    module_name: Option<&str>,
+) -> Result<Vec<(PolicyChange, Access)>, String> {
+    let mut found = Vec::new();
+    for module in a2l.project.module.iter() {
+        if module_name.is_some_and(|name| module.get_name() != name) {
+            continue;
+        }
+        let compiled = compile(module, rules)?;
+        let mut check = |kind: &str, name: &str, required: Access, differences: Vec<(&str, String, String)>| {
+            for (field, old, new) in differences {
+                let change = PolicyChange {
+                    module: module.get_name().to_string(),
+                    kind: kind.to_string(),
+                    name: name.to_string(),
+                    field: field.to_string(),
+                    old,
+                    new,
+                };
+                found.push((change, required.clone()));
+            }
+        };
+        for c in module.characteristic.iter() {
+            let required = required_access(&compiled, c.get_name(), c.address);
+            let differences = differences!(c, &required);
+            check("Characteristic", c.get_name(), required, differences);
+        }
+        for a in module.axis_pts.iter() {
+            let required = required_access(&compiled, a.get_name(), a.address);
+            let differences = differences!(a, &required);
+            check("AxisPts", a.get_name(), required, differences);
+        }
+    }
+    Ok(found)
+}
+
+#[tauri::command]
+pub fn get_calibration_access_policy(state: tauri::State<AppState>) -> Result<Vec<AccessRule>, String> {
+    Ok(state.access_policy.lock().map_err(|_| "State lock poisoned")?.clone())
+}
+
+/// Makes `rules` the current policy and sets CALIBRATION_ACCESS, READ_ONLY and
+/// GUARD_RAILS of all characteristics and axis points accordingly.
+#[tauri::command]
+pub fn apply_calibration_access_policy(
+    rules: Vec<AccessRule>,
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<PolicyChange>, String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let found = evaluate(guard.get(doc_id.as_deref())?, &rules, module_name.as_deref())?;
+    *state.access_policy.lock().map_err(|_| "State lock poisoned")? = rules;
+    if found.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut edit = guard.edit("apply_calibration_access_policy", doc_id.as_deref())?;
+    for (change, _) in &found {
+        edit.touch(&change.module, &change.kind, &change.name);
+    }
+    let a2l = edit.a2l_mut();
+    for (change, required) in &found {
+        let Some(module) = a2l.project.module.iter_mut().find(|m| m.get_name() == change.module) else {
+            continue;
+        };
+        if change.kind == "Characteristic" {
+            if let Some(c) = module.characteristic.get_mut(&change.name) {
+                enforce!(c, required);
+            }
+        } else if let Some(a) = module.axis_pts.get_mut(&change.name) {
+            enforce!(a, required);
+        }
+    }
+    Ok(found.into_iter().map(|(change, _)| change).collect())
+}
+
+/// Objects whose flags differ from the current policy; nothing is changed.
+#[tauri::command]
+pub fn find_access_policy_violations(
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<PolicyChange>, String> {
+    let rules = state.access_policy.lock().map_err(|_| "State lock poisoned")?.clone();
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let found = evaluate(guard.get(doc_id.as_deref())?, &rules, module_name.as_deref())?;
+    Ok(found.into_iter().map(|(change, _)| change).collect())
+}
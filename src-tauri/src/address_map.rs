@@ -0,0 +1,157 @@
+use a2lfile::A2lObjectName;
+use serde::Serialize;
+
+use crate::layout::{axis_pts_size, characteristic_size, instance_size, measurement_size};
+use crate::AppState;
+
+#[derive(Serialize, Clone)]
+pub(crate) struct AddressEntry {
+    pub(crate) module: String,
+    pub(crate) kind: String,
+    pub(crate) name: String,
+    pub(crate) address: u32,
+    pub(crate) size: Option<u32>,
+    #[serde(skip)]
+    pub(crate) bit_masked: bool,
+}
+
+impl AddressEntry {
+    pub(crate) fn end(&self) -> u32 {
+        self.address.saturating_add(self.size.unwrap_or(1).max(1))
+    }
+}
+
+#[derive(Serialize)]
+struct AddressOverlap {
+    first: String,
+    second: String,
+    start: u32,
+    end: u32,
+}
+
+#[derive(Serialize)]
+struct AddressGap {
+    start: u32,
+    size: u32,
+}
+
+#[derive(Serialize)]
+pub struct AddressMap {
+    entries: Vec<AddressEntry>,
+    overlaps: Vec<AddressOverlap>,
+    gaps: Vec<AddressGap>,
+    outside_segments: Vec<String>,
+    unresolved_sizes: Vec<String>,
+}
+
+/// Every object with a fixed ECU address, sorted by address.
+pub(crate) fn collect_address_entries(a2l: &a2lfile::A2lFile) -> Vec<AddressEntry> {
+    let mut entries = Vec::new();
+    for module in a2l.project.module.iter() {
+        let module_name = module.get_name().to_string();
+        let mut push = |kind: &str, name: &str, address: u32, size: Option<u32>, bit_masked: bool| {
+            entries.push(AddressEntry {
+                module: module_name.clone(),
+                kind: kind.to_string(),
+                name: name.to_string(),
+                address,
+                size,
+                bit_masked,
+            });
+        };
+        for m in module.measurement.iter() {
+            if let Some(ecu_address) = &m.ecu_address {
+                push("Measurement", m.get_name(), ecu_address.address, measurement_size(m), m.bit_mask.is_some());
+            }
+        }
+        for c in module.characteristic.iter() {
+            push("Characteristic", c.get_name(), c.address, characteristic_size(module, c), c.bit_mask.is_some());
+        }
+        for a in module.axis_pts.iter() {
+            push("AxisPts", a.get_name(), a.address, axis_pts_size(module, a), false);
+        }
+        for b in module.blob.iter() {
+            push("Blob", b.get_name(), b.start_address, Some(b.size), false);
+        }
+        for i in module.instance.iter() {
+            push("Instance", i.get_name(), i.start_address, instance_size(module, i), false);
+        }
+    }
+    entries.sort_by(|a, b| a.address.cmp(&b.address).then_with(|| a.name.cmp(&b.name)));
+    entries
+}
+
+pub(crate) fn memory_segments(a2l: &a2lfile::A2lFile) -> Vec<(String, u32, u32)> {
+    a2l.project
+        .module
+        .iter()
+        .filter_map(|module| module.mod_par.as_ref())
+        .flat_map(|mod_par| mod_par.memory_segment.iter())
+        .map(|segment| (segment.get_name().to_string(), segment.address, segment.size))
+        .collect()
+}
+
+pub(crate) fn build_map(a2l: &a2lfile::A2lFile) -> AddressMap {
+    let entries = collect_address_entries(a2l);
+    let segments = memory_segments(a2l);
+
+    let mut overlaps = Vec::new();
+    let mut gaps = Vec::new();
+    let mut outside_segments = Vec::new();
+    let mut unresolved_sizes = Vec::new();
+
+    // Index of the entry reaching furthest so far; overlaps are reported
+    // against it so that one large object covering many small ones is found.
+    let mut furthest: Option<usize> = None;
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.size.is_none() {
+            unresolved_sizes.push(entry.name.clone());
+        }
+        if !segments.is_empty()
+            && !segments
+                .iter()
+                .any(|(_, start, size)| entry.address >= *start && entry.end() <= start.saturating_add(*size))
+        {
+            outside_segments.push(entry.name.clone());
+        }
+        if let Some(prev_index) = furthest {
+            let prev = &entries[prev_index];
+            if entry.address < prev.end() {
+                // Bit-field measurements legitimately share their container.
+                if !(prev.bit_masked && entry.bit_masked) {
+                    overlaps.push(AddressOverlap {
+                        first: prev.name.clone(),
+                        second: entry.name.clone(),
+                        start: entry.address,
+                        end: entry.end().min(prev.end()),
+                    });
+                }
+            } else if entry.address > prev.end() {
+                gaps.push(AddressGap {
+                    start: prev.end(),
+                    size: entry.address - prev.end(),
+                });
+            }
+            if entry.end() > prev.end() {
+                furthest = Some(index);
+            }
+        } else {
+            furthest = Some(index);
+        }
+    }
+
+    AddressMap {
+        entries,
+        overlaps,
+        gaps,
+        outside_segments,
+        unresolved_sizes,
+    }
+}
+
+#[tauri::command]
+pub fn build_address_map(doc_id: Option<String>, state: tauri::State<AppState>) -> Result<AddressMap, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    Ok(build_map(a2l))
+}
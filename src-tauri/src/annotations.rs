@@ -0,0 +1,156 @@
+use a2lfile::A2lObjectName;
+use serde::{Deserialize, Serialize};
+
+use crate::AppState;
+
+#[derive(Serialize, Deserialize)]
+pub struct AnnotationData {
+    label: Option<String>,
+    origin: Option<String>,
+    text: Vec<String>,
+}
+
+fn annotation_data(annotation: &a2lfile::Annotation) -> AnnotationData {
+    AnnotationData {
+        label: annotation.annotation_label.as_ref().map(|l| l.label.clone()),
+        origin: annotation.annotation_origin.as_ref().map(|o| o.origin.clone()),
+        text: annotation
+            .annotation_text
+            .as_ref()
+            .map(|t| t.annotation_text.clone())
+            .unwrap_or_default(),
+    }
+}
+
+fn apply(annotation: &mut a2lfile::Annotation, data: AnnotationData) {
+    annotation.annotation_label = data
+        .label
+        .filter(|label| !label.is_empty())
+        .map(a2lfile::AnnotationLabel::new);
+    annotation.annotation_origin = data
+        .origin
+        .filter(|origin| !origin.is_empty())
+        .map(a2lfile::AnnotationOrigin::new);
+    annotation.annotation_text = if data.text.is_empty() {
+        None
+    } else {
+        let mut text = a2lfile::AnnotationText::new();
+        text.annotation_text = data.text;
+        Some(text)
+    };
+}
+
+/// Annotation list of the object `kind`/`name`, searched across all modules.
+fn annotations<'a>(a2l: &'a a2lfile::A2lFile, kind: &str, name: &str) -> Result<&'a Vec<a2lfile::Annotation>, String> {
+    for module in a2l.project.module.iter() {
+        let found = match kind {
+            "Measurement" => module.measurement.get(name).map(|item| &item.annotation),
+            "Characteristic" => module.characteristic.get(name).map(|item| &item.annotation),
+            "AxisPts" => module.axis_pts.get(name).map(|item| &item.annotation),
+            "Function" => module.function.get(name).map(|item| &item.annotation),
+            "Group" => module.group.get(name).map(|item| &item.annotation),
+            "Blob" => module.blob.get(name).map(|item| &item.annotation),
+            "Instance" => module.instance.get(name).map(|item| &item.annotation),
+            _ => return Err(format!("Objects of kind {kind} have no annotations")),
+        };
+        if let Some(annotations) = found {
+            return Ok(annotations);
+        }
+    }
+    Err(format!("{kind} '{name}' not found"))
+}
+
+/// Mutable counterpart of `annotations`, for the edit commands.
+fn annotations_mut<'a>(
+    a2l: &'a mut a2lfile::A2lFile,
+    kind: &str,
+    name: &str,
+) -> Result<&'a mut Vec<a2lfile::Annotation>, String> {
+    for module in a2l.project.module.iter_mut() {
+        let found = match kind {
+            "Measurement" => module.measurement.get_mut(name).map(|item| &mut item.annotation),
+            "Characteristic" => module.characteristic.get_mut(name).map(|item| &mut item.annotation),
+            "AxisPts" => module.axis_pts.get_mut(name).map(|item| &mut item.annotation),
+            "Function" => module.function.get_mut(name).map(|item| &mut item.annotation),
+            "Group" => module.group.get_mut(name).map(|item| &mut item.annotation),
+            "Blob" => module.blob.get_mut(name).map(|item| &mut item.annotation),
+            "Instance" => module.instance.get_mut(name).map(|item| &mut item.annotation),
+            _ => return Err(format!("Objects of kind {kind} have no annotations")),
+        };
+        if let Some(annotations) = found {
+            return Ok(annotations);
+        }
+    }
+    Err(format!("{kind} '{name}' not found"))
+}
+
+#[tauri::command]
+pub fn list_annotations(
+    kind: String,
+    name: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<AnnotationData>, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    Ok(annotations(a2l, &kind, &name)?.iter().map(annotation_data).collect())
+}
+
+#[tauri::command]
+pub fn add_annotation(
+    kind: String,
+    name: String,
+    data: AnnotationData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<usize, String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("add_annotation", doc_id.as_deref())?;
+    edit.touch_object(&kind, &name);
+    let a2l = edit.a2l_mut();
+    let annotations = annotations_mut(a2l, &kind, &name)?;
+    let mut annotation = a2lfile::Annotation::new();
+    apply(&mut annotation, data);
+    annotations.push(annotation);
+    Ok(annotations.len() - 1)
+}
+
+#[tauri::command]
+pub fn update_annotation(
+    kind: String,
+    name: String,
+    index: usize,
+    data: AnnotationData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("update_annotation", doc_id.as_deref())?;
+    edit.touch_object(&kind, &name);
+    let a2l = edit.a2l_mut();
+    let annotation = annotations_mut(a2l, &kind, &name)?
+        .get_mut(index)
+        .ok_or_else(|| format!("{kind} '{name}' has no annotation {index}"))?;
+    apply(annotation, data);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_annotation(
+    kind: String,
+    name: String,
+    index: usize,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("delete_annotation", doc_id.as_deref())?;
+    edit.touch_object(&kind, &name);
+    let a2l = edit.a2l_mut();
+    let annotations = annotations_mut(a2l, &kind, &name)?;
+    if index >= annotations.len() {
+        return Err(format!("{kind} '{name}' has no annotation {index}"));
+    }
+    annotations.remove(index);
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use std::collections::VecDeque;
+use std::fs;
+
+use serde::Serialize;
+
+use crate::session::now_secs;
+use crate::table::csv_escape;
+use crate::AppState;
+
+/// Oldest entries are dropped beyond this many.
+const MAX_ENTRIES: usize = 50_000;
+
+/// One changed object. Values are the object's A2L text; `None` when the
+/// object was created (old) or deleted (new).
+#[derive(Serialize, Clone)]
+pub struct AuditEntry {
+    timestamp: u64,
+    /// Command whose edit made the change.
+    command: String,
+    doc_id: String,
+    module: String,
+    kind: String,
+    name: String,
+    old_value: Option<String>,
+    new_value: Option<String>,
+}
+
+#[derive(Default)]
+pub(crate) struct AuditLog {
+    entries: VecDeque<AuditEntry>,
+}
+
+pub(crate) struct AuditChange {
+    pub(crate) module: String,
+    pub(crate) kind: String,
+    pub(crate) name: String,
+    pub(crate) old_value: Option<String>,
+    pub(crate) new_value: Option<String>,
+}
+
+impl AuditLog {
+    pub(crate) fn record(&mut self, doc_id: &str, command: &str, changes: Vec<AuditChange>) {
+        let timestamp = now_secs();
+        for change in changes {
+            self.entries.push_back(AuditEntry {
+                timestamp,
+                command: command.to_string(),
+                doc_id: doc_id.to_string(),
+                module: change.module,
+                kind: change.kind,
+                name: change.name,
+                old_value: change.old_value,
+                new_value: change.new_value,
+            });
+        }
+        while self.entries.len() > MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+    }
+
+    fn entries(&self, doc_id: Option<&str>) -> Vec<AuditEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| doc_id.is_none_or(|id| entry.doc_id == id))
+            .cloned()
+            .collect()
+    }
+}
+
+fn write_csv(path: &str, entries: &[AuditEntry]) -> Result<(), String> {
+    let mut out = String::from("timestamp,command,doc_id,module,kind,name,old_value,new_value\n");
+    for entry in entries {
+        let cells = [
+            entry.timestamp.to_string(),
+            entry.command.clone(),
+            entry.doc_id.clone(),
+            entry.module.clone(),
+            entry.kind.clone(),
+            entry.name.clone(),
+            entry.old_value.clone().unwrap_or_default(),
+            entry.new_value.clone().unwrap_or_default(),
+        ];
+        out.push_str(&cells.iter().map(|c| csv_escape(c)).collect::<Vec<_>>().join(","));
+        out.push('\n');
+    }
+    fs::write(path, out).map_err(|e| e.to_string())
+}
+
+/// Recorded changes, oldest first. Without `doc_id` the changes of all
+/// documents are returned.
+#[tauri::command]
+pub fn get_change_log(doc_id: Option<String>, state: tauri::State<AppState>) -> Result<Vec<AuditEntry>, String> {
+    Ok(state.audit.lock().map_err(|_| "State lock poisoned")?.entries(doc_id.as_deref()))
+}
+
+/// Writes the change log as "csv" or "json"; returns the number of entries.
+#[tauri::command]
+pub fn export_change_log(
+    path: String,
+    format: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<usize, String> {
+    let entries = state.audit.lock().map_err(|_| "State lock poisoned")?.entries(doc_id.as_deref());
+    match format.to_lowercase().as_str() {
+        "csv" => write_csv(&path, &entries)?,
+        "json" => {
+            let text = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
+            fs::write(&path, text).map_err(|e| e.to_string())?;
+        }
+        other => return Err(format!("Unsupported change log format: {other}")),
+    }
+    Ok(entries.len())
+}
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use a2lfile::A2lObjectName;
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+
+use crate::documents::OpenedDocument;
+use crate::export_options::{render_a2l, ExportOptions};
+use crate::session::{now_secs, settings_path};
+use crate::{build_metadata, diagnostics, parse_a2l, AppState};
+
+const BACKUP_DIR: &str = "backups";
+const FAILED_EVENT: &str = "autosave-failed";
+const MIN_INTERVAL_SECS: u64 = 5;
+/// Oldest backups beyond this count are deleted after each pass.
+const MAX_BACKUPS: usize = 20;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AutosaveConfig {
+    enabled: bool,
+    interval_secs: u64,
+}
+
+impl Default for AutosaveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 60,
+        }
+    }
+}
+
+pub(crate) struct Autosave {
+    config: AutosaveConfig,
+    /// Prefix of this run's backup IDs, so a restart does not overwrite the
+    /// backups of a session that crashed.
+    session: u64,
+    /// Document revision of the last backup, keyed by document ID.
+    written: HashMap<String, u64>,
+}
+
+impl Default for Autosave {
+    fn default() -> Self {
+        Self {
+            config: AutosaveConfig::default(),
+            session: now_secs(),
+            written: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BackupInfo {
+    id: String,
+    doc_id: String,
+    original_path: Option<String>,
+    project_name: String,
+    saved_at: u64,
+}
+
+fn backup_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = settings_path(app, BACKUP_DIR)?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+fn backup_id(session: u64, doc_id: &str) -> String {
+    format!("{session}-{doc_id}")
+}
+
+fn check_backup_id(id: &str) -> Result<(), String> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(format!("Invalid backup ID: {id}"));
+    }
+    Ok(())
+}
+
+fn remove_backup(dir: &Path, id: &str) {
+    let _ = fs::remove_file(dir.join(format!("{id}.a2l")));
+    let _ = fs::remove_file(dir.join(format!("{id}.json")));
+}
+
+fn read_backups(dir: &Path) -> Vec<BackupInfo> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<BackupInfo> = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+        .filter_map(|text| serde_json::from_str(&text).ok())
+        .collect();
+    backups.sort_by(|a, b| b.saved_at.cmp(&a.saved_at));
+    backups
+}
+
+/// Writes a backup of every document changed since its last save or backup,
+/// and drops this session's backups of documents that were saved or closed.
+fn autosave_pass(app: &tauri::AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let (session, written) = {
+        let autosave = state.autosave.lock().map_err(|_| "State lock poisoned")?;
+        (autosave.session, autosave.written.clone())
+    };
+
+    // (doc ID, path, project name, rendered text, revision), rendered under the lock.
+    let mut pending = Vec::new();
+    let mut stale = Vec::new();
+    {
+        let documents = state.documents.lock().map_err(|_| "State lock poisoned")?;
+        for (id, document) in documents.iter() {
+            if document.is_dirty() && written.get(id) != Some(&document.revision) {
+                pending.push((
+                    id.clone(),
+                    document.path.clone(),
+                    document.a2l.project.get_name().to_string(),
+                    // Merged, so the backup holds included objects too.
+                    render_a2l(&document.a2l, &ExportOptions::default()),
+                    document.revision,
+                ));
+            }
+        }
+        for id in written.keys() {
+            if !documents.iter().any(|(open, document)| open == id && document.is_dirty()) {
+                stale.push(id.clone());
+            }
+        }
+    }
+
+    let dir = backup_dir(app)?;
+    let mut autosave = state.autosave.lock().map_err(|_| "State lock poisoned")?;
+    for id in stale {
+        remove_backup(&dir, &backup_id(session, &id));
+        autosave.written.remove(&id);
+    }
+    for (doc_id, original_path, project_name, text, revision) in pending {
+        let id = backup_id(session, &doc_id);
+        fs::write(dir.join(format!("{id}.a2l")), text).map_err(|e| e.to_string())?;
+        let info = BackupInfo {
+            id: id.clone(),
+            doc_id: doc_id.clone(),
+            original_path,
+            project_name,
+            saved_at: now_secs(),
+        };
+        let meta = serde_json::to_string_pretty(&info).map_err(|e| e.to_string())?;
+        fs::write(dir.join(format!("{id}.json")), meta).map_err(|e| e.to_string())?;
+        autosave.written.insert(doc_id, revision);
+    }
+    for old in read_backups(&dir).iter().skip(MAX_BACKUPS) {
+        remove_backup(&dir, &old.id);
+    }
+    Ok(())
+}
+
+/// Starts the autosave thread. The configuration is re-read every second, so
+/// interval changes take effect without a restart.
+pub(crate) fn start(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let mut elapsed = 0;
+        loop {
+            std::thread::sleep(Duration::from_secs(1));
+            elapsed += 1;
+            let config = match app.state::<AppState>().autosave.lock() {
+                Ok(autosave) => autosave.config.clone(),
+                Err(_) => return,
+            };
+            if !config.enabled || elapsed < config.interval_secs {
+                continue;
+            }
+            elapsed = 0;
+            if let Err(error) = autosave_pass(&app) {
+                let _ = app.emit(FAILED_EVENT, error);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_autosave_config(state: tauri::State<AppState>) -> Result<AutosaveConfig, String> {
+    Ok(state.autosave.lock().map_err(|_| "State lock poisoned")?.config.clone())
+}
+
+#[tauri::command]
+pub fn set_autosave_config(config: AutosaveConfig, state: tauri::State<AppState>) -> Result<(), String> {
+    if config.interval_secs < MIN_INTERVAL_SECS {
+        return Err(format!("Autosave interval must be at least {MIN_INTERVAL_SECS} seconds"));
+    }
+    state.autosave.lock().map_err(|_| "State lock poisoned")?.config = config;
+    Ok(())
+}
+
+/// Backups left on disk, newest first. Backups of earlier sessions are what
+/// remains after a crash.
+#[tauri::command]
+pub fn list_backups(app: tauri::AppHandle) -> Result<Vec<BackupInfo>, String> {
+    Ok(read_backups(&backup_dir(&app)?))
+}
+
+/// Opens a backup as a new, unsaved document pointing at the original path.
+#[tauri::command]
+pub fn recover_backup(
+    backup_id: String,
+    app: tauri::AppHandle,
+    state: tauri::State<AppState>,
+) -> Result<OpenedDocument, String> {
+    check_backup_id(&backup_id)?;
+    let dir = backup_dir(&app)?;
+    let meta = fs::read_to_string(dir.join(format!("{backup_id}.json")))
+        .map_err(|_| format!("Backup '{backup_id}' not found"))?;
+    let info: BackupInfo = serde_json::from_str(&meta).map_err(|e| e.to_string())?;
+    let contents = fs::read_to_string(dir.join(format!("{backup_id}.a2l"))).map_err(|e| e.to_string())?;
+    let (a2l, warnings) = parse_a2l(&contents, None)?;
+    let metadata = build_metadata(&a2l, warnings.len());
+
+    let mut documents = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let id = documents.open(info.original_path, a2l);
+    // The backup is not what is on disk at the original path.
+    documents.edit("recover_backup", Some(&id))?.mark_changed();
+    let document = documents.document_mut(Some(&id))?;
+    document.diagnostics = diagnostics::from_warnings(&warnings);
+    document.base = None;
+    Ok(OpenedDocument { id, metadata })
+}
+
+#[tauri::command]
+pub fn delete_backup(backup_id: String, app: tauri::AppHandle) -> Result<(), String> {
+    check_backup_id(&backup_id)?;
+    let dir = backup_dir(&app)?;
+    if !dir.join(format!("{backup_id}.json")).exists() {
+        return Err(format!("Backup '{backup_id}' not found"));
+    }
+    remove_backup(&dir, &backup_id);
+    Ok(())
+}
@@ -0,0 +1,270 @@
+use a2lfile::A2lObjectName;
+use serde::{Deserialize, Serialize};
+
+use crate::{characteristic_type_to_string, AppState};
+
+#[derive(Serialize, Deserialize)]
+pub struct FixAxisParData {
+    offset: f64,
+    shift: f64,
+    numberapo: u16,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FixAxisParDistData {
+    offset: f64,
+    distance: f64,
+    numberapo: u16,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AxisDescrData {
+    attribute: String,
+    input_quantity: String,
+    conversion: String,
+    max_axis_points: u16,
+    lower_limit: f64,
+    upper_limit: f64,
+    axis_pts_ref: Option<String>,
+    curve_axis_ref: Option<String>,
+    fix_axis_par: Option<FixAxisParData>,
+    fix_axis_par_dist: Option<FixAxisParDistData>,
+    fix_axis_par_list: Option<Vec<f64>>,
+    monotony: Option<String>,
+    format: Option<String>,
+}
+
+pub(crate) fn attribute_to_string(attribute: &a2lfile::AxisDescrAttribute) -> String {
+    match attribute {
+        a2lfile::AxisDescrAttribute::CurveAxis => "CURVE_AXIS",
+        a2lfile::AxisDescrAttribute::ComAxis => "COM_AXIS",
+        a2lfile::AxisDescrAttribute::FixAxis => "FIX_AXIS",
+        a2lfile::AxisDescrAttribute::ResAxis => "RES_AXIS",
+        a2lfile::AxisDescrAttribute::StdAxis => "STD_AXIS",
+    }
+    .to_string()
+}
+
+fn string_to_attribute(s: &str) -> Option<a2lfile::AxisDescrAttribute> {
+    match s {
+        "CURVE_AXIS" => Some(a2lfile::AxisDescrAttribute::CurveAxis),
+        "COM_AXIS" => Some(a2lfile::AxisDescrAttribute::ComAxis),
+        "FIX_AXIS" => Some(a2lfile::AxisDescrAttribute::FixAxis),
+        "RES_AXIS" => Some(a2lfile::AxisDescrAttribute::ResAxis),
+        "STD_AXIS" => Some(a2lfile::AxisDescrAttribute::StdAxis),
+        _ => None,
+    }
+}
+
+fn monotony_to_string(monotony: &a2lfile::MonotonyType) -> String {
+    match monotony {
+        a2lfile::MonotonyType::MonDecrease => "MON_DECREASE",
+        a2lfile::MonotonyType::MonIncrease => "MON_INCREASE",
+        a2lfile::MonotonyType::StrictDecrease => "STRICT_DECREASE",
+        a2lfile::MonotonyType::StrictIncrease => "STRICT_INCREASE",
+        a2lfile::MonotonyType::Monotonous => "MONOTONOUS",
+        a2lfile::MonotonyType::StrictMon => "STRICT_MON",
+        a2lfile::MonotonyType::NotMon => "NOT_MON",
+    }
+    .to_string()
+}
+
+fn string_to_monotony(s: &str) -> Option<a2lfile::MonotonyType> {
+    match s {
+        "MON_DECREASE" => Some(a2lfile::MonotonyType::MonDecrease),
+        "MON_INCREASE" => Some(a2lfile::MonotonyType::MonIncrease),
+        "STRICT_DECREASE" => Some(a2lfile::MonotonyType::StrictDecrease),
+        "STRICT_INCREASE" => Some(a2lfile::MonotonyType::StrictIncrease),
+        "MONOTONOUS" => Some(a2lfile::MonotonyType::Monotonous),
+        "STRICT_MON" => Some(a2lfile::MonotonyType::StrictMon),
+        "NOT_MON" => Some(a2lfile::MonotonyType::NotMon),
+        _ => None,
+    }
+}
+
+/// Number of AXIS_DESCR blocks a characteristic of this type has.
+fn axis_count(characteristic_type: &a2lfile::CharacteristicType) -> usize {
+    match characteristic_type {
+        a2lfile::CharacteristicType::Curve => 1,
+        a2lfile::CharacteristicType::Map => 2,
+        a2lfile::CharacteristicType::Cuboid => 3,
+        a2lfile::CharacteristicType::Cube4 => 4,
+        a2lfile::CharacteristicType::Cube5 => 5,
+        a2lfile::CharacteristicType::Ascii
+        | a2lfile::CharacteristicType::ValBlk
+        | a2lfile::CharacteristicType::Value => 0,
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+fn axis_descr_data(axis: &a2lfile::AxisDescr) -> AxisDescrData {
+    AxisDescrData {
+        attribute: attribute_to_string(&axis.attribute),
+        input_quantity: axis.input_quantity.clone(),
+        conversion: axis.conversion.clone(),
+        max_axis_points: axis.max_axis_points,
+        lower_limit: axis.lower_limit,
+        upper_limit: axis.upper_limit,
+        axis_pts_ref: axis.axis_pts_ref.as_ref().map(|r| r.axis_points.clone()),
+        curve_axis_ref: axis.curve_axis_ref.as_ref().map(|r| r.curve_axis.clone()),
+        fix_axis_par: axis.fix_axis_par.as_ref().map(|p| FixAxisParData {
+            offset: p.offset,
+            shift: p.shift,
+            numberapo: p.numberapo,
+        }),
+        fix_axis_par_dist: axis.fix_axis_par_dist.as_ref().map(|p| FixAxisParDistData {
+            offset: p.offset,
+            distance: p.distance,
+            numberapo: p.numberapo,
+        }),
+        fix_axis_par_list: axis.fix_axis_par_list.as_ref().map(|l| l.axis_pts_value_list.clone()),
+        monotony: axis.monotony.as_ref().map(|m| monotony_to_string(&m.monotony)),
+        format: axis.format.as_ref().map(|f| f.format_string.clone()),
+    }
+}
+
+/// Checks that the optional parts match the axis attribute, e.g. a FIX_AXIS
+/// needs exactly one FIX_AXIS_PAR variant and a COM_AXIS an AXIS_PTS_REF.
+fn validate(attribute: &a2lfile::AxisDescrAttribute, data: &AxisDescrData) -> Result<(), String> {
+    let fix_variants = [
+        data.fix_axis_par.is_some(),
+        data.fix_axis_par_dist.is_some(),
+        data.fix_axis_par_list.is_some(),
+    ]
+    .iter()
+    .filter(|set| **set)
+    .count();
+    let axis_pts_ref = non_empty(data.axis_pts_ref.clone()).is_some();
+    let curve_axis_ref = non_empty(data.curve_axis_ref.clone()).is_some();
+    match attribute {
+        a2lfile::AxisDescrAttribute::FixAxis if fix_variants != 1 => {
+            Err("FIX_AXIS requires exactly one of FIX_AXIS_PAR, FIX_AXIS_PAR_DIST or FIX_AXIS_PAR_LIST".to_string())
+        }
+        a2lfile::AxisDescrAttribute::ComAxis | a2lfile::AxisDescrAttribute::ResAxis if !axis_pts_ref => {
+            Err(format!("{} requires AXIS_PTS_REF", attribute_to_string(attribute)))
+        }
+        a2lfile::AxisDescrAttribute::CurveAxis if !curve_axis_ref => Err("CURVE_AXIS requires CURVE_AXIS_REF".to_string()),
+        a2lfile::AxisDescrAttribute::FixAxis => Ok(()),
+        _ if fix_variants > 0 => Err("FIX_AXIS_PAR is only allowed for FIX_AXIS".to_string()),
+        _ => Ok(()),
+    }
+}
+
+#[tauri::command]
+pub fn get_axis_descr(
+    characteristic: String,
+    index: usize,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<AxisDescrData, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    for module in a2l.project.module.iter() {
+        if let Some(c) = module.characteristic.iter().find(|c| c.get_name() == characteristic) {
+            return c
+                .axis_descr
+                .get(index)
+                .map(axis_descr_data)
+                .ok_or_else(|| format!("Characteristic '{characteristic}' has no axis {index}"));
+        }
+    }
+    Err(format!("Characteristic '{}' not found", characteristic))
+}
+
+/// Replaces axis `index` of a characteristic, or appends a new axis when
+/// `index` equals the current axis count.
+#[tauri::command]
+pub fn update_axis_descr(
+    characteristic: String,
+    index: usize,
+    data: AxisDescrData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let attribute = string_to_attribute(&data.attribute)
+        .ok_or_else(|| format!("Invalid axis attribute: {}", data.attribute))?;
+    let monotony = match non_empty(data.monotony.clone()) {
+        Some(m) => Some(string_to_monotony(&m).ok_or_else(|| format!("Invalid monotony: {m}"))?),
+        None => None,
+    };
+    validate(&attribute, &data)?;
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("update_axis_descr", doc_id.as_deref())?;
+    edit.touch_object("Characteristic", &characteristic);
+    let a2l = edit.a2l_mut();
+    for module in a2l.project.module.iter_mut() {
+        if let Some(c) = module.characteristic.iter_mut().find(|c| c.get_name() == characteristic) {
+            if index > c.axis_descr.len() {
+                return Err(format!("Characteristic '{characteristic}' has no axis {index}"));
+            }
+            if index == c.axis_descr.len() {
+                let count = axis_count(&c.characteristic_type);
+                if index >= count {
+                    return Err(format!(
+                        "A {} characteristic has {count} axes",
+                        characteristic_type_to_string(&c.characteristic_type)
+                    ));
+                }
+                c.axis_descr.push(a2lfile::AxisDescr::new(
+                    attribute.clone(),
+                    String::new(),
+                    String::new(),
+                    0,
+                    0.0,
+                    0.0,
+                ));
+            }
+            let axis = &mut c.axis_descr[index];
+            axis.attribute = attribute;
+            axis.input_quantity = data.input_quantity;
+            axis.conversion = data.conversion;
+            axis.max_axis_points = data.max_axis_points;
+            axis.lower_limit = data.lower_limit;
+            axis.upper_limit = data.upper_limit;
+            axis.axis_pts_ref = non_empty(data.axis_pts_ref).map(a2lfile::AxisPtsRef::new);
+            axis.curve_axis_ref = non_empty(data.curve_axis_ref).map(a2lfile::CurveAxisRef::new);
+            axis.fix_axis_par = data
+                .fix_axis_par
+                .map(|p| a2lfile::FixAxisPar::new(p.offset, p.shift, p.numberapo));
+            axis.fix_axis_par_dist = data
+                .fix_axis_par_dist
+                .map(|p| a2lfile::FixAxisParDist::new(p.offset, p.distance, p.numberapo));
+            axis.fix_axis_par_list = data.fix_axis_par_list.map(|values| {
+                let mut list = a2lfile::FixAxisParList::new();
+                list.axis_pts_value_list = values;
+                list
+            });
+            axis.monotony = monotony.map(a2lfile::Monotony::new);
+            axis.format = non_empty(data.format).map(a2lfile::Format::new);
+            return Ok(());
+        }
+    }
+    Err(format!("Characteristic '{}' not found", characteristic))
+}
+
+#[tauri::command]
+pub fn delete_axis_descr(
+    characteristic: String,
+    index: usize,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("delete_axis_descr", doc_id.as_deref())?;
+    edit.touch_object("Characteristic", &characteristic);
+    let a2l = edit.a2l_mut();
+    for module in a2l.project.module.iter_mut() {
+        if let Some(c) = module.characteristic.iter_mut().find(|c| c.get_name() == characteristic) {
+            if index >= c.axis_descr.len() {
+                return Err(format!("Characteristic '{characteristic}' has no axis {index}"));
+            }
+            c.axis_descr.remove(index);
+            return Ok(());
+        }
+    }
+    Err(format!("Characteristic '{}' not found", characteristic))
+}
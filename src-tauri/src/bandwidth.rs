@@ -0,0 +1,158 @@
+use a2lfile::A2lObjectName;
+use serde::{Deserialize, Serialize};
+
+use crate::layout::measurement_size;
+use crate::AppState;
+
+#[derive(Deserialize)]
+pub struct RasterInput {
+    name: String,
+    /// Cycle time in milliseconds.
+    period_ms: f64,
+    measurements: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BandwidthOptions {
+    /// Usable bus throughput in bytes per second.
+    bus_bytes_per_second: f64,
+    /// Largest DTO (data transfer object) the transport carries, e.g. 8 on CAN.
+    max_dto: u32,
+    /// Per-DTO protocol/framing overhead in bytes on top of `max_dto`.
+    #[serde(default)]
+    frame_overhead: u32,
+    max_odt_per_raster: Option<u32>,
+    /// Fraction of the bus a single raster may occupy, defaults to 1.0.
+    max_raster_share: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct RasterLoad {
+    name: String,
+    period_ms: f64,
+    signal_count: usize,
+    payload_bytes: u32,
+    odt_count: u32,
+    bytes_per_second: f64,
+    bus_share: f64,
+    overloaded: bool,
+    missing_measurements: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct BandwidthPlan {
+    rasters: Vec<RasterLoad>,
+    total_bytes_per_second: f64,
+    bus_utilization: f64,
+    bus_overloaded: bool,
+}
+
+/// Converts a FRAME's SCALING_UNIT/RATE pair into milliseconds. Only the
+/// time-based scaling units (codes 0..=10) can be planned.
+fn frame_period_ms(scaling_unit: u16, rate: u32) -> Option<f64> {
+    let unit_ms = match scaling_unit {
+        0 => 0.001,
+        1 => 0.01,
+        2 => 0.1,
+        3 => 1.0,
+        4 => 10.0,
+        5 => 100.0,
+        6 => 1_000.0,
+        7 => 10_000.0,
+        8 => 60_000.0,
+        9 => 3_600_000.0,
+        10 => 86_400_000.0,
+        _ => return None,
+    };
+    Some(unit_ms * f64::from(rate))
+}
+
+fn rasters_from_frames(a2l: &a2lfile::A2lFile) -> Vec<RasterInput> {
+    a2l.project
+        .module
+        .iter()
+        .flat_map(|module| module.frame.iter())
+        .filter_map(|frame| {
+            Some(RasterInput {
+                name: frame.get_name().to_string(),
+                period_ms: frame_period_ms(frame.scaling_unit, frame.rate)?,
+                measurements: frame
+                    .frame_measurement
+                    .as_ref()
+                    .map(|fm| fm.identifier_list.clone())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn plan(a2l: &a2lfile::A2lFile, rasters: Vec<RasterInput>, options: &BandwidthOptions) -> Result<BandwidthPlan, String> {
+    if options.max_dto < 2 {
+        return Err("max_dto must leave room for the packet identifier".to_string());
+    }
+    let odt_payload = options.max_dto - 1;
+    let max_share = options.max_raster_share.unwrap_or(1.0);
+
+    let mut loads = Vec::with_capacity(rasters.len());
+    for raster in rasters {
+        if raster.period_ms <= 0.0 {
+            return Err(format!("Raster '{}' has a non-positive period", raster.name));
+        }
+        let mut payload_bytes = 0;
+        let mut missing_measurements = Vec::new();
+        for name in &raster.measurements {
+            let size = a2l
+                .project
+                .module
+                .iter()
+                .find_map(|module| module.measurement.get(name))
+                .and_then(measurement_size);
+            match size {
+                Some(size) => payload_bytes += size,
+                None => missing_measurements.push(name.clone()),
+            }
+        }
+        let odt_count = payload_bytes.div_ceil(odt_payload);
+        let per_second = 1_000.0 / raster.period_ms;
+        let bytes_per_second = per_second * f64::from(odt_count) * f64::from(options.max_dto + options.frame_overhead);
+        let bus_share = bytes_per_second / options.bus_bytes_per_second;
+        let overloaded = bus_share > max_share
+            || options.max_odt_per_raster.is_some_and(|max| odt_count > max);
+        loads.push(RasterLoad {
+            name: raster.name,
+            period_ms: raster.period_ms,
+            signal_count: raster.measurements.len(),
+            payload_bytes,
+            odt_count,
+            bytes_per_second,
+            bus_share,
+            overloaded,
+            missing_measurements,
+        });
+    }
+
+    let total_bytes_per_second: f64 = loads.iter().map(|load| load.bytes_per_second).sum();
+    let bus_utilization = total_bytes_per_second / options.bus_bytes_per_second;
+    Ok(BandwidthPlan {
+        rasters: loads,
+        total_bytes_per_second,
+        bus_utilization,
+        bus_overloaded: bus_utilization > 1.0,
+    })
+}
+
+#[tauri::command]
+pub fn plan_raster_bandwidth(
+    rasters: Option<Vec<RasterInput>>,
+    options: BandwidthOptions,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<BandwidthPlan, String> {
+    if options.bus_bytes_per_second <= 0.0 {
+        return Err("Bus bandwidth must be positive".to_string());
+    }
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let rasters = rasters.unwrap_or_else(|| rasters_from_frames(a2l));
+    plan(a2l, rasters, &options)
+}
@@ -0,0 +1,5 @@
+// Headless command line interface sharing the backend logic of the GUI.
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    std::process::exit(opent_a2l_forge_lib::cli::main(args))
+}
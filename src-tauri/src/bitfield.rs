@@ -0,0 +1,185 @@
+use a2lfile::A2lObjectName;
+use serde::{Deserialize, Serialize};
+
+use crate::layout::datatype_size;
+use crate::references::name_taken;
+use crate::{build_metadata, collect_core_entities, find_module_mut, AppState, EntityUpdateResult};
+
+/// BIT_OPERATION of a measurement. At most one of the shifts may be set.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct BitOperationData {
+    left_shift: Option<u32>,
+    right_shift: Option<u32>,
+    sign_extend: bool,
+}
+
+impl BitOperationData {
+    pub(crate) fn from_a2l(bit_operation: &a2lfile::BitOperation) -> Self {
+        Self {
+            left_shift: bit_operation.left_shift.as_ref().map(|s| s.bitcount),
+            right_shift: bit_operation.right_shift.as_ref().map(|s| s.bitcount),
+            sign_extend: bit_operation.sign_extend.is_some(),
+        }
+    }
+
+    /// `None` when the operation does nothing.
+    pub(crate) fn to_a2l(&self) -> Result<Option<a2lfile::BitOperation>, String> {
+        if self.left_shift.is_some() && self.right_shift.is_some() {
+            return Err("BIT_OPERATION allows either LEFT_SHIFT or RIGHT_SHIFT, not both".to_string());
+        }
+        if let Some(count) = self.left_shift.or(self.right_shift).filter(|&count| count > 63) {
+            return Err(format!("Shift count {count} is out of range 0..63"));
+        }
+        if self.left_shift.is_none() && self.right_shift.is_none() && !self.sign_extend {
+            return Ok(None);
+        }
+        let mut bit_operation = a2lfile::BitOperation::new();
+        bit_operation.left_shift = self.left_shift.map(a2lfile::LeftShift::new);
+        bit_operation.right_shift = self.right_shift.map(a2lfile::RightShift::new);
+        bit_operation.sign_extend = self.sign_extend.then(a2lfile::SignExtend::new);
+        Ok(Some(bit_operation))
+    }
+}
+
+/// One bit-field of a register, counted from the least significant bit.
+#[derive(Deserialize)]
+pub struct BitField {
+    name: String,
+    bit_offset: u32,
+    bit_size: u32,
+    #[serde(default)]
+    signed: bool,
+    #[serde(default)]
+    long_identifier: String,
+    #[serde(default)]
+    conversion: Option<String>,
+}
+
+fn register_datatype(size: u64) -> Option<a2lfile::DataType> {
+    match size {
+        1 => Some(a2lfile::DataType::Ubyte),
+        2 => Some(a2lfile::DataType::Uword),
+        4 => Some(a2lfile::DataType::Ulong),
+        8 => Some(a2lfile::DataType::AUint64),
+        _ => None,
+    }
+}
+
+fn field_datatype(register: &a2lfile::DataType, signed: bool) -> a2lfile::DataType {
+    use a2lfile::DataType::*;
+    match (register, signed) {
+        (Ubyte | Sbyte, false) => Ubyte,
+        (Ubyte | Sbyte, true) => Sbyte,
+        (Uword | Sword, false) => Uword,
+        (Uword | Sword, true) => Sword,
+        (Ulong | Slong, false) => Ulong,
+        (Ulong | Slong, true) => Slong,
+        (_, false) => AUint64,
+        (_, true) => AInt64,
+    }
+}
+
+/// Creates one measurement per bit-field of `parent_symbol`. The register is an
+/// existing measurement of the module or, failing that, a symbol of the loaded
+/// ELF. Each field gets BIT_MASK, a RIGHT_SHIFT by its offset and SIGN_EXTEND
+/// when signed, with limits covering the raw field range.
+#[tauri::command]
+pub fn create_bitfield_measurements(
+    parent_symbol: String,
+    fields: Vec<BitField>,
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<EntityUpdateResult, String> {
+    if fields.is_empty() {
+        return Err("No bit-fields given".to_string());
+    }
+    let elf_symbol = state
+        .elf
+        .lock()
+        .map_err(|_| "State lock poisoned")?
+        .as_ref()
+        .and_then(|index| index.symbols().iter().find(|s| s.name == parent_symbol).cloned());
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("create_bitfield_measurements", doc_id.as_deref())?;
+    edit.touch_target_module(module_name.as_deref())?;
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+
+    // Template the fields are cloned from: address, byte order and register width.
+    let register = match module.measurement.get(&parent_symbol) {
+        Some(parent) => {
+            let mut register = a2lfile::Measurement::new(String::new(), parent.datatype);
+            register.ecu_address = parent.ecu_address.clone();
+            register.ecu_address_extension = parent.ecu_address_extension.clone();
+            register.byte_order = parent.byte_order.clone();
+            register.symbol_link = parent.symbol_link.clone();
+            register
+        }
+        None => {
+            let symbol = elf_symbol.ok_or_else(|| format!("Symbol '{parent_symbol}' not found in module or ELF"))?;
+            let datatype = register_datatype(symbol.size)
+                .ok_or_else(|| format!("Symbol '{parent_symbol}' has size {}, not a register", symbol.size))?;
+            let mut register = a2lfile::Measurement::new(String::new(), datatype);
+            register.ecu_address = Some(a2lfile::EcuAddress::new(symbol.address as u32));
+            register.symbol_link = Some(a2lfile::SymbolLink::new(parent_symbol.clone(), 0));
+            register
+        }
+    };
+    if matches!(
+        register.datatype,
+        a2lfile::DataType::Float16Ieee | a2lfile::DataType::Float32Ieee | a2lfile::DataType::Float64Ieee
+    ) {
+        return Err(format!("Register '{parent_symbol}' has a floating point data type"));
+    }
+
+    let width = datatype_size(&register.datatype) * 8;
+    let mut errors = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let end = field.bit_offset.checked_add(field.bit_size);
+        if field.bit_size == 0 || field.bit_offset >= width || end.is_none_or(|end| end > width) {
+            errors.push(format!(
+                "{}: {} bits at offset {} do not fit a {width} bit register",
+                field.name, field.bit_size, field.bit_offset
+            ));
+        }
+        if name_taken(module, "Measurement", &field.name) || fields[..index].iter().any(|f| f.name == field.name) {
+            errors.push(format!("{}: name is already used", field.name));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
+
+    for field in fields {
+        let mut m = register.clone();
+        m.set_name(field.name);
+        m.long_identifier = field.long_identifier;
+        m.datatype = field_datatype(&register.datatype, field.signed);
+        m.conversion = field.conversion.unwrap_or_else(|| "NO_COMPU_METHOD".to_string());
+        m.resolution = 1;
+        m.accuracy = 0.0;
+        let mask = (u64::MAX >> (64 - field.bit_size)) << field.bit_offset;
+        m.bit_mask = Some(a2lfile::BitMask::new(mask));
+        m.bit_operation = BitOperationData {
+            left_shift: None,
+            right_shift: (field.bit_offset > 0).then_some(field.bit_offset),
+            sign_extend: field.signed,
+        }
+        .to_a2l()?;
+        let bits = field.bit_size as i32;
+        (m.lower_limit, m.upper_limit) = if field.signed {
+            (-(2f64.powi(bits - 1)), 2f64.powi(bits - 1) - 1.0)
+        } else {
+            (0.0, 2f64.powi(bits) - 1.0)
+        };
+        module.measurement.push(m);
+    }
+
+    Ok(EntityUpdateResult {
+        metadata: build_metadata(a2l, 0),
+        entities: collect_core_entities(a2l),
+    })
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::layout::{axis_pts_size, characteristic_size};
+use crate::session::settings_path;
+use crate::AppState;
+
+const BUDGETS_FILE: &str = "budgets.json";
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct BudgetConfig {
+    max_objects: Option<usize>,
+    #[serde(default)]
+    max_objects_per_kind: HashMap<String, usize>,
+    max_file_size: Option<usize>,
+    max_calibration_ram: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct BudgetCheck {
+    name: String,
+    limit: u64,
+    actual: u64,
+    passed: bool,
+}
+
+#[derive(Serialize)]
+pub struct BudgetReport {
+    passed: bool,
+    checks: Vec<BudgetCheck>,
+}
+
+fn object_counts(a2l: &a2lfile::A2lFile) -> HashMap<&'static str, usize> {
+    let mut counts = HashMap::new();
+    for module in a2l.project.module.iter() {
+        *counts.entry("Measurement").or_default() += module.measurement.len();
+        *counts.entry("Characteristic").or_default() += module.characteristic.len();
+        *counts.entry("AxisPts").or_default() += module.axis_pts.len();
+        *counts.entry("CompuMethod").or_default() += module.compu_method.len();
+        *counts.entry("CompuTab").or_default() += module.compu_tab.len();
+        *counts.entry("CompuVtab").or_default() += module.compu_vtab.len();
+        *counts.entry("CompuVtabRange").or_default() += module.compu_vtab_range.len();
+        *counts.entry("RecordLayout").or_default() += module.record_layout.len();
+        *counts.entry("Function").or_default() += module.function.len();
+        *counts.entry("Group").or_default() += module.group.len();
+        *counts.entry("Unit").or_default() += module.unit.len();
+        *counts.entry("Frame").or_default() += module.frame.len();
+        *counts.entry("Blob").or_default() += module.blob.len();
+        *counts.entry("Instance").or_default() += module.instance.len();
+    }
+    counts
+}
+
+fn calibration_ram(a2l: &a2lfile::A2lFile) -> u64 {
+    let mut total = 0u64;
+    for module in a2l.project.module.iter() {
+        for c in module.characteristic.iter() {
+            total += u64::from(characteristic_size(module, c).unwrap_or(0));
+        }
+        for a in module.axis_pts.iter() {
+            total += u64::from(axis_pts_size(module, a).unwrap_or(0));
+        }
+    }
+    total
+}
+
+fn check(name: impl ToString, limit: u64, actual: u64) -> BudgetCheck {
+    BudgetCheck {
+        name: name.to_string(),
+        limit,
+        actual,
+        passed: actual <= limit,
+    }
+}
+
+pub(crate) fn evaluate(a2l: &a2lfile::A2lFile, config: &BudgetConfig) -> BudgetReport {
+    let counts = object_counts(a2l);
+    let mut checks = Vec::new();
+
+    if let Some(limit) = config.max_objects {
+        let total: usize = counts.values().sum();
+        checks.push(check("Total objects", limit as u64, total as u64));
+    }
+    let mut per_kind: Vec<_> = config.max_objects_per_kind.iter().collect();
+    per_kind.sort();
+    for (kind, limit) in per_kind {
+        let actual = counts.get(kind.as_str()).copied().unwrap_or(0);
+        checks.push(check(format!("{kind} count"), *limit as u64, actual as u64));
+    }
+    if let Some(limit) = config.max_file_size {
+        let size = a2l.write_to_string().len();
+        checks.push(check("A2L file size", limit as u64, size as u64));
+    }
+    if let Some(limit) = config.max_calibration_ram {
+        checks.push(check("Calibration RAM", limit, calibration_ram(a2l)));
+    }
+
+    BudgetReport {
+        passed: checks.iter().all(|check| check.passed),
+        checks,
+    }
+}
+
+fn load_config(app: &tauri::AppHandle) -> Result<BudgetConfig, String> {
+    let path = settings_path(app, BUDGETS_FILE)?;
+    match fs::read_to_string(&path) {
+        Ok(text) => serde_json::from_str(&text).map_err(|e| format!("{BUDGETS_FILE}: {e}")),
+        Err(_) => Ok(BudgetConfig::default()),
+    }
+}
+
+#[tauri::command]
+pub fn get_budget_config(app: tauri::AppHandle) -> Result<BudgetConfig, String> {
+    load_config(&app)
+}
+
+#[tauri::command]
+pub fn set_budget_config(config: BudgetConfig, app: tauri::AppHandle) -> Result<(), String> {
+    let path = settings_path(&app, BUDGETS_FILE)?;
+    let text = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    fs::write(path, text).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn evaluate_budgets(
+    doc_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<AppState>,
+) -> Result<BudgetReport, String> {
+    let config = load_config(&app)?;
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    Ok(evaluate(a2l, &config))
+}
@@ -0,0 +1,181 @@
+use std::collections::{BTreeMap, HashSet};
+
+use a2lfile::A2lObjectName;
+use serde::Serialize;
+
+use crate::references::{for_each_reference, is_null_reference, target_candidates};
+use crate::AppState;
+
+const CLEANUP_KINDS: &[&str] = &[
+    "CompuMethod",
+    "CompuTab",
+    "CompuVtab",
+    "CompuVtabRange",
+    "RecordLayout",
+    "Unit",
+    "TypedefStructure",
+    "TypedefMeasurement",
+    "TypedefCharacteristic",
+    "TypedefAxis",
+    "TypedefBlob",
+    "Group",
+];
+
+#[derive(Serialize)]
+pub struct UnusedObject {
+    module: String,
+    kind: String,
+    name: String,
+}
+
+fn unused_in_module(module: &a2lfile::Module, kinds: &[&str]) -> Vec<(String, String)> {
+    let mut used: HashSet<(String, String)> = HashSet::new();
+    for_each_reference(module, &mut |source_kind, source_name, _field, target_kind, target| {
+        // A self reference (e.g. a group listing itself) does not keep it alive.
+        let self_reference = target == source_name && target_candidates(target_kind).contains(&source_kind);
+        if is_null_reference(target) || self_reference {
+            return;
+        }
+        for kind in target_candidates(target_kind) {
+            used.insert((kind.to_string(), target.to_string()));
+        }
+    });
+
+    let mut unused = Vec::new();
+    let mut check = |kind: &str, name: &str| {
+        if kinds.contains(&kind) && !used.contains(&(kind.to_string(), name.to_string())) {
+            unused.push((kind.to_string(), name.to_string()));
+        }
+    };
+    for item in module.compu_method.iter() {
+        check("CompuMethod", item.get_name());
+    }
+    for item in module.compu_tab.iter() {
+        check("CompuTab", item.get_name());
+    }
+    for item in module.compu_vtab.iter() {
+        check("CompuVtab", item.get_name());
+    }
+    for item in module.compu_vtab_range.iter() {
+        check("CompuVtabRange", item.get_name());
+    }
+    for item in module.record_layout.iter() {
+        check("RecordLayout", item.get_name());
+    }
+    for item in module.unit.iter() {
+        check("Unit", item.get_name());
+    }
+    for item in module.typedef_structure.iter() {
+        check("TypedefStructure", item.get_name());
+    }
+    for item in module.typedef_measurement.iter() {
+        check("TypedefMeasurement", item.get_name());
+    }
+    for item in module.typedef_characteristic.iter() {
+        check("TypedefCharacteristic", item.get_name());
+    }
+    for item in module.typedef_axis.iter() {
+        check("TypedefAxis", item.get_name());
+    }
+    for item in module.typedef_blob.iter() {
+        check("TypedefBlob", item.get_name());
+    }
+    // Root groups are entry points of the group tree and always in use.
+    for item in module.group.iter().filter(|g| g.root.is_none()) {
+        check("Group", item.get_name());
+    }
+    unused
+}
+
+pub(crate) fn remove_object(module: &mut a2lfile::Module, kind: &str, name: &str) {
+    match kind {
+        "Measurement" => module.measurement.retain(|item| item.get_name() != name),
+        "Characteristic" => module.characteristic.retain(|item| item.get_name() != name),
+        "AxisPts" => module.axis_pts.retain(|item| item.get_name() != name),
+        "Blob" => module.blob.retain(|item| item.get_name() != name),
+        "Instance" => module.instance.retain(|item| item.get_name() != name),
+        "Function" => module.function.retain(|item| item.get_name() != name),
+        "Frame" => module.frame.retain(|item| item.get_name() != name),
+        "CompuMethod" => module.compu_method.retain(|item| item.get_name() != name),
+        "CompuTab" => module.compu_tab.retain(|item| item.get_name() != name),
+        "CompuVtab" => module.compu_vtab.retain(|item| item.get_name() != name),
+        "CompuVtabRange" => module.compu_vtab_range.retain(|item| item.get_name() != name),
+        "RecordLayout" => module.record_layout.retain(|item| item.get_name() != name),
+        "Unit" => module.unit.retain(|item| item.get_name() != name),
+        "TypedefStructure" => module.typedef_structure.retain(|item| item.get_name() != name),
+        "TypedefMeasurement" => module.typedef_measurement.retain(|item| item.get_name() != name),
+        "TypedefCharacteristic" => module.typedef_characteristic.retain(|item| item.get_name() != name),
+        "TypedefAxis" => module.typedef_axis.retain(|item| item.get_name() != name),
+        "TypedefBlob" => module.typedef_blob.retain(|item| item.get_name() != name),
+        "Group" => module.group.retain(|item| item.get_name() != name),
+        _ => {}
+    }
+}
+
+fn selected_kinds(kinds: &Option<Vec<String>>) -> Result<Vec<&'static str>, String> {
+    match kinds {
+        None => Ok(CLEANUP_KINDS.to_vec()),
+        Some(kinds) => kinds
+            .iter()
+            .map(|kind| {
+                CLEANUP_KINDS
+                    .iter()
+                    .copied()
+                    .find(|candidate| candidate == kind)
+                    .ok_or_else(|| format!("Unsupported kind {kind}"))
+            })
+            .collect(),
+    }
+}
+
+#[tauri::command]
+pub fn find_unused_objects(
+    kinds: Option<Vec<String>>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<UnusedObject>, String> {
+    let kinds = selected_kinds(&kinds)?;
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let mut unused = Vec::new();
+    for module in a2l.project.module.iter() {
+        for (kind, name) in unused_in_module(module, &kinds) {
+            unused.push(UnusedObject {
+                module: module.get_name().to_string(),
+                kind,
+                name,
+            });
+        }
+    }
+    Ok(unused)
+}
+
+/// Deletes unreferenced objects and returns the number removed per kind.
+/// Runs until nothing changes, since removing e.g. a COMPU_METHOD can leave
+/// its COMPU_VTAB and UNIT unreferenced as well.
+#[tauri::command]
+pub fn remove_unused_objects(
+    kinds: Option<Vec<String>>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<BTreeMap<String, usize>, String> {
+    let kinds = selected_kinds(&kinds)?;
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("remove_unused_objects", doc_id.as_deref())?;
+    edit.touch_all();
+    let a2l = edit.a2l_mut();
+    let mut removed = BTreeMap::new();
+    for module in a2l.project.module.iter_mut() {
+        loop {
+            let unused = unused_in_module(module, &kinds);
+            if unused.is_empty() {
+                break;
+            }
+            for (kind, name) in unused {
+                remove_object(module, &kind, &name);
+                *removed.entry(kind).or_insert(0) += 1;
+            }
+        }
+    }
+    Ok(removed)
+}
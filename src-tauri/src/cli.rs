@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::a2l_json::{block_tree, is_yaml, render_items, serialize_document, Item};
+use crate::export_options::{render_a2l, ExportOptions};
+use crate::references::{check_module_references, ReferenceConfig};
+use crate::symbol_links::check_links;
+use crate::{diagnostics, load_a2l_file, read_elf_symbols, symbol_sources, ElfSymbol};
+
+const USAGE: &str = "\
+Usage: a2lforge <command> [arguments]
+
+Commands:
+  validate <file.a2l> [--strict] [--allow-cross-module]
+      Parse the file and check references. --strict also fails on parser warnings.
+  diff <old.a2l> <new.a2l>
+      List added, removed and changed objects per module.
+  update-addresses <file.a2l> --elf <symbols> [--module <name>] [--output <out.a2l>]
+      Rewrite addresses from SYMBOL_LINKs; <symbols> may be an ELF, map or PDB file.
+  export-json <file.a2l> <out.json|out.yaml>
+      Write the full object model as JSON or YAML.
+
+Reports are printed as JSON on stdout. Exit code 1 means issues were found,
+2 means the command failed.";
+
+struct Args {
+    positional: Vec<String>,
+    options: BTreeMap<String, Option<String>>,
+}
+
+impl Args {
+    /// `--name value` options; `flags` lists the options that take no value.
+    fn parse(args: &[String], flags: &[&str]) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut options = BTreeMap::new();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if let Some(name) = arg.strip_prefix("--") {
+                let value = if flags.contains(&name) {
+                    None
+                } else {
+                    Some(iter.next().ok_or_else(|| format!("Missing value for --{name}"))?.clone())
+                };
+                options.insert(name.to_string(), value);
+            } else {
+                positional.push(arg.clone());
+            }
+        }
+        Ok(Self { positional, options })
+    }
+
+    fn positional(&self, index: usize, what: &str) -> Result<&str, String> {
+        self.positional
+            .get(index)
+            .map(String::as_str)
+            .ok_or_else(|| format!("Missing {what}"))
+    }
+
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).and_then(|value| value.as_deref())
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+}
+
+fn load(path: &str) -> Result<(a2lfile::A2lFile, Vec<String>), String> {
+    load_a2l_file(path, None).map_err(|e| format!("{path}: {e}"))
+}
+
+fn read_symbols(path: &str) -> Result<Vec<ElfSymbol>, String> {
+    let extension = Path::new(path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "map" | "pdb" => symbol_sources::read_symbol_file(path, None),
+        _ => read_elf_symbols(path),
+    }
+}
+
+fn print_json(value: &impl Serialize) -> Result<(), String> {
+    println!("{}", serde_json::to_string_pretty(value).map_err(|e| e.to_string())?);
+    Ok(())
+}
+
+fn validate(args: &Args) -> Result<bool, String> {
+    #[derive(Serialize)]
+    struct ValidationReport {
+        diagnostics: Vec<diagnostics::Diagnostic>,
+        reference_issues: Vec<crate::references::ReferenceIssue>,
+    }
+
+    let (a2l, warnings) = load(args.positional(0, "A2L file")?)?;
+    let config = ReferenceConfig {
+        allow_cross_module: args.flag("allow-cross-module"),
+    };
+    let report = ValidationReport {
+        diagnostics: diagnostics::from_warnings(&warnings),
+        reference_issues: check_module_references(&a2l, &config),
+    };
+    print_json(&report)?;
+    Ok(report.reference_issues.is_empty() && (!args.flag("strict") || report.diagnostics.is_empty()))
+}
+
+/// Objects of every module, keyed by module name and `KEYWORD name`, rendered
+/// as normalized A2L text.
+fn module_objects(a2l: &a2lfile::A2lFile) -> Result<BTreeMap<String, BTreeMap<String, String>>, String> {
+    fn blocks<'a>(items: &'a [Item], keyword: &'a str) -> impl Iterator<Item = &'a crate::a2l_json::Block> {
+        items.iter().filter_map(move |item| match item {
+            Item::Block(block) if block.block == keyword => Some(block),
+            _ => None,
+        })
+    }
+    fn first_token(items: &[Item]) -> String {
+        match items.first() {
+            Some(Item::Token(token)) => token.clone(),
+            _ => String::new(),
+        }
+    }
+
+    let tree = block_tree(a2l)?;
+    let mut modules = BTreeMap::new();
+    for project in blocks(&tree, "PROJECT") {
+        for module in blocks(&project.items, "MODULE") {
+            let mut objects = BTreeMap::new();
+            for item in &module.items {
+                let Item::Block(block) = item else { continue };
+                let mut text = String::new();
+                render_items(std::slice::from_ref(item), 0, &mut text);
+                objects.insert(format!("{} {}", block.block, first_token(&block.items)), text);
+            }
+            modules.insert(first_token(&module.items), objects);
+        }
+    }
+    Ok(modules)
+}
+
+fn diff(args: &Args) -> Result<bool, String> {
+    #[derive(Serialize, Default)]
+    struct ModuleDiff {
+        added: Vec<String>,
+        removed: Vec<String>,
+        changed: Vec<String>,
+    }
+
+    let (old, _) = load(args.positional(0, "old A2L file")?)?;
+    let (new, _) = load(args.positional(1, "new A2L file")?)?;
+    let old = module_objects(&old)?;
+    let new = module_objects(&new)?;
+    let empty = BTreeMap::new();
+
+    let mut report: BTreeMap<String, ModuleDiff> = BTreeMap::new();
+    for module in old.keys().chain(new.keys()) {
+        if report.contains_key(module) {
+            continue;
+        }
+        let before = old.get(module).unwrap_or(&empty);
+        let after = new.get(module).unwrap_or(&empty);
+        let mut module_diff = ModuleDiff::default();
+        for (key, text) in after {
+            match before.get(key) {
+                None => module_diff.added.push(key.clone()),
+                Some(previous) if previous != text => module_diff.changed.push(key.clone()),
+                Some(_) => {}
+            }
+        }
+        module_diff.removed = before.keys().filter(|key| !after.contains_key(*key)).cloned().collect();
+        report.insert(module.clone(), module_diff);
+    }
+    report.retain(|_, d| !(d.added.is_empty() && d.removed.is_empty() && d.changed.is_empty()));
+    print_json(&report)?;
+    Ok(report.is_empty())
+}
+
+/// Rewrites the addresses of linked objects from the symbols. Links to missing
+/// symbols are kept and fail the command.
+fn update_addresses(args: &Args) -> Result<bool, String> {
+    let path = args.positional(0, "A2L file")?;
+    let symbols = read_symbols(args.option("elf").ok_or("Missing --elf")?)?;
+    let (mut a2l, _) = load(path)?;
+    let report = check_links(&mut a2l, &symbols, args.option("module"), true, false)?;
+    let output = args.option("output").unwrap_or(path);
+    fs::write(output, render_a2l(&a2l, &ExportOptions::default())).map_err(|e| format!("{output}: {e}"))?;
+    print_json(&report)?;
+    Ok(!report.has_missing())
+}
+
+fn export_json(args: &Args) -> Result<bool, String> {
+    let (a2l, _) = load(args.positional(0, "A2L file")?)?;
+    let output = args.positional(1, "output file")?;
+    fs::write(output, serialize_document(&a2l, is_yaml(output))?).map_err(|e| format!("{output}: {e}"))?;
+    Ok(true)
+}
+
+/// Entry point of the `a2lforge` binary; returns the process exit code.
+pub fn main(args: Vec<String>) -> i32 {
+    let Some((command, rest)) = args.split_first() else {
+        eprintln!("{USAGE}");
+        return 2;
+    };
+    let result = match command.as_str() {
+        "validate" => Args::parse(rest, &["strict", "allow-cross-module"]).and_then(|args| validate(&args)),
+        "diff" => Args::parse(rest, &[]).and_then(|args| diff(&args)),
+        "update-addresses" => Args::parse(rest, &[]).and_then(|args| update_addresses(&args)),
+        "export-json" => Args::parse(rest, &[]).and_then(|args| export_json(&args)),
+        "help" | "--help" | "-h" => {
+            println!("{USAGE}");
+            return 0;
+        }
+        other => Err(format!("Unknown command: {other}\n\n{USAGE}")),
+    };
+    match result {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(error) => {
+            eprintln!("error: {error}");
+            2
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::fs;
+
+use a2lfile::A2lObjectName;
+use serde::Serialize;
+
+use crate::hex::MemoryImage;
+use crate::layout::{characteristic_size, characteristic_value_count, encode_raw, is_big_endian};
+use crate::AppState;
+
+#[derive(Serialize)]
+pub struct DatasetTemplateReport {
+    format: String,
+    characteristics: usize,
+    bytes: usize,
+    skipped: Vec<String>,
+}
+
+/// Default raw value for a fresh dataset: zero, unless an identity conversion
+/// puts zero outside the characteristic limits, in which case the lower limit.
+fn default_raw_value(c: &a2lfile::Characteristic) -> f64 {
+    let identity = c.conversion == "NO_COMPU_METHOD";
+    if identity && (c.lower_limit > 0.0 || c.upper_limit < 0.0) {
+        c.lower_limit
+    } else {
+        0.0
+    }
+}
+
+fn build_hex_image(a2l: &a2lfile::A2lFile, skipped: &mut Vec<String>) -> (MemoryImage, usize) {
+    let mut image = MemoryImage::default();
+    let mut count = 0;
+    for module in a2l.project.module.iter() {
+        for c in module.characteristic.iter() {
+            let Some(size) = characteristic_size(module, c) else {
+                let name = c.get_name();
+                skipped.push(format!("{name}: record layout '{}' not resolvable or size too large", c.deposit));
+                continue;
+            };
+            let fnc_datatype = module
+                .record_layout
+                .get(&c.deposit)
+                .and_then(|layout| layout.fnc_values.as_ref())
+                .map(|fnc| fnc.datatype);
+            let mut data = Vec::with_capacity(size as usize);
+            if let Some(datatype) = fnc_datatype {
+                let value = encode_raw(&datatype, default_raw_value(c), is_big_endian(module, &c.byte_order));
+                for _ in 0..characteristic_value_count(c).unwrap_or(0) {
+                    data.extend_from_slice(&value);
+                }
+            }
+            data.resize(size as usize, 0);
+            image.write(c.address, &data);
+            count += 1;
+        }
+    }
+    (image, count)
+}
+
+fn dcm_values(count: u32, value: f64) -> String {
+    vec![value.to_string(); count as usize].join(" ")
+}
+
+fn build_dcm(a2l: &a2lfile::A2lFile, skipped: &mut Vec<String>) -> (String, usize) {
+    let mut out = String::from("KONSERVIERUNG_FORMAT 2.0\n\n");
+    let mut count = 0;
+    for module in a2l.project.module.iter() {
+        for c in module.characteristic.iter() {
+            let name = c.get_name();
+            let Some(n) = characteristic_value_count(c) else {
+                skipped.push(format!("{name}: value count too large"));
+                continue;
+            };
+            let long_identifier = c.long_identifier.replace('"', "'");
+            let axes: Vec<u32> = c.axis_descr.iter().map(|a| u32::from(a.max_axis_points.max(1))).collect();
+            let block = match c.characteristic_type {
+                a2lfile::CharacteristicType::Value => {
+                    format!("FESTWERT {name}\n   LANGNAME \"{long_identifier}\"\n   WERT 0\nEND\n")
+                }
+                a2lfile::CharacteristicType::Ascii => {
+                    format!("TEXTSTRING {name}\n   LANGNAME \"{long_identifier}\"\n   TEXT \"\"\nEND\n")
+                }
+                a2lfile::CharacteristicType::ValBlk => {
+                    format!(
+                        "FESTWERTEBLOCK {name} {n}\n   LANGNAME \"{long_identifier}\"\n   WERT {}\nEND\n",
+                        dcm_values(n, 0.0)
+                    )
+                }
+                a2lfile::CharacteristicType::Curve => {
+                    let nx = axes.first().copied().unwrap_or(1);
+                    format!(
+                        "KENNLINIE {name} {nx}\n   LANGNAME \"{long_identifier}\"\n   ST/X {}\n   WERT {}\nEND\n",
+                        dcm_values(nx, 0.0),
+                        dcm_values(nx, 0.0)
+                    )
+                }
+                a2lfile::CharacteristicType::Map => {
+                    let nx = axes.first().copied().unwrap_or(1);
+                    let ny = axes.get(1).copied().unwrap_or(1);
+                    let mut block = format!(
+                        "KENNFELD {name} {nx} {ny}\n   LANGNAME \"{long_identifier}\"\n   ST/X {}\n",
+                        dcm_values(nx, 0.0)
+                    );
+                    for _ in 0..ny {
+                        block.push_str(&format!("   ST/Y 0\n   WERT {}\n", dcm_values(nx, 0.0)));
+                    }
+                    block.push_str("END\n");
+                    block
+                }
+                // DCM has no native 3D+ tables; emit them as flat value blocks.
+                _ => {
+                    format!(
+                        "FESTWERTEBLOCK {name} {n}\n   LANGNAME \"{long_identifier}\"\n   WERT {}\nEND\n",
+                        dcm_values(n, 0.0)
+                    )
+                }
+            };
+            out.push_str(&block);
+            out.push('\n');
+            count += 1;
+        }
+    }
+    (out, count)
+}
+
+#[tauri::command]
+pub fn generate_dataset_template(
+    path: String,
+    format: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<DatasetTemplateReport, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+
+    let mut skipped = Vec::new();
+    let (content, characteristics, bytes) = match format.to_lowercase().as_str() {
+        "hex" | "ihex" => {
+            let (image, count) = build_hex_image(a2l, &mut skipped);
+            (image.to_intel_hex(), count, image.len())
+        }
+        "dcm" => {
+            let (text, count) = build_dcm(a2l, &mut skipped);
+            let len = text.len();
+            (text, count, len)
+        }
+        other => return Err(format!("Unsupported dataset format: {other}")),
+    };
+    fs::write(&path, content).map_err(|e| e.to_string())?;
+
+    Ok(DatasetTemplateReport {
+        format,
+        characteristics,
+        bytes,
+        skipped,
+    })
+}
@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::fs;
+
+use a2lfile::A2lObjectName;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::references::name_taken;
+use crate::templates::load_template;
+use crate::{build_metadata, find_module_mut, A2lMetadata, AppState};
+
+/// Pseudo message that holds signals not sent by any node.
+const INDEPENDENT_MESSAGE: &str = "VECTOR__INDEPENDENT_SIG_MSG";
+
+struct DbcSignal {
+    name: String,
+    length: u32,
+    little_endian: bool,
+    signed: bool,
+    factor: f64,
+    offset: f64,
+    min: f64,
+    max: f64,
+    unit: String,
+}
+
+struct DbcMessage {
+    id: u32,
+    name: String,
+    signals: Vec<DbcSignal>,
+}
+
+#[derive(Serialize)]
+pub struct DbcImportResult {
+    messages: usize,
+    measurements: Vec<String>,
+    compu_methods: Vec<String>,
+    units: Vec<String>,
+    skipped: Vec<String>,
+    metadata: A2lMetadata,
+}
+
+/// Messages with their signals, plus signal comments and SIG_VALTYPE_ entries
+/// (1 = float, 2 = double) keyed by (message ID, signal name).
+type ParsedDbc = (Vec<DbcMessage>, HashMap<(u32, String), String>, HashMap<(u32, String), u8>);
+
+fn parse_dbc(text: &str) -> Result<ParsedDbc, String> {
+    let message_re = Regex::new(r"^BO_\s+(\d+)\s+(\w+)\s*:").map_err(|e| e.to_string())?;
+    let signal_re = Regex::new(
+        r#"^SG_\s+(\w+)\s*\w*\s*:\s*\d+\|(\d+)@([01])([+-])\s*\(([^,]+),([^)]+)\)\s*\[([^|]+)\|([^\]]+)\]\s*"([^"]*)""#,
+    )
+    .map_err(|e| e.to_string())?;
+    let comment_re = Regex::new(r#"CM_\s+SG_\s+(\d+)\s+(\w+)\s+"((?:[^"\\]|\\.)*)"\s*;"#).map_err(|e| e.to_string())?;
+    let value_type_re = Regex::new(r"SIG_VALTYPE_\s+(\d+)\s+(\w+)\s*:\s*([12])\s*;").map_err(|e| e.to_string())?;
+    let number = |value: &str, what: &str| -> Result<f64, String> {
+        value.trim().parse::<f64>().map_err(|_| format!("Invalid {what}: {value}"))
+    };
+
+    let mut messages: Vec<DbcMessage> = Vec::new();
+    for (line_no, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if let Some(caps) = message_re.captures(line) {
+            messages.push(DbcMessage {
+                id: caps[1].parse().map_err(|_| format!("Line {}: invalid message ID", line_no + 1))?,
+                name: caps[2].to_string(),
+                signals: Vec::new(),
+            });
+        } else if line.starts_with("SG_ ") {
+            let caps = signal_re
+                .captures(line)
+                .ok_or_else(|| format!("Line {}: unrecognized signal definition", line_no + 1))?;
+            let message = messages
+                .last_mut()
+                .ok_or_else(|| format!("Line {}: signal outside of a message", line_no + 1))?;
+            message.signals.push(DbcSignal {
+                name: caps[1].to_string(),
+                length: caps[2].parse().map_err(|_| format!("Line {}: invalid signal length", line_no + 1))?,
+                little_endian: &caps[3] == "1",
+                signed: &caps[4] == "-",
+                factor: number(&caps[5], "factor")?,
+                offset: number(&caps[6], "offset")?,
+                min: number(&caps[7], "minimum")?,
+                max: number(&caps[8], "maximum")?,
+                unit: caps[9].to_string(),
+            });
+        }
+    }
+
+    let comments = comment_re
+        .captures_iter(text)
+        .filter_map(|caps| Some(((caps[1].parse().ok()?, caps[2].to_string()), caps[3].replace("\\\"", "'"))))
+        .collect();
+    let value_types = value_type_re
+        .captures_iter(text)
+        .filter_map(|caps| Some(((caps[1].parse().ok()?, caps[2].to_string()), caps[3].parse().ok()?)))
+        .collect();
+    Ok((messages, comments, value_types))
+}
+
+fn signal_datatype(signal: &DbcSignal, value_type: Option<u8>) -> a2lfile::DataType {
+    match (value_type, signal.length, signal.signed) {
+        (Some(1), _, _) => a2lfile::DataType::Float32Ieee,
+        (Some(2), _, _) => a2lfile::DataType::Float64Ieee,
+        (_, 0..=8, false) => a2lfile::DataType::Ubyte,
+        (_, 0..=8, true) => a2lfile::DataType::Sbyte,
+        (_, 9..=16, false) => a2lfile::DataType::Uword,
+        (_, 9..=16, true) => a2lfile::DataType::Sword,
+        (_, 17..=32, false) => a2lfile::DataType::Ulong,
+        (_, 17..=32, true) => a2lfile::DataType::Slong,
+        (_, _, false) => a2lfile::DataType::AUint64,
+        (_, _, true) => a2lfile::DataType::AInt64,
+    }
+}
+
+/// Physical limits; DBC files often leave [0|0] when the full raw range applies.
+fn signal_limits(signal: &DbcSignal) -> (f64, f64) {
+    if signal.min != 0.0 || signal.max != 0.0 {
+        return (signal.min, signal.max);
+    }
+    let bits = signal.length.min(64) as i32;
+    let (raw_min, raw_max) = if signal.signed {
+        (-(2f64.powi(bits - 1)), 2f64.powi(bits - 1) - 1.0)
+    } else {
+        (0.0, 2f64.powi(bits) - 1.0)
+    };
+    let a = raw_min * signal.factor + signal.offset;
+    let b = raw_max * signal.factor + signal.offset;
+    (a.min(b), a.max(b))
+}
+
+fn identifier(text: &str) -> String {
+    let id: String = text.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    id.trim_matches('_').to_string()
+}
+
+fn number_tag(value: f64) -> String {
+    value.to_string().replace('.', "p").replace('-', "m")
+}
+
+/// `%<width>.<decimals>` with as many decimals as the factor needs, up to six.
+fn display_format(factor: f64) -> String {
+    let text = factor.abs().to_string();
+    let decimals = text.split_once('.').map(|(_, frac)| frac.len().min(6)).unwrap_or(0);
+    format!("%{}.{}", decimals + 8, decimals)
+}
+
+/// First of `base`, `base_2`, `base_3`, ... for which `taken` is false.
+fn free_name(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    let mut name = base.to_string();
+    let mut n = 1;
+    while taken(&name) {
+        n += 1;
+        name = format!("{base}_{n}");
+    }
+    name
+}
+
+/// Generates a MEASUREMENT per signal, linear COMPU_METHODs and UNITs shared
+/// between signals with equal scaling, and a FUNCTION per message listing its
+/// signals. Existing objects are never overwritten or extended.
+#[tauri::command]
+pub fn import_dbc(
+    path: String,
+    module: Option<String>,
+    template: Option<String>,
+    doc_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<AppState>,
+) -> Result<DbcImportResult, String> {
+    let template = load_template(&app, template.as_deref(), "Measurement")?;
+    let bytes = fs::read(&path).map_err(|e| e.to_string())?;
+    // DBC files are usually Windows-1252; units like "°C" must not abort the import.
+    let text = String::from_utf8_lossy(&bytes);
+    let (messages, comments, value_types) = parse_dbc(&text)?;
+
+    let mut signal_count: HashMap<&str, usize> = HashMap::new();
+    for message in &messages {
+        for signal in &message.signals {
+            *signal_count.entry(signal.name.as_str()).or_default() += 1;
+        }
+    }
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("import_dbc", doc_id.as_deref())?;
+    edit.touch_target_module(module.as_deref())?;
+    let a2l = edit.a2l_mut();
+    let target = find_module_mut(a2l, module.as_deref())?;
+
+    let mut units: HashMap<String, String> = target
+        .unit
+        .iter()
+        .map(|unit| (unit.display.clone(), unit.get_name().to_string()))
+        .collect();
+    let mut conversions: HashMap<String, String> = HashMap::new();
+    let mut result_units = Vec::new();
+    let mut result_conversions = Vec::new();
+    let mut measurements = Vec::new();
+    let mut skipped = Vec::new();
+    let mut message_count = 0;
+
+    for message in messages.iter().filter(|m| m.name != INDEPENDENT_MESSAGE) {
+        message_count += 1;
+        // Bit 31 flags extended (29-bit) identifiers.
+        let can_id = message.id & 0x1FFF_FFFF;
+        let mut members = Vec::new();
+        for signal in &message.signals {
+            let key = (message.id, signal.name.clone());
+            let name = if signal_count[signal.name.as_str()] > 1 || name_taken(target, "Measurement", &signal.name) {
+                format!("{}_{}", message.name, signal.name)
+            } else {
+                signal.name.clone()
+            };
+            if name_taken(target, "Measurement", &name) {
+                skipped.push(format!("{}.{}: name '{}' is already used", message.name, signal.name, name));
+                continue;
+            }
+
+            let unit_name = match signal.unit.trim() {
+                "" => None,
+                unit => Some(match units.get(unit) {
+                    Some(existing) => existing.clone(),
+                    None => {
+                        let base = match identifier(unit) {
+                            id if id.is_empty() || id.starts_with(|c: char| c.is_ascii_digit()) => format!("U_{id}"),
+                            id => id,
+                        };
+                        let unit_name = free_name(&base, |n| target.unit.get(n).is_some());
+                        target.unit.push(a2lfile::Unit::new(
+                            unit_name.clone(),
+                            String::new(),
+                            unit.to_string(),
+                            a2lfile::UnitType::Derived,
+                        ));
+                        units.insert(unit.to_string(), unit_name.clone());
+                        result_units.push(unit_name.clone());
+                        unit_name
+                    }
+                }),
+            };
+
+            let identity = signal.factor == 1.0 && signal.offset == 0.0;
+            let conversion = if identity && unit_name.is_none() {
+                "NO_COMPU_METHOD".to_string()
+            } else {
+                let scaling = format!("{}|{}|{}", signal.factor, signal.offset, signal.unit.trim());
+                match conversions.get(&scaling) {
+                    Some(existing) => existing.clone(),
+                    None => {
+                        let base = format!(
+                            "CM_{}_{}{}",
+                            number_tag(signal.factor),
+                            number_tag(signal.offset),
+                            unit_name.as_deref().map(|u| format!("_{u}")).unwrap_or_default()
+                        );
+                        let cm_name = free_name(&base, |n| target.compu_method.get(n).is_some());
+                        let mut cm = a2lfile::CompuMethod::new(
+                            cm_name.clone(),
+                            format!("{} * x + {}", signal.factor, signal.offset),
+                            a2lfile::ConversionType::Linear,
+                            display_format(signal.factor),
+                            signal.unit.trim().to_string(),
+                        );
+                        cm.coeffs_linear = Some(a2lfile::CoeffsLinear::new(signal.factor, signal.offset));
+                        cm.ref_unit = unit_name.clone().map(a2lfile::RefUnit::new);
+                        target.compu_method.push(cm);
+                        conversions.insert(scaling, cm_name.clone());
+                        result_conversions.push(cm_name.clone());
+                        cm_name
+                    }
+                }
+            };
+
+            let (lower, upper) = signal_limits(signal);
+            let mut m = a2lfile::Measurement::new(name.clone(), signal_datatype(signal, value_types.get(&key).copied()));
+            m.long_identifier = comments
+                .get(&key)
+                .cloned()
+                .unwrap_or_else(|| format!("{} in {} (0x{:X})", signal.name, message.name, can_id));
+            m.conversion = conversion;
+            m.resolution = 1;
+            m.lower_limit = lower;
+            m.upper_limit = upper;
+            if let Some(template) = &template {
+                template.apply_to_measurement(&mut m);
+            }
+            // The signal's own byte order wins over the template.
+            m.byte_order = Some(a2lfile::ByteOrder::new(if signal.little_endian {
+                a2lfile::ByteOrderEnum::MsbLast
+            } else {
+                a2lfile::ByteOrderEnum::MsbFirst
+            }));
+            target.measurement.push(m);
+            members.push(name.clone());
+            measurements.push(name);
+        }
+
+        if members.is_empty() {
+            continue;
+        }
+        // An existing FUNCTION of the same name is not the message's; the
+        // signals get a function of their own.
+        let function_name = free_name(&message.name, |n| target.function.get(n).is_some());
+        let mut function = a2lfile::Function::new(function_name, format!("CAN message 0x{:X}", can_id));
+        let mut loc = a2lfile::LocMeasurement::new();
+        loc.identifier_list = members;
+        function.loc_measurement = Some(loc);
+        target.function.push(function);
+    }
+
+    Ok(DbcImportResult {
+        messages: message_count,
+        measurements,
+        compu_methods: result_conversions,
+        units: result_units,
+        skipped,
+        metadata: build_metadata(a2l, 0),
+    })
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use a2lfile::{A2lObject, A2lObjectName, A2lObjectNameSetter};
+use serde::Serialize;
+
+use crate::cleanup::remove_object;
+use crate::references::{for_each_reference, rename_references, target_candidates};
+use crate::AppState;
+
+/// Tables come first so that conversion methods pointing at merged tables
+/// compare equal afterwards.
+const CONVERSION_KINDS: &[&str] = &["CompuTab", "CompuVtab", "CompuVtabRange", "CompuMethod"];
+
+#[derive(Serialize)]
+pub struct DuplicateGroup {
+    module: String,
+    kind: String,
+    names: Vec<String>,
+    /// Number of references to each entry of `names`.
+    references: Vec<usize>,
+}
+
+#[derive(Serialize)]
+pub struct MergedGroup {
+    module: String,
+    kind: String,
+    survivor: String,
+    removed: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct DedupReport {
+    groups: Vec<MergedGroup>,
+    references_updated: usize,
+}
+
+#[derive(Clone, Copy)]
+enum KeepStrategy {
+    /// The object that comes first in the file.
+    First,
+    ShortestName,
+    MostReferenced,
+}
+
+impl KeepStrategy {
+    fn parse(strategy: Option<&str>) -> Result<Self, String> {
+        match strategy.unwrap_or("first") {
+            "first" => Ok(Self::First),
+            "shortest_name" => Ok(Self::ShortestName),
+            "most_referenced" => Ok(Self::MostReferenced),
+            other => Err(format!("Unknown keep strategy: {other}")),
+        }
+    }
+}
+
+/// Names of `items` grouped by equal content, in file order. Only groups with
+/// more than one member are returned.
+fn equal_groups<T: PartialEq>(items: Vec<(String, T)>) -> Vec<Vec<String>> {
+    let mut groups: Vec<(T, Vec<String>)> = Vec::new();
+    for (name, item) in items {
+        match groups.iter_mut().find(|(representative, _)| *representative == item) {
+            Some((_, names)) => names.push(name),
+            None => groups.push((item, vec![name])),
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(_, names)| names)
+        .filter(|names| names.len() > 1)
+        .collect()
+}
+
+/// Objects of `kind` with the same content apart from name, long identifier
+/// and the include file they come from, in file order.
+fn duplicate_sets(module: &a2lfile::Module, kind: &str) -> Vec<Vec<String>> {
+    macro_rules! duplicates {
+        ($list:ident) => {
+            equal_groups(
+                module
+                    .$list
+                    .iter()
+                    .map(|item| {
+                        let mut copy = item.clone();
+                        copy.set_name("_".to_string());
+                        copy.long_identifier = String::new();
+                        copy.get_layout_mut().incfile = None;
+                        (item.get_name().to_string(), copy)
+                    })
+                    .collect(),
+            )
+        };
+    }
+    match kind {
+        "CompuMethod" => duplicates!(compu_method),
+        "CompuTab" => duplicates!(compu_tab),
+        "CompuVtab" => duplicates!(compu_vtab),
+        "CompuVtabRange" => duplicates!(compu_vtab_range),
+        _ => Vec::new(),
+    }
+}
+
+fn reference_counts(module: &a2lfile::Module, kind: &str) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for_each_reference(module, &mut |_, _, _, target_kind, target| {
+        if target_candidates(target_kind).contains(&kind) {
+            *counts.entry(target.to_string()).or_default() += 1;
+        }
+    });
+    counts
+}
+
+#[tauri::command]
+pub fn find_duplicate_conversions(
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<DuplicateGroup>, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let mut groups = Vec::new();
+    for module in a2l.project.module.iter() {
+        if module_name.as_deref().is_some_and(|name| module.get_name() != name) {
+            continue;
+        }
+        for kind in CONVERSION_KINDS {
+            let counts = reference_counts(module, kind);
+            for names in duplicate_sets(module, kind) {
+                groups.push(DuplicateGroup {
+                    module: module.get_name().to_string(),
+                    kind: kind.to_string(),
+                    references: names.iter().map(|name| counts.get(name).copied().unwrap_or(0)).collect(),
+                    names,
+                });
+            }
+        }
+    }
+    Ok(groups)
+}
+
+/// Keeps one object per duplicate group, points all references at it and
+/// deletes the others.
+#[tauri::command]
+pub fn deduplicate_conversions(
+    keep_strategy: Option<String>,
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<DedupReport, String> {
+    let strategy = KeepStrategy::parse(keep_strategy.as_deref())?;
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("deduplicate_conversions", doc_id.as_deref())?;
+    edit.touch_all();
+    let a2l = edit.a2l_mut();
+    let mut report = DedupReport {
+        groups: Vec::new(),
+        references_updated: 0,
+    };
+
+    for module in a2l.project.module.iter_mut() {
+        if module_name.as_deref().is_some_and(|name| module.get_name() != name) {
+            continue;
+        }
+        let module_id = module.get_name().to_string();
+        for kind in CONVERSION_KINDS {
+            let counts = reference_counts(module, kind);
+            for mut names in duplicate_sets(module, kind) {
+                let keep = match strategy {
+                    KeepStrategy::First => 0,
+                    KeepStrategy::ShortestName => (0..names.len()).min_by_key(|&i| names[i].len()).unwrap_or(0),
+                    KeepStrategy::MostReferenced => (0..names.len())
+                        .max_by_key(|&i| (counts.get(&names[i]).copied().unwrap_or(0), std::cmp::Reverse(i)))
+                        .unwrap_or(0),
+                };
+                let survivor = names.remove(keep);
+                for name in &names {
+                    report.references_updated += rename_references(module, kind, name, &survivor);
+                    remove_object(module, kind, name);
+                }
+                report.groups.push(MergedGroup {
+                    module: module_id.clone(),
+                    kind: kind.to_string(),
+                    survivor,
+                    removed: names,
+                });
+            }
+        }
+    }
+    Ok(report)
+}
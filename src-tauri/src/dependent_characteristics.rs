@@ -0,0 +1,184 @@
+use std::sync::OnceLock;
+
+use a2lfile::A2lObjectName;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::references::{ReferenceIssue, ReferenceSite};
+use crate::AppState;
+
+/// Formula of a DEPENDENT_CHARACTERISTIC or VIRTUAL_CHARACTERISTIC; `X1`
+/// refers to the first characteristic of the list, `X2` to the second, ...
+#[derive(Serialize, Deserialize)]
+pub struct FormulaData {
+    formula: String,
+    characteristics: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct CharacteristicFormulas {
+    dependent: Option<FormulaData>,
+    #[serde(rename = "virtual")]
+    virtual_characteristic: Option<FormulaData>,
+}
+
+fn placeholder_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"\b[Xx](\d+)\b").expect("valid placeholder regex"))
+}
+
+/// Problems of a formula whose placeholders do not match the list: an unused
+/// list entry or a placeholder beyond the end of the list.
+fn formula_problems(formula: &str, count: usize) -> Vec<String> {
+    let used: Vec<usize> = placeholder_regex()
+        .captures_iter(formula)
+        .filter_map(|captures| captures[1].parse().ok())
+        .collect();
+    let mut problems = Vec::new();
+    if let Some(index) = used.iter().find(|&&index| index == 0 || index > count) {
+        problems.push(format!("placeholder X{index} has no characteristic (list has {count})"));
+    }
+    for index in 1..=count {
+        if !used.contains(&index) {
+            problems.push(format!("characteristic {index} of the list is not used as X{index}"));
+        }
+    }
+    problems
+}
+
+/// Formula issues of all characteristics of `module`, for the reference check.
+/// Missing list entries are reported by the reference check itself.
+pub(crate) fn formula_issues(module: &a2lfile::Module) -> Vec<ReferenceIssue> {
+    let mut issues = Vec::new();
+    for c in module.characteristic.iter() {
+        let formulas = [
+            ("dependent_characteristic", c.dependent_characteristic.as_ref().map(|d| (&d.formula, &d.characteristic_list))),
+            ("virtual_characteristic", c.virtual_characteristic.as_ref().map(|v| (&v.formula, &v.characteristic_list))),
+        ];
+        for (field, formula) in formulas {
+            let Some((formula, list)) = formula else { continue };
+            for message in formula_problems(formula, list.len()) {
+                issues.push(ReferenceIssue {
+                    site: ReferenceSite {
+                        module: module.get_name().to_string(),
+                        kind: "Characteristic".to_string(),
+                        name: c.get_name().to_string(),
+                        field: field.to_string(),
+                        target_kind: "Formula".to_string(),
+                        target: formula.clone(),
+                    },
+                    severity: "error".to_string(),
+                    message: format!("Formula '{formula}': {message}"),
+                    resolved_module: None,
+                });
+            }
+        }
+    }
+    issues
+}
+
+fn module_with_characteristic<'a>(
+    a2l: &'a mut a2lfile::A2lFile,
+    module_name: Option<&str>,
+    name: &str,
+) -> Result<&'a mut a2lfile::Module, String> {
+    a2l.project
+        .module
+        .iter_mut()
+        .filter(|module| module_name.is_none_or(|m| module.get_name() == m))
+        .find(|module| module.characteristic.get(name).is_some())
+        .ok_or_else(|| format!("Characteristic '{name}' not found"))
+}
+
+#[tauri::command]
+pub fn get_characteristic_formulas(
+    name: String,
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<CharacteristicFormulas, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let c = a2l
+        .project
+        .module
+        .iter()
+        .filter(|module| module_name.as_deref().is_none_or(|m| module.get_name() == m))
+        .find_map(|module| module.characteristic.get(&name))
+        .ok_or_else(|| format!("Characteristic '{name}' not found"))?;
+    Ok(CharacteristicFormulas {
+        dependent: c.dependent_characteristic.as_ref().map(|d| FormulaData {
+            formula: d.formula.clone(),
+            characteristics: d.characteristic_list.clone(),
+        }),
+        virtual_characteristic: c.virtual_characteristic.as_ref().map(|v| FormulaData {
+            formula: v.formula.clone(),
+            characteristics: v.characteristic_list.clone(),
+        }),
+    })
+}
+
+/// Sets or, with `data` = `None`, removes the DEPENDENT_CHARACTERISTIC
+/// (`kind` "dependent") or VIRTUAL_CHARACTERISTIC (`kind` "virtual") of a
+/// characteristic. The listed characteristics must exist in the same module
+/// and the formula must use exactly X1..Xn.
+#[tauri::command]
+pub fn update_characteristic_formula(
+    name: String,
+    kind: String,
+    data: Option<FormulaData>,
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    if !matches!(kind.as_str(), "dependent" | "virtual") {
+        return Err(format!("Unknown formula kind: {kind}"));
+    }
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("update_characteristic_formula", doc_id.as_deref())?;
+    edit.touch_object("Characteristic", &name);
+    let a2l = edit.a2l_mut();
+    let module = module_with_characteristic(a2l, module_name.as_deref(), &name)?;
+
+    if let Some(data) = &data {
+        if data.formula.trim().is_empty() {
+            return Err("Formula must not be empty".to_string());
+        }
+        if data.characteristics.iter().any(|c| *c == name) {
+            return Err(format!("Characteristic '{name}' cannot depend on itself"));
+        }
+        let missing: Vec<&str> = data
+            .characteristics
+            .iter()
+            .filter(|c| module.characteristic.get(c).is_none())
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!("Unknown characteristics: {}", missing.join(", ")));
+        }
+        let problems = formula_problems(&data.formula, data.characteristics.len());
+        if !problems.is_empty() {
+            return Err(format!("Formula '{}': {}", data.formula, problems.join("; ")));
+        }
+    }
+
+    let c = module
+        .characteristic
+        .get_mut(&name)
+        .ok_or_else(|| format!("Characteristic '{name}' not found"))?;
+    match (kind.as_str(), data) {
+        ("dependent", Some(data)) => {
+            let mut dependent = a2lfile::DependentCharacteristic::new(data.formula);
+            dependent.characteristic_list = data.characteristics;
+            c.dependent_characteristic = Some(dependent);
+        }
+        ("dependent", None) => c.dependent_characteristic = None,
+        (_, Some(data)) => {
+            let mut virtual_characteristic = a2lfile::VirtualCharacteristic::new(data.formula);
+            virtual_characteristic.characteristic_list = data.characteristics;
+            c.virtual_characteristic = Some(virtual_characteristic);
+        }
+        (_, None) => c.virtual_characteristic = None,
+    }
+    Ok(())
+}
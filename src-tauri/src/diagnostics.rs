@@ -0,0 +1,65 @@
+use serde::Serialize;
+
+use crate::AppState;
+
+/// A parser message split into the parts the frontend needs to jump to the
+/// offending source location.
+#[derive(Serialize, Clone)]
+pub(crate) struct Diagnostic {
+    severity: String,
+    message: String,
+    file: Option<String>,
+    line: Option<u32>,
+    keyword: Option<String>,
+}
+
+/// Extracts `(file, line)` from messages of the form `file.a2l:123: ...` or
+/// `... on line 123 ...`.
+fn location(message: &str) -> (Option<String>, Option<u32>) {
+    let mut parts = message.splitn(3, ':');
+    if let (Some(file), Some(line), Some(_)) = (parts.next(), parts.next(), parts.next()) {
+        if let Ok(line) = line.trim().parse::<u32>() {
+            return (Some(file.trim().to_string()).filter(|f| !f.is_empty()), Some(line));
+        }
+    }
+    let lower = message.to_ascii_lowercase();
+    let line = lower.find("line ").and_then(|pos| {
+        let digits: String = lower[pos + 5..].chars().take_while(|c| c.is_ascii_digit()).collect();
+        digits.parse().ok()
+    });
+    (None, line)
+}
+
+/// First token that looks like an ASAP2 keyword, e.g. `CHARACTERISTIC` or
+/// `MATRIX_DIM`.
+fn keyword(message: &str) -> Option<String> {
+    message
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .find(|token| {
+            token.len() >= 3
+                && token.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+                && token.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        })
+        .map(str::to_string)
+}
+
+pub(crate) fn diagnostic(message: &str, severity: &str) -> Diagnostic {
+    let (file, line) = location(message);
+    Diagnostic {
+        severity: severity.to_string(),
+        message: message.to_string(),
+        file,
+        line,
+        keyword: keyword(message),
+    }
+}
+
+pub(crate) fn from_warnings(warnings: &[String]) -> Vec<Diagnostic> {
+    warnings.iter().map(|warning| diagnostic(warning, "warning")).collect()
+}
+
+#[tauri::command]
+pub fn get_load_diagnostics(doc_id: Option<String>, state: tauri::State<AppState>) -> Result<Vec<Diagnostic>, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    Ok(guard.document(doc_id.as_deref())?.diagnostics.clone())
+}
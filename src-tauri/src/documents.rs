@@ -0,0 +1,474 @@
+use std::collections::{BTreeMap, HashSet};
+
+use a2lfile::A2lObjectName;
+use serde::Serialize;
+
+use crate::audit::AuditChange;
+use crate::diagnostics::{self, Diagnostic};
+use crate::entity_events::publish;
+use crate::entity_source::{extract_part, part_text, MODULE_HEADER, PROJECT_HEADER};
+use crate::hex::MemoryImage;
+use crate::references::{for_each_reference, object_exists, object_names, target_candidates, OBJECT_KINDS};
+use crate::snapshots::Snapshot;
+use crate::{build_metadata, find_module, load_a2l_file, A2lMetadata, AppState};
+
+pub(crate) struct Document {
+    pub(crate) path: Option<String>,
+    pub(crate) a2l: a2lfile::A2lFile,
+    /// Deep copies of the model taken with `create_snapshot`, keyed by label.
+    pub(crate) snapshots: BTreeMap<String, Snapshot>,
+    /// Parser warnings of the last load.
+    pub(crate) diagnostics: Vec<Diagnostic>,
+    /// Flash image loaded with `load_hex_image`, with its path.
+    pub(crate) hex_image: Option<(String, MemoryImage)>,
+    /// Bumped by every `Edit` that changed the model.
+    pub(crate) revision: u64,
+    /// Revision last written to `path`.
+    pub(crate) saved_revision: u64,
+    /// Model as last read from or written to `path`; the common ancestor when
+    /// external changes are merged with local edits.
+    pub(crate) base: Option<a2lfile::A2lFile>,
+}
+
+impl Document {
+    fn new(path: Option<String>, a2l: a2lfile::A2lFile) -> Self {
+        let base = path.as_ref().map(|_| a2l.clone());
+        Self {
+            path,
+            a2l,
+            snapshots: BTreeMap::new(),
+            diagnostics: Vec::new(),
+            hex_image: None,
+            revision: 0,
+            saved_revision: 0,
+            base,
+        }
+    }
+
+    pub(crate) fn is_dirty(&self) -> bool {
+        self.revision != self.saved_revision
+    }
+}
+
+/// Open A2L documents keyed by document ID. Commands that receive no
+/// `doc_id` operate on the active document, which is the one opened last
+/// unless the frontend switched explicitly.
+#[derive(Default)]
+pub(crate) struct DocumentStore {
+    documents: BTreeMap<String, Document>,
+    active: Option<String>,
+    next_id: u64,
+    /// Receives the entity events and change log entries of edits; set at
+    /// startup.
+    pub(crate) app: Option<tauri::AppHandle>,
+}
+
+impl DocumentStore {
+    pub(crate) fn resolve_id<'a>(&'a self, doc_id: Option<&'a str>) -> Result<&'a str, String> {
+        match doc_id {
+            Some(id) if self.documents.contains_key(id) => Ok(id),
+            Some(id) => Err(format!("Document '{id}' is not open")),
+            None => self.active.as_deref().ok_or_else(|| "No A2L loaded".to_string()),
+        }
+    }
+
+    pub(crate) fn document(&self, doc_id: Option<&str>) -> Result<&Document, String> {
+        let id = self.resolve_id(doc_id)?;
+        self.documents.get(id).ok_or_else(|| "No A2L loaded".to_string())
+    }
+
+    pub(crate) fn document_mut(&mut self, doc_id: Option<&str>) -> Result<&mut Document, String> {
+        let id = self.resolve_id(doc_id)?.to_string();
+        self.documents.get_mut(&id).ok_or_else(|| "No A2L loaded".to_string())
+    }
+
+    pub(crate) fn get(&self, doc_id: Option<&str>) -> Result<&a2lfile::A2lFile, String> {
+        Ok(&self.document(doc_id)?.a2l)
+    }
+
+    /// Starts an edit of the model of `doc_id` (or the active document) on
+    /// behalf of `command`, which its changes are logged under.
+    pub(crate) fn edit(&mut self, command: &'static str, doc_id: Option<&str>) -> Result<Edit<'_>, String> {
+        let id = self.resolve_id(doc_id)?.to_string();
+        let app = self.app.clone();
+        let document = self.documents.get_mut(&id).ok_or_else(|| "No A2L loaded".to_string())?;
+        Ok(Edit {
+            document,
+            doc_id: id,
+            command,
+            app,
+            touched: Vec::new(),
+            keys: HashSet::new(),
+            scopes: Vec::new(),
+            whole_model: false,
+            changed: false,
+        })
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&String, &Document)> {
+        self.documents.iter()
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&String, &mut Document)> {
+        self.documents.iter_mut()
+    }
+
+    /// Opens `a2l` as a new document and makes it active.
+    pub(crate) fn open(&mut self, path: Option<String>, a2l: a2lfile::A2lFile) -> String {
+        self.next_id += 1;
+        let id = format!("doc-{}", self.next_id);
+        self.documents.insert(id.clone(), Document::new(path, a2l));
+        self.active = Some(id.clone());
+        id
+    }
+
+    /// Replaces the content of `doc_id` (or the active document). Opens a new
+    /// document if nothing is loaded yet.
+    pub(crate) fn replace(&mut self, doc_id: Option<&str>, path: Option<String>, a2l: a2lfile::A2lFile) -> Result<String, String> {
+        match doc_id.or(self.active.as_deref()) {
+            Some(id) => {
+                let id = id.to_string();
+                let document = self
+                    .documents
+                    .get_mut(&id)
+                    .ok_or_else(|| format!("Document '{id}' is not open"))?;
+                *document = Document::new(path, a2l);
+                Ok(id)
+            }
+            None => Ok(self.open(path, a2l)),
+        }
+    }
+
+    fn close(&mut self, doc_id: &str) -> Result<(), String> {
+        self.documents
+            .remove(doc_id)
+            .ok_or_else(|| format!("Document '{doc_id}' is not open"))?;
+        if self.active.as_deref() == Some(doc_id) {
+            // IDs are handed out in open order; "doc-10" sorts before "doc-9".
+            self.active = self
+                .documents
+                .keys()
+                .max_by_key(|id| id.trim_start_matches("doc-").parse::<u64>().unwrap_or(0))
+                .cloned();
+        }
+        Ok(())
+    }
+}
+
+struct Touched {
+    module: String,
+    kind: String,
+    name: String,
+    before: Option<a2lfile::A2lFile>,
+}
+
+/// Mutable access to a document's model. Commands `touch` the parts they are
+/// about to change; when the edit is dropped, those parts are compared with
+/// their previous state and the revision is only bumped if one of them
+/// differs. Rejected or read-only runs therefore leave the document clean.
+/// The differing parts are published as entity events and change log entries.
+pub(crate) struct Edit<'a> {
+    document: &'a mut Document,
+    doc_id: String,
+    command: &'static str,
+    app: Option<tauri::AppHandle>,
+    touched: Vec<Touched>,
+    keys: HashSet<(String, String, String)>,
+    /// Modules whose objects were all touched; objects created in them are
+    /// picked up when the edit ends.
+    scopes: Vec<String>,
+    /// Every module was touched, including ones created later.
+    whole_model: bool,
+    changed: bool,
+}
+
+impl Edit<'_> {
+    pub(crate) fn a2l(&self) -> &a2lfile::A2lFile {
+        &self.document.a2l
+    }
+
+    /// The model. Parts changed through it must be touched beforehand.
+    pub(crate) fn a2l_mut(&mut self) -> &mut a2lfile::A2lFile {
+        &mut self.document.a2l
+    }
+
+    /// Records the current state of `kind`/`name` in `module`. `kind` may also
+    /// be `MODULE_HEADER` or `PROJECT_HEADER`. Objects that do not exist yet
+    /// can be touched before they are created.
+    pub(crate) fn touch(&mut self, module: &str, kind: &str, name: &str) {
+        if !self.keys.insert((module.to_string(), kind.to_string(), name.to_string())) {
+            return;
+        }
+        self.touched.push(Touched {
+            module: module.to_string(),
+            kind: kind.to_string(),
+            name: name.to_string(),
+            before: extract_part(&self.document.a2l, module, kind, name),
+        });
+    }
+
+    /// Touches `kind`/`name` in every module that defines it.
+    pub(crate) fn touch_object(&mut self, kind: &str, name: &str) {
+        let modules: Vec<String> = self
+            .document
+            .a2l
+            .project
+            .module
+            .iter()
+            .filter(|module| object_exists(module, kind, name))
+            .map(|module| module.get_name().to_string())
+            .collect();
+        for module in modules {
+            self.touch(&module, kind, name);
+        }
+    }
+
+    /// Touches `kind`/`name` and `kind`/`new_name` in every module that
+    /// defines `name`, before a rename.
+    pub(crate) fn touch_renamed(&mut self, kind: &str, name: &str, new_name: &str) {
+        let modules: Vec<String> = self
+            .document
+            .a2l
+            .project
+            .module
+            .iter()
+            .filter(|module| object_exists(module, kind, name))
+            .map(|module| module.get_name().to_string())
+            .collect();
+        for module in modules {
+            self.touch(&module, kind, name);
+            self.touch(&module, kind, new_name);
+        }
+    }
+
+    pub(crate) fn touch_module(&mut self, module: &str) {
+        self.touch(module, MODULE_HEADER, module);
+    }
+
+    pub(crate) fn touch_project(&mut self) {
+        self.touch("", PROJECT_HEADER, "");
+    }
+
+    /// Touches the header and every object of `module`, for commands that
+    /// create or rewrite objects in bulk.
+    pub(crate) fn touch_module_objects(&mut self, module: &str) {
+        self.touch_module(module);
+        for (kind, name) in module_objects(&self.document.a2l, module) {
+            self.touch(module, kind, &name);
+        }
+        self.scopes.push(module.to_string());
+    }
+
+    /// Name of the module `module_name` resolves to, like `find_module`.
+    pub(crate) fn module_id(&self, module_name: Option<&str>) -> Result<String, String> {
+        Ok(find_module(&self.document.a2l, module_name)?.get_name().to_string())
+    }
+
+    /// Resolves `module_name` like `find_module` and touches all of that
+    /// module. Returns its name.
+    pub(crate) fn touch_target_module(&mut self, module_name: Option<&str>) -> Result<String, String> {
+        let module = self.module_id(module_name)?;
+        self.touch_module_objects(&module);
+        Ok(module)
+    }
+
+    /// Touches the whole model, for commands that may change any part of it.
+    pub(crate) fn touch_all(&mut self) {
+        self.touch_project();
+        for module in module_names(&self.document.a2l) {
+            self.touch_module_objects(&module);
+        }
+        self.whole_model = true;
+    }
+
+    /// Touches `kind`/`name` and every object in `module` that refers to it.
+    /// References from module headers (USER_RIGHTS) touch the header.
+    pub(crate) fn touch_with_referrers(&mut self, module: &str, kind: &str, name: &str) {
+        self.touch(module, kind, name);
+        let Some(source) = self.document.a2l.project.module.iter().find(|m| m.get_name() == module) else {
+            return;
+        };
+        let mut referrers = Vec::new();
+        for_each_reference(source, &mut |source_kind, source_name, _, target_kind, target| {
+            if target == name && target_candidates(target_kind).contains(&kind) {
+                referrers.push((source_kind.to_string(), source_name.to_string()));
+            }
+        });
+        for (source_kind, source_name) in referrers {
+            if OBJECT_KINDS.contains(&source_kind.as_str()) {
+                self.touch(module, &source_kind, &source_name);
+            } else {
+                self.touch_module(module);
+            }
+        }
+    }
+
+    /// Replaces the whole model.
+    pub(crate) fn replace(&mut self, a2l: a2lfile::A2lFile) {
+        self.touch_all();
+        self.document.a2l = a2l;
+        self.changed = true;
+    }
+
+    /// Records a change the touched parts do not show, e.g. a new object order.
+    pub(crate) fn mark_changed(&mut self) {
+        self.changed = true;
+    }
+
+    /// Compares the touched parts with their recorded state and bumps the
+    /// revision if anything changed. Returns whether it did.
+    pub(crate) fn finish(mut self) -> bool {
+        self.commit()
+    }
+
+    /// Parts created in a touched scope; they did not exist when it was touched.
+    fn created_parts(&self) -> Vec<(String, &'static str, String)> {
+        let mut modules = self.scopes.clone();
+        if self.whole_model {
+            modules.extend(module_names(&self.document.a2l));
+        }
+        modules.sort();
+        modules.dedup();
+        let mut created = Vec::new();
+        for module in modules {
+            let parts = std::iter::once((MODULE_HEADER, module.clone()))
+                .chain(module_objects(&self.document.a2l, &module));
+            for (kind, name) in parts {
+                if !self.keys.contains(&(module.clone(), kind.to_string(), name.clone())) {
+                    created.push((module.clone(), kind, name));
+                }
+            }
+        }
+        created
+    }
+
+    /// Touched and created parts whose current state differs from the
+    /// recorded one, with the text of both states.
+    fn changes(&mut self) -> Vec<AuditChange> {
+        let created = self.created_parts().into_iter().map(|(module, kind, name)| Touched {
+            module,
+            kind: kind.to_string(),
+            name,
+            before: None,
+        });
+        let touched: Vec<Touched> = std::mem::take(&mut self.touched).into_iter().chain(created).collect();
+        let mut changes = Vec::new();
+        for touched in touched {
+            let after = extract_part(&self.document.a2l, &touched.module, &touched.kind, &touched.name);
+            if after == touched.before {
+                continue;
+            }
+            let text = |part: &Option<a2lfile::A2lFile>| part.as_ref().map(|part| part_text(part, &touched.kind));
+            changes.push(AuditChange {
+                old_value: text(&touched.before),
+                new_value: text(&after),
+                module: touched.module,
+                kind: touched.kind,
+                name: touched.name,
+            });
+        }
+        changes
+    }
+
+    fn commit(&mut self) -> bool {
+        let changes = self.changes();
+        let changed = std::mem::take(&mut self.changed) || !changes.is_empty();
+        self.keys.clear();
+        self.scopes.clear();
+        self.whole_model = false;
+        if changed {
+            self.document.revision += 1;
+            if let Some(app) = &self.app {
+                publish(app, &self.doc_id, self.command, changes);
+            }
+        }
+        changed
+    }
+}
+
+impl Drop for Edit<'_> {
+    fn drop(&mut self) {
+        self.commit();
+    }
+}
+
+fn module_names(a2l: &a2lfile::A2lFile) -> Vec<String> {
+    a2l.project.module.iter().map(|module| module.get_name().to_string()).collect()
+}
+
+fn module_objects(a2l: &a2lfile::A2lFile, module: &str) -> Vec<(&'static str, String)> {
+    let Some(source) = a2l.project.module.iter().find(|m| m.get_name() == module) else {
+        return Vec::new();
+    };
+    OBJECT_KINDS
+        .iter()
+        .flat_map(|kind| object_names(source, kind).into_iter().map(move |name| (*kind, name)))
+        .collect()
+}
+
+#[derive(Serialize)]
+pub struct DocumentInfo {
+    id: String,
+    path: Option<String>,
+    project_name: String,
+    module_names: Vec<String>,
+    active: bool,
+    dirty: bool,
+}
+
+#[derive(Serialize)]
+pub struct OpenedDocument {
+    pub(crate) id: String,
+    pub(crate) metadata: A2lMetadata,
+}
+
+#[tauri::command]
+pub fn open_document(
+    path: String,
+    normalization: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<OpenedDocument, String> {
+    let (a2l, warnings) = load_a2l_file(&path, normalization)?;
+    let metadata = build_metadata(&a2l, warnings.len());
+    let mut documents = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let id = documents.open(Some(path), a2l);
+    documents.document_mut(Some(&id))?.diagnostics = diagnostics::from_warnings(&warnings);
+    Ok(OpenedDocument { id, metadata })
+}
+
+#[tauri::command]
+pub fn close_document(doc_id: String, state: tauri::State<AppState>) -> Result<(), String> {
+    let mut documents = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    documents.close(&doc_id)
+}
+
+#[tauri::command]
+pub fn set_active_document(doc_id: String, state: tauri::State<AppState>) -> Result<(), String> {
+    let mut documents = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    documents.resolve_id(Some(&doc_id))?;
+    documents.active = Some(doc_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_documents(state: tauri::State<AppState>) -> Result<Vec<DocumentInfo>, String> {
+    let documents = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    Ok(documents
+        .documents
+        .iter()
+        .map(|(id, document)| DocumentInfo {
+            id: id.clone(),
+            path: document.path.clone(),
+            project_name: document.a2l.project.get_name().to_string(),
+            module_names: document
+                .a2l
+                .project
+                .module
+                .iter()
+                .map(|module| module.get_name().to_string())
+                .collect(),
+            active: documents.active.as_deref() == Some(id.as_str()),
+            dirty: document.is_dirty(),
+        })
+        .collect())
+}
@@ -0,0 +1,174 @@
+use a2lfile::A2lObjectName;
+use serde::Deserialize;
+
+use crate::layout::{datatype_range, datatype_size};
+use crate::references::object_namespace;
+use crate::templates::load_template;
+use crate::{
+    build_metadata, collect_core_entities, datatype_to_string, find_module_mut, string_to_datatype, symbol_names,
+    AppState, ElfSymbol, EntityUpdateResult,
+};
+
+/// Shape and defaults shared by all characteristics generated in one call.
+#[derive(Deserialize)]
+pub struct CharacteristicTemplate {
+    /// VALUE, VAL_BLK, CURVE or MAP.
+    characteristic_type: String,
+    datatype: String,
+    #[serde(default)]
+    conversion: Option<String>,
+    /// Existing record layout; a FNC_VALUES layout for `datatype` is created when empty.
+    #[serde(default)]
+    record_layout: Option<String>,
+    #[serde(default)]
+    lower_limit: Option<f64>,
+    #[serde(default)]
+    upper_limit: Option<f64>,
+    /// Number of values (VAL_BLK) or x axis points (CURVE, MAP); derived from the symbol size when omitted.
+    #[serde(default)]
+    x_points: Option<u16>,
+    /// Number of y axis points of a MAP; derived from the symbol size when omitted.
+    #[serde(default)]
+    y_points: Option<u16>,
+    #[serde(default)]
+    rules: Option<symbol_names::NamingRules>,
+}
+
+fn fix_axis(points: u16, lower_limit: f64, upper_limit: f64) -> a2lfile::AxisDescr {
+    let mut axis = a2lfile::AxisDescr::new(
+        a2lfile::AxisDescrAttribute::FixAxis,
+        "NO_INPUT_QUANTITY".to_string(),
+        "NO_COMPU_METHOD".to_string(),
+        points,
+        lower_limit,
+        upper_limit,
+    );
+    axis.fix_axis_par_dist = Some(a2lfile::FixAxisParDist::new(0.0, 1.0, points));
+    axis
+}
+
+/// Value count of the symbol; an unknown size counts as a single value.
+fn value_count(symbol: &ElfSymbol, element_size: u32) -> u32 {
+    ((symbol.size / u64::from(element_size)) as u32).max(1)
+}
+
+/// Creates calibration characteristics for the given symbols. Symbols from code
+/// sections are skipped; every characteristic keeps a SYMBOL_LINK to its symbol.
+/// `entity_template` names a saved template (see `save_entity_template`).
+#[tauri::command]
+pub fn create_characteristics_from_elf(
+    module_name: Option<String>,
+    symbols: Vec<ElfSymbol>,
+    template: CharacteristicTemplate,
+    entity_template: Option<String>,
+    doc_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<AppState>,
+) -> Result<EntityUpdateResult, String> {
+    let entity_template = load_template(&app, entity_template.as_deref(), "Characteristic")?;
+    let characteristic_type = match template.characteristic_type.to_uppercase().as_str() {
+        "VALUE" => a2lfile::CharacteristicType::Value,
+        "VAL_BLK" => a2lfile::CharacteristicType::ValBlk,
+        "CURVE" => a2lfile::CharacteristicType::Curve,
+        "MAP" => a2lfile::CharacteristicType::Map,
+        other => return Err(format!("Unsupported characteristic type: {other}")),
+    };
+    if characteristic_type == a2lfile::CharacteristicType::Map
+        && template.x_points.unwrap_or(0) == 0
+        && template.y_points.unwrap_or(0) == 0
+    {
+        return Err("A MAP template needs x_points or y_points".to_string());
+    }
+    let datatype = string_to_datatype(&template.datatype)
+        .ok_or_else(|| format!("Invalid datatype: {}", template.datatype))?;
+    let element_size = datatype_size(&datatype);
+    let (default_lower, default_upper) = datatype_range(&datatype);
+    let lower_limit = template.lower_limit.unwrap_or(default_lower);
+    let upper_limit = template.upper_limit.unwrap_or(default_upper);
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("create_characteristics_from_elf", doc_id.as_deref())?;
+    edit.touch_target_module(module_name.as_deref())?;
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+
+    let conversion = template
+        .conversion
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| "NO_COMPU_METHOD".to_string());
+    if conversion != "NO_COMPU_METHOD" && module.compu_method.get(&conversion).is_none() {
+        return Err(format!("Conversion '{conversion}' not found"));
+    }
+    let record_layout = match template.record_layout.filter(|r| !r.is_empty()) {
+        Some(name) if module.record_layout.get(&name).is_some() => name,
+        Some(name) => return Err(format!("Record layout '{name}' not found")),
+        None => {
+            let name = format!("__{}_Z", datatype_to_string(&datatype));
+            if module.record_layout.get(&name).is_none() {
+                let mut layout = a2lfile::RecordLayout::new(name.clone());
+                layout.fnc_values = Some(a2lfile::FncValues::new(
+                    1,
+                    datatype,
+                    a2lfile::IndexMode::RowDir,
+                    a2lfile::AddrType::Direct,
+                ));
+                module.record_layout.push(layout);
+            }
+            name
+        }
+    };
+
+    let symbols: Vec<ElfSymbol> = symbols.into_iter().filter(|s| s.type_str != "FUNC").collect();
+    let existing = object_namespace(module);
+    let symbol_names: Vec<String> = symbols.iter().map(|s| s.name.clone()).collect();
+    let names = symbol_names::apply_rules(&template.rules.unwrap_or_default(), &symbol_names, &existing);
+
+    for (symbol, name) in symbols.iter().zip(names) {
+        let count = value_count(symbol, element_size);
+        let mut c = a2lfile::Characteristic::new(
+            name.name().to_string(),
+            String::new(),
+            characteristic_type,
+            symbol.address as u32,
+            record_layout.clone(),
+            0.0,
+            conversion.clone(),
+            lower_limit,
+            upper_limit,
+        );
+        match characteristic_type {
+            a2lfile::CharacteristicType::ValBlk => {
+                let mut matrix_dim = a2lfile::MatrixDim::new();
+                matrix_dim.dim_list = vec![template.x_points.unwrap_or(count as u16)];
+                c.matrix_dim = Some(matrix_dim);
+            }
+            a2lfile::CharacteristicType::Curve => {
+                let x = template.x_points.unwrap_or(count as u16);
+                c.axis_descr.push(fix_axis(x, 0.0, f64::from(x.saturating_sub(1))));
+            }
+            a2lfile::CharacteristicType::Map => {
+                let (x, y) = match (template.x_points, template.y_points) {
+                    (Some(x), Some(y)) if x > 0 && y > 0 => (x, y),
+                    (Some(x), _) if x > 0 => (x, (count / u32::from(x)).max(1) as u16),
+                    (_, y) => {
+                        let y = y.unwrap_or(1);
+                        ((count / u32::from(y)).max(1) as u16, y)
+                    }
+                };
+                c.axis_descr.push(fix_axis(x, 0.0, f64::from(x.saturating_sub(1))));
+                c.axis_descr.push(fix_axis(y, 0.0, f64::from(y.saturating_sub(1))));
+            }
+            _ => {}
+        }
+        c.symbol_link = Some(a2lfile::SymbolLink::new(symbol.name.clone(), 0));
+        if let Some(entity_template) = &entity_template {
+            entity_template.apply_to_characteristic(&mut c);
+        }
+        module.characteristic.push(c);
+    }
+
+    Ok(EntityUpdateResult {
+        metadata: build_metadata(a2l, 0),
+        entities: collect_core_entities(a2l),
+    })
+}
@@ -0,0 +1,200 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use a2lfile::A2lObjectName;
+use goblin::elf::section_header::SHT_NOBITS;
+use goblin::elf::sym::{STB_LOCAL, STT_FILE, STT_OBJECT};
+use goblin::elf::Elf;
+
+use crate::{build_metadata, collect_core_entities, AppState, EntityUpdateResult};
+
+const ROOT_GROUP_NAME: &str = "ELF_Origin";
+
+fn group_identifier(prefix: &str, origin: &str) -> String {
+    let body: String = origin
+        .trim_start_matches('.')
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() || ch == '_' { ch } else { '_' })
+        .collect();
+    format!("{prefix}_{body}")
+}
+
+/// Compilation unit of every variable address described in `.debug_info`,
+/// keyed by address. Empty when the ELF carries no (uncompressed) DWARF.
+fn dwarf_compile_units(elf: &Elf, buffer: &[u8]) -> Result<HashMap<u64, String>, gimli::Error> {
+    let endian = if elf.little_endian {
+        gimli::RunTimeEndian::Little
+    } else {
+        gimli::RunTimeEndian::Big
+    };
+    let section = |id: gimli::SectionId| -> Result<gimli::EndianSlice<gimli::RunTimeEndian>, gimli::Error> {
+        let data = elf
+            .section_headers
+            .iter()
+            .find(|sh| sh.sh_type != SHT_NOBITS && elf.shdr_strtab.get_at(sh.sh_name) == Some(id.name()))
+            .and_then(|sh| buffer.get(sh.sh_offset as usize..(sh.sh_offset + sh.sh_size) as usize))
+            .unwrap_or(&[]);
+        Ok(gimli::EndianSlice::new(data, endian))
+    };
+    let dwarf = gimli::Dwarf::load(section)?;
+
+    let mut units = HashMap::new();
+    let mut headers = dwarf.units();
+    while let Some(header) = headers.next()? {
+        let unit = dwarf.unit(header)?;
+        let Some(unit_name) = unit.name else { continue };
+        let unit_name = unit_name.to_string_lossy();
+        // DW_AT_name is the source path as passed to the compiler; the file name
+        // matches what STT_FILE symbols carry.
+        let unit_name = Path::new(unit_name.as_ref())
+            .file_name()
+            .map_or_else(|| unit_name.to_string(), |name| name.to_string_lossy().into_owned());
+        let mut entries = unit.entries();
+        while let Some((_, entry)) = entries.next_dfs()? {
+            if entry.tag() != gimli::DW_TAG_variable {
+                continue;
+            }
+            let Some(gimli::AttributeValue::Exprloc(expression)) = entry.attr_value(gimli::DW_AT_location)? else {
+                continue;
+            };
+            let address = match expression.operations(unit.encoding()).next() {
+                Ok(Some(gimli::Operation::Address { address })) => address,
+                Ok(Some(gimli::Operation::AddressIndex { index })) => dwarf.address(&unit, index)?,
+                _ => continue,
+            };
+            units.insert(address, unit_name.clone());
+        }
+    }
+    Ok(units)
+}
+
+/// Maps every data symbol to the section it lives in and the translation unit
+/// it was emitted from. The unit comes from the DW_TAG_compile_unit that
+/// describes the symbol's address. Without DWARF it is taken from the
+/// preceding STT_FILE symbol, which linkers only keep for local symbols;
+/// globals then end up in "<global>".
+fn symbol_origins(elf: &Elf, buffer: &[u8]) -> HashMap<String, (String, String)> {
+    let compile_units = dwarf_compile_units(elf, buffer).unwrap_or_default();
+    let mut origins = HashMap::new();
+    let mut current_file = String::from("<global>");
+    for sym in elf.syms.iter() {
+        let name = elf.strtab.get_at(sym.st_name).unwrap_or("");
+        if sym.st_type() == STT_FILE {
+            current_file = if name.is_empty() { "<global>".to_string() } else { name.to_string() };
+            continue;
+        }
+        if sym.st_type() != STT_OBJECT || name.is_empty() {
+            continue;
+        }
+        let section = if sym.st_shndx < elf.section_headers.len() {
+            let sh = &elf.section_headers[sym.st_shndx];
+            elf.shdr_strtab.get_at(sh.sh_name).unwrap_or("").to_string()
+        } else {
+            String::new()
+        };
+        let file = match compile_units.get(&sym.st_value) {
+            Some(unit) => unit.clone(),
+            None if sym.st_bind() == STB_LOCAL => current_file.clone(),
+            None => "<global>".to_string(),
+        };
+        origins.insert(name.to_string(), (section, file));
+    }
+    origins
+}
+
+#[tauri::command]
+pub fn group_measurements_by_elf_origin(
+    path: String,
+    module_name: Option<String>,
+    origin: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<EntityUpdateResult, String> {
+    let by_file = match origin.as_str() {
+        "section" => false,
+        "file" | "compilation_unit" => true,
+        other => return Err(format!("Unknown origin kind: {other}")),
+    };
+
+    let buffer = fs::read(&path).map_err(|e| e.to_string())?;
+    let elf = Elf::parse(&buffer).map_err(|e| e.to_string())?;
+    let origins = symbol_origins(&elf, &buffer);
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("group_measurements_by_elf_origin", doc_id.as_deref())?;
+    edit.touch_target_module(module_name.as_deref())?;
+    let a2l = edit.a2l_mut();
+
+    let target_module = if let Some(name) = module_name {
+        a2l.project.module.iter_mut().find(|m| m.get_name() == name)
+            .ok_or(format!("Module {} not found", name))?
+    } else {
+        a2l.project.module.first_mut().ok_or("No modules in project")?
+    };
+
+    let mut assignments: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for measurement in target_module.measurement.iter() {
+        let symbol_name = measurement
+            .symbol_link
+            .as_ref()
+            .map(|link| link.symbol_name.as_str())
+            .unwrap_or_else(|| measurement.get_name());
+        if let Some((section, file)) = origins.get(symbol_name) {
+            let key = if by_file { file } else { section };
+            if key.is_empty() {
+                continue;
+            }
+            assignments
+                .entry(key.clone())
+                .or_default()
+                .push(measurement.get_name().to_string());
+        }
+    }
+
+    let prefix = if by_file { "ELF_CU" } else { "ELF_SEC" };
+    let mut child_groups = Vec::new();
+    for (key, members) in assignments {
+        let group_name = group_identifier(prefix, &key);
+        if target_module.group.get(&group_name).is_none() {
+            target_module
+                .group
+                .push(a2lfile::Group::new(group_name.clone(), format!("Symbols from {key}")));
+        }
+        let group = target_module
+            .group
+            .get_mut(&group_name)
+            .ok_or_else(|| format!("Group {group_name} could not be created"))?;
+        let ref_measurement = group.ref_measurement.get_or_insert_with(a2lfile::RefMeasurement::new);
+        for member in members {
+            if !ref_measurement.identifier_list.contains(&member) {
+                ref_measurement.identifier_list.push(member);
+            }
+        }
+        child_groups.push(group_name);
+    }
+
+    if !child_groups.is_empty() {
+        if target_module.group.get(ROOT_GROUP_NAME).is_none() {
+            let mut root = a2lfile::Group::new(
+                ROOT_GROUP_NAME.to_string(),
+                "Measurements grouped by ELF origin".to_string(),
+            );
+            root.root = Some(a2lfile::Root::new());
+            target_module.group.push(root);
+        }
+        if let Some(root) = target_module.group.get_mut(ROOT_GROUP_NAME) {
+            let sub_group = root.sub_group.get_or_insert_with(a2lfile::SubGroup::new);
+            for child in child_groups {
+                if !sub_group.identifier_list.contains(&child) {
+                    sub_group.identifier_list.push(child);
+                }
+            }
+        }
+    }
+
+    Ok(EntityUpdateResult {
+        metadata: build_metadata(a2l, 0),
+        entities: collect_core_entities(a2l),
+    })
+}
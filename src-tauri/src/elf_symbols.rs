@@ -0,0 +1,104 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+use crate::{AppState, ElfSymbol};
+
+/// Default and maximum page size of `query_elf_symbols`.
+const DEFAULT_LIMIT: usize = 500;
+const MAX_LIMIT: usize = 10_000;
+
+/// Symbols of the last loaded ELF, sorted by name.
+pub(crate) struct ElfIndex {
+    path: String,
+    symbols: Vec<ElfSymbol>,
+}
+
+#[derive(Serialize)]
+pub struct ElfSummary {
+    path: String,
+    symbol_count: usize,
+    sections: Vec<String>,
+    binds: Vec<String>,
+    types: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct ElfSymbolPage {
+    total: usize,
+    offset: usize,
+    symbols: Vec<ElfSymbol>,
+}
+
+impl ElfIndex {
+    pub(crate) fn new(path: String, symbols: Vec<ElfSymbol>) -> Self {
+        Self { path, symbols }
+    }
+
+    pub(crate) fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub(crate) fn symbols(&self) -> &[ElfSymbol] {
+        &self.symbols
+    }
+
+    pub(crate) fn summary(&self) -> ElfSummary {
+        let distinct = |field: fn(&ElfSymbol) -> &String| -> Vec<String> {
+            self.symbols
+                .iter()
+                .map(field)
+                .filter(|value| !value.is_empty())
+                .cloned()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect()
+        };
+        ElfSummary {
+            path: self.path.clone(),
+            symbol_count: self.symbols.len(),
+            sections: distinct(|symbol| &symbol.section),
+            binds: distinct(|symbol| &symbol.bind),
+            types: distinct(|symbol| &symbol.type_str),
+        }
+    }
+}
+
+/// Filtered, paginated view of the loaded ELF symbols. `filter` is a
+/// case-insensitive substring of the symbol name; the other filters must
+/// match exactly.
+#[tauri::command]
+pub fn query_elf_symbols(
+    filter: Option<String>,
+    section: Option<String>,
+    bind: Option<String>,
+    type_str: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    state: tauri::State<AppState>,
+) -> Result<ElfSymbolPage, String> {
+    let guard = state.elf.lock().map_err(|_| "State lock poisoned")?;
+    let index = guard.as_ref().ok_or_else(|| "No ELF loaded".to_string())?;
+
+    let needle = filter.map(|f| f.trim().to_lowercase()).filter(|f| !f.is_empty());
+    let matches = |symbol: &&ElfSymbol| {
+        needle
+            .as_ref()
+            .is_none_or(|needle| symbol.name.to_lowercase().contains(needle.as_str()))
+            && section.as_ref().is_none_or(|section| &symbol.section == section)
+            && bind.as_ref().is_none_or(|bind| &symbol.bind == bind)
+            && type_str.as_ref().is_none_or(|type_str| &symbol.type_str == type_str)
+    };
+
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    let mut total = 0;
+    let mut symbols = Vec::new();
+    for symbol in index.symbols.iter().filter(matches) {
+        if total >= offset && symbols.len() < limit {
+            symbols.push(symbol.clone());
+        }
+        total += 1;
+    }
+    Ok(ElfSymbolPage { total, offset, symbols })
+}
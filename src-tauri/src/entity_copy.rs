@@ -0,0 +1,179 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use a2lfile::A2lObjectName;
+use serde::Serialize;
+
+use crate::references::{for_each_reference, is_null_reference, object_exists, target_candidates};
+use crate::{find_module, find_module_mut, AppState};
+
+/// Reference targets that are copied along with an object. Measurements used as
+/// input quantities, functions and groups are left out on purpose: they belong
+/// to the target project's own structure.
+pub(crate) const DEPENDENCY_TARGETS: &[&str] = &[
+    "CompuMethod",
+    "CompuTab",
+    "CompuVtab",
+    "RecordLayout",
+    "Unit",
+    "AxisPts",
+    "Characteristic",
+];
+
+#[derive(Serialize)]
+pub struct CopiedEntity {
+    kind: String,
+    name: String,
+    dependency: bool,
+}
+
+#[derive(Serialize)]
+pub struct CopyResult {
+    copied: Vec<CopiedEntity>,
+    collisions: Vec<CopiedEntity>,
+    missing: Vec<String>,
+}
+
+/// Clones `kind`/`name` from `from` into `to`. Returns false if `from` has no
+/// such object.
+pub(crate) fn transfer(from: &a2lfile::Module, to: &mut a2lfile::Module, kind: &str, name: &str) -> bool {
+    macro_rules! copy_item {
+        ($list:ident) => {
+            match from.$list.get(name) {
+                Some(item) => {
+                    to.$list.push(item.clone());
+                    true
+                }
+                None => false,
+            }
+        };
+    }
+    match kind {
+        "Measurement" => copy_item!(measurement),
+        "Characteristic" => copy_item!(characteristic),
+        "AxisPts" => copy_item!(axis_pts),
+        "Blob" => copy_item!(blob),
+        "Instance" => copy_item!(instance),
+        "CompuMethod" => copy_item!(compu_method),
+        "CompuTab" => copy_item!(compu_tab),
+        "CompuVtab" => copy_item!(compu_vtab),
+        "CompuVtabRange" => copy_item!(compu_vtab_range),
+        "RecordLayout" => copy_item!(record_layout),
+        "Unit" => copy_item!(unit),
+        "Function" => copy_item!(function),
+        "Group" => copy_item!(group),
+        "Frame" => copy_item!(frame),
+        "TypedefStructure" => copy_item!(typedef_structure),
+        "TypedefMeasurement" => copy_item!(typedef_measurement),
+        "TypedefCharacteristic" => copy_item!(typedef_characteristic),
+        "TypedefAxis" => copy_item!(typedef_axis),
+        "TypedefBlob" => copy_item!(typedef_blob),
+        _ => false,
+    }
+}
+
+/// `seeds` followed by everything they reference through `targets` kinds, in
+/// breadth-first order. The flag marks objects pulled in as dependencies.
+/// Functions and groups only list their members, so their references are not
+/// followed.
+pub(crate) fn dependency_closure(
+    module: &a2lfile::Module,
+    seeds: &[(String, String)],
+    targets: &[&str],
+) -> Vec<(String, String, bool)> {
+    let mut edges: HashMap<(String, String), Vec<(String, String)>> = HashMap::new();
+    for_each_reference(module, &mut |source_kind, source_name, _field, target_kind, target| {
+        if matches!(source_kind, "Function" | "Group")
+            || !targets.contains(&target_kind)
+            || is_null_reference(target)
+        {
+            return;
+        }
+        if let Some(resolved) = target_candidates(target_kind)
+            .iter()
+            .find(|candidate| object_exists(module, candidate, target))
+        {
+            edges
+                .entry((source_kind.to_string(), source_name.to_string()))
+                .or_default()
+                .push((resolved.to_string(), target.to_string()));
+        }
+    });
+
+    let mut seen = BTreeSet::new();
+    let mut order = Vec::new();
+    let mut queue: VecDeque<(String, String, bool)> = seeds
+        .iter()
+        .map(|(kind, name)| (kind.clone(), name.clone(), false))
+        .collect();
+    while let Some((kind, name, dependency)) = queue.pop_front() {
+        if !seen.insert((kind.clone(), name.clone())) {
+            continue;
+        }
+        if let Some(targets) = edges.get(&(kind.clone(), name.clone())) {
+            queue.extend(targets.iter().map(|(k, n)| (k.clone(), n.clone(), true)));
+        }
+        order.push((kind, name, dependency));
+    }
+    order
+}
+
+/// Copies objects of `kind` between modules of the same or different open
+/// documents. Objects whose name already exists in the target module are not
+/// overwritten and are reported as collisions instead.
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub fn copy_entities(
+    source_doc: Option<String>,
+    target_doc: Option<String>,
+    source_module: Option<String>,
+    target_module: Option<String>,
+    kind: String,
+    names: Vec<String>,
+    with_dependencies: bool,
+    state: tauri::State<AppState>,
+) -> Result<CopyResult, String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+
+    // Stage the clones first so that copies within one document do not need
+    // overlapping borrows.
+    let source = find_module(guard.get(source_doc.as_deref())?, source_module.as_deref())?;
+    let seeds: Vec<(String, String)> = names.iter().map(|name| (kind.clone(), name.clone())).collect();
+    let targets = if with_dependencies { DEPENDENCY_TARGETS } else { &[] };
+    let order = dependency_closure(source, &seeds, targets);
+    let mut staging = a2lfile::Module::new(source.get_name().to_string(), String::new());
+    let mut missing = Vec::new();
+    let mut staged = Vec::new();
+    for (item_kind, name, dependency) in order {
+        if transfer(source, &mut staging, &item_kind, &name) {
+            staged.push((item_kind, name, dependency));
+        } else {
+            missing.push(name);
+        }
+    }
+
+    let mut edit = guard.edit("copy_entities", target_doc.as_deref())?;
+    let target_name = find_module(edit.a2l(), target_module.as_deref())?.get_name().to_string();
+    for (kind, name, _) in &staged {
+        edit.touch(&target_name, kind, name);
+    }
+    let target = find_module_mut(edit.a2l_mut(), target_module.as_deref())?;
+    let mut result = CopyResult {
+        copied: Vec::new(),
+        collisions: Vec::new(),
+        missing,
+    };
+    for (kind, name, dependency) in staged {
+        let entity = CopiedEntity {
+            kind: kind.clone(),
+            name: name.clone(),
+            dependency,
+        };
+        if object_exists(target, &kind, &name) {
+            result.collisions.push(entity);
+        } else {
+            transfer(&staging, target, &kind, &name);
+            result.copied.push(entity);
+        }
+    }
+    Ok(result)
+}
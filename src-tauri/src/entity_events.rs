@@ -0,0 +1,95 @@
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+use crate::audit::AuditChange;
+use crate::entity_source::{MODULE_HEADER, PROJECT_HEADER};
+use crate::references::OBJECT_KINDS;
+use crate::AppState;
+
+const ADDED_EVENT: &str = "entity-added";
+const UPDATED_EVENT: &str = "entity-updated";
+const REMOVED_EVENT: &str = "entity-removed";
+/// Sent instead of single events when an edit touches more than `MAX_EVENTS`
+/// objects or only changes their order; the frontend re-fetches the tree.
+const RESET_EVENT: &str = "entity-tree-reset";
+const MAX_EVENTS: usize = 500;
+
+#[derive(Serialize, Clone)]
+struct EntityEvent {
+    doc_id: String,
+    kind: String,
+    name: String,
+    module: String,
+    /// Tree item ID for `get_entity_tree_item`.
+    id: String,
+}
+
+#[derive(Serialize, Clone)]
+struct ResetEvent {
+    doc_id: String,
+}
+
+
+fn event(doc_id: &str, module: &str, kind: &str, name: &str) -> EntityEvent {
+    EntityEvent {
+        doc_id: doc_id.to_string(),
+        kind: kind.to_string(),
+        name: name.to_string(),
+        module: module.to_string(),
+        id: if kind == MODULE_HEADER {
+            module.to_string()
+        } else {
+            format!("{module}::{kind}::{name}")
+        },
+    }
+}
+
+/// Event for a changed tree item. Module header changes (MOD_PAR, IF_DATA,
+/// transformers, ...) are reported as an update of the module; the project
+/// header is not part of the tree.
+fn entity_event(doc_id: &str, change: &AuditChange) -> Option<(&'static str, EntityEvent)> {
+    if change.kind != MODULE_HEADER && !OBJECT_KINDS.contains(&change.kind.as_str()) {
+        return None;
+    }
+    let name = match (&change.old_value, &change.new_value) {
+        (None, _) => ADDED_EVENT,
+        (_, None) => REMOVED_EVENT,
+        _ => UPDATED_EVENT,
+    };
+    Some((name, event(doc_id, &change.module, &change.kind, &change.name)))
+}
+
+/// Emits the changes of one edit and adds them to the change log. An edit
+/// that changed no part, only the object order, resets the tree and is logged
+/// against the project without values.
+pub(crate) fn publish(app: &tauri::AppHandle, doc_id: &str, command: &str, mut changes: Vec<AuditChange>) {
+    let events: Vec<_> = changes.iter().filter_map(|change| entity_event(doc_id, change)).collect();
+    if changes.is_empty() || events.len() > MAX_EVENTS {
+        let _ = app.emit(RESET_EVENT, ResetEvent { doc_id: doc_id.to_string() });
+    } else {
+        for (name, payload) in events {
+            let _ = app.emit(name, payload);
+        }
+    }
+    if changes.is_empty() {
+        changes.push(AuditChange {
+            module: String::new(),
+            kind: PROJECT_HEADER.to_string(),
+            name: String::new(),
+            old_value: None,
+            new_value: None,
+        });
+    }
+    if let Ok(mut audit) = app.state::<AppState>().audit.lock() {
+        audit.record(doc_id, command, changes);
+    }
+}
+
+/// Hands the app to the document store, so edits publish their changes as
+/// they are committed. Newly opened documents emit nothing; the frontend
+/// loads their tree anyway.
+pub(crate) fn start(app: tauri::AppHandle) {
+    if let Ok(mut documents) = app.state::<AppState>().documents.lock() {
+        documents.app = Some(app.clone());
+    }
+}
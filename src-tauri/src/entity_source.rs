@@ -0,0 +1,237 @@
+use a2lfile::{A2lObject, A2lObjectName};
+
+use crate::entity_copy::transfer;
+use crate::references::object_exists;
+use crate::{find_module_mut, AppState};
+
+/// Serializes a file that contains nothing but `module` and returns the text
+/// between `/begin MODULE` and `/end MODULE`, dedented. Include origins are
+/// cleared, so the objects are written inline instead of as `/include` lines.
+pub(crate) fn module_body(mut module: a2lfile::Module) -> String {
+    module.get_layout_mut().incfile = None;
+    macro_rules! inline {
+        ($($list:ident),*) => {
+            $(for item in module.$list.iter_mut() {
+                item.get_layout_mut().incfile = None;
+            })*
+        };
+    }
+    inline!(
+        measurement, characteristic, axis_pts, blob, instance, compu_method, compu_tab, compu_vtab,
+        compu_vtab_range, record_layout, unit, function, group, frame, typedef_structure,
+        typedef_measurement, typedef_characteristic, typedef_axis, typedef_blob
+    );
+    let mut file = a2lfile::A2lFile::new(a2lfile::Project::new("SOURCE".to_string(), String::new()));
+    file.project.module.push(module);
+    let text = file.write_to_string();
+
+    let lines: Vec<&str> = text
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("/begin MODULE"))
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with("/end MODULE"))
+        .collect();
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `kind` of the part of a module outside its object lists.
+pub(crate) const MODULE_HEADER: &str = "Module";
+/// `kind` of the project header and the file's version numbers.
+pub(crate) const PROJECT_HEADER: &str = "Project";
+
+/// Copy of one part of `a2l` in an otherwise empty file: the object
+/// `kind`/`name` of `module`, a module header or the project header. `None` if
+/// the part does not exist.
+pub(crate) fn extract_part(a2l: &a2lfile::A2lFile, module: &str, kind: &str, name: &str) -> Option<a2lfile::A2lFile> {
+    let project = &a2l.project;
+    let mut part = a2lfile::A2lFile::new(a2lfile::Project::new(project.get_name().to_string(), String::new()));
+    if kind == PROJECT_HEADER {
+        part.asap2_version = a2l.asap2_version.clone();
+        part.a2ml_version = a2l.a2ml_version.clone();
+        part.project.long_identifier = project.long_identifier.clone();
+        part.project.header = project.header.clone();
+        return Some(part);
+    }
+    let source = project.module.iter().find(|m| m.get_name() == module)?;
+    let mut target = a2lfile::Module::new(module.to_string(), String::new());
+    if kind == MODULE_HEADER {
+        target.long_identifier = source.long_identifier.clone();
+        target.a2ml = source.a2ml.clone();
+        target.if_data = source.if_data.clone();
+        target.mod_common = source.mod_common.clone();
+        target.mod_par = source.mod_par.clone();
+        target.variant_coding = source.variant_coding.clone();
+        target.user_rights = source.user_rights.clone();
+        target.transformer = source.transformer.clone();
+    } else if !transfer(source, &mut target, kind, name) {
+        return None;
+    }
+    part.project.module.push(target);
+    Some(part)
+}
+
+/// Text of a part from `extract_part`. Headers keep their enclosing lines so
+/// that a changed name or description shows.
+pub(crate) fn part_text(part: &a2lfile::A2lFile, kind: &str) -> String {
+    match part.project.module.iter().next() {
+        Some(module) if kind != MODULE_HEADER && kind != PROJECT_HEADER => module_body(module.clone()),
+        _ => part.write_to_string().trim_end().to_string(),
+    }
+}
+
+/// Parses `text` as the body of an otherwise empty module. IF_DATA blocks are
+/// checked against `a2ml` when given. Text the parser only accepts with
+/// warnings is rejected, since applying it would silently drop parts of it.
+pub(crate) fn parse_snippet(text: &str, a2ml: Option<&str>) -> Result<a2lfile::Module, String> {
+    let a2ml_block = a2ml
+        .map(|spec| format!("/begin A2ML\n{spec}\n/end A2ML\n"))
+        .unwrap_or_default();
+    let wrapped = format!(
+        "ASAP2_VERSION 1 71\n/begin PROJECT SOURCE \"\"\n/begin MODULE SOURCE \"\"\n{a2ml_block}{text}\n/end MODULE\n/end PROJECT\n"
+    );
+    let (a2l, warnings) = a2lfile::load_from_string(&wrapped, None, false).map_err(|error| error.to_string())?;
+    if !warnings.is_empty() {
+        let messages: Vec<String> = warnings.iter().map(|warning| warning.to_string()).collect();
+        return Err(format!("Snippet is not valid: {}", messages.join("; ")));
+    }
+    a2l.project
+        .module
+        .iter()
+        .next()
+        .cloned()
+        .ok_or_else(|| "Snippet contains no module".to_string())
+}
+
+/// Single object of `kind` in `module`, which must contain nothing else.
+pub(crate) fn single_object_name(module: &a2lfile::Module, kind: &str) -> Result<String, String> {
+    macro_rules! single {
+        ($list:ident) => {{
+            let total = module.measurement.len()
+                + module.characteristic.len()
+                + module.axis_pts.len()
+                + module.blob.len()
+                + module.instance.len()
+                + module.compu_method.len()
+                + module.compu_tab.len()
+                + module.compu_vtab.len()
+                + module.compu_vtab_range.len()
+                + module.record_layout.len()
+                + module.unit.len()
+                + module.function.len()
+                + module.group.len()
+                + module.frame.len();
+            match module.$list.iter().next() {
+                Some(item) if total == 1 => Ok(item.get_name().to_string()),
+                _ => Err(format!("Snippet must contain exactly one {kind} and nothing else")),
+            }
+        }};
+    }
+    match kind {
+        "Measurement" => single!(measurement),
+        "Characteristic" => single!(characteristic),
+        "AxisPts" => single!(axis_pts),
+        "Blob" => single!(blob),
+        "Instance" => single!(instance),
+        "CompuMethod" => single!(compu_method),
+        "CompuTab" => single!(compu_tab),
+        "CompuVtab" => single!(compu_vtab),
+        "CompuVtabRange" => single!(compu_vtab_range),
+        "RecordLayout" => single!(record_layout),
+        "Unit" => single!(unit),
+        "Function" => single!(function),
+        "Group" => single!(group),
+        "Frame" => single!(frame),
+        _ => Err(format!("Unsupported kind {kind}")),
+    }
+}
+
+/// Overwrites `name` in `target` with the single object of `source`, keeping
+/// its position in the list.
+pub(crate) fn replace_object(target: &mut a2lfile::Module, source: a2lfile::Module, kind: &str, name: &str) {
+    macro_rules! replace_item {
+        ($list:ident) => {
+            if let (Some(item), Some(new_item)) = (target.$list.get_mut(name), source.$list.iter().next()) {
+                *item = new_item.clone();
+            }
+        };
+    }
+    match kind {
+        "Measurement" => replace_item!(measurement),
+        "Characteristic" => replace_item!(characteristic),
+        "AxisPts" => replace_item!(axis_pts),
+        "Blob" => replace_item!(blob),
+        "Instance" => replace_item!(instance),
+        "CompuMethod" => replace_item!(compu_method),
+        "CompuTab" => replace_item!(compu_tab),
+        "CompuVtab" => replace_item!(compu_vtab),
+        "CompuVtabRange" => replace_item!(compu_vtab_range),
+        "RecordLayout" => replace_item!(record_layout),
+        "Unit" => replace_item!(unit),
+        "Function" => replace_item!(function),
+        "Group" => replace_item!(group),
+        "Frame" => replace_item!(frame),
+        _ => {}
+    }
+}
+
+#[tauri::command]
+pub fn get_entity_source(
+    kind: String,
+    name: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<String, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    for module in a2l.project.module.iter() {
+        let mut single = a2lfile::Module::new("SOURCE".to_string(), String::new());
+        if transfer(module, &mut single, &kind, &name) {
+            return Ok(module_body(single));
+        }
+    }
+    Err(format!("{kind} '{name}' not found"))
+}
+
+/// Parses an edited source block and replaces the object in place. Renaming
+/// through the text is allowed as long as the new name is free.
+#[tauri::command]
+pub fn apply_entity_source(
+    kind: String,
+    name: String,
+    text: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<String, String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let module = guard
+        .get(doc_id.as_deref())?
+        .project
+        .module
+        .iter()
+        .find(|module| object_exists(module, &kind, &name))
+        .ok_or_else(|| format!("{kind} '{name}' not found"))?;
+    let module_id = module.get_name().to_string();
+    let a2ml = module.a2ml.as_ref().map(|a2ml| a2ml.a2ml_text.clone());
+    let parsed = parse_snippet(&text, a2ml.as_deref())?;
+    let new_name = single_object_name(&parsed, &kind)?;
+
+    let mut edit = guard.edit("apply_entity_source", doc_id.as_deref())?;
+    edit.touch(&module_id, &kind, &name);
+    edit.touch(&module_id, &kind, &new_name);
+    let module = find_module_mut(edit.a2l_mut(), Some(&module_id))?;
+    if new_name != name && object_exists(module, &kind, &new_name) {
+        return Err(format!("{kind} '{new_name}' already exists"));
+    }
+    replace_object(module, parsed, &kind, &name);
+    Ok(new_name)
+}
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+
+use crate::hex::MemoryImage;
+use crate::{find_module, parse_hex_address, AppState};
+
+#[derive(Serialize)]
+pub struct EpkCheck {
+    address: String,
+    expected: String,
+    found: Option<String>,
+    matches: bool,
+}
+
+/// Checksum the ECU expects for the CALIBRATION_HANDLE region at `address`.
+#[derive(Deserialize)]
+pub struct ExpectedChecksum {
+    address: String,
+    /// "sum32" or "crc32".
+    algorithm: String,
+    value: String,
+}
+
+#[derive(Serialize)]
+pub struct ChecksumRegion {
+    method: String,
+    address: String,
+    size: u32,
+    present: bool,
+    sum32: Option<String>,
+    crc32: Option<String>,
+    expected: Option<String>,
+    /// `None` if no expected value was given or the region is not in the image.
+    checksum_matches: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct EpkReport {
+    hex_path: String,
+    epk: Option<String>,
+    epk_checks: Vec<EpkCheck>,
+    regions: Vec<ChecksumRegion>,
+    passed: bool,
+}
+
+/// CRC-32 (IEEE 802.3, reflected polynomial 0xEDB88320).
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn check_epk(image: &MemoryImage, epk: &str, address: u32) -> EpkCheck {
+    let found = image.read(address, epk.len() as u32).map(|bytes| {
+        String::from_utf8_lossy(&bytes)
+            .trim_end_matches('\0')
+            .to_string()
+    });
+    EpkCheck {
+        address: format!("0x{:X}", address),
+        expected: epk.to_string(),
+        matches: found.as_deref() == Some(epk),
+        found,
+    }
+}
+
+/// Region described by a CALIBRATION_HANDLE. The handle layout is vendor
+/// specific; the common convention of a start address followed by a size is
+/// assumed, further values are ignored.
+fn check_region(
+    image: &MemoryImage,
+    method: &str,
+    handle: &a2lfile::CalibrationHandle,
+    expected: &[(u32, String, u32)],
+) -> Option<ChecksumRegion> {
+    let address = *handle.handle.first()? as u32;
+    let size = *handle.handle.get(1)? as u32;
+    let data = image.read(address, size);
+    let sum = data
+        .as_ref()
+        .map(|data| data.iter().fold(0u32, |sum, byte| sum.wrapping_add(u32::from(*byte))));
+    let crc = data.as_deref().map(crc32);
+    let expected = expected.iter().find(|(region, _, _)| *region == address);
+    let checksum_matches = expected.and_then(|(_, algorithm, value)| {
+        let actual = if algorithm == "crc32" { crc } else { sum };
+        actual.map(|actual| actual == *value)
+    });
+    Some(ChecksumRegion {
+        method: method.to_string(),
+        address: format!("0x{:X}", address),
+        size,
+        present: data.is_some(),
+        sum32: sum.map(|sum| format!("0x{:08X}", sum)),
+        crc32: crc.map(|crc| format!("0x{:08X}", crc)),
+        expected: expected.map(|(_, algorithm, value)| format!("{algorithm} 0x{value:08X}")),
+        checksum_matches,
+    })
+}
+
+/// Validated `expected` entries as (address, algorithm, value).
+fn parse_expected(expected: Vec<ExpectedChecksum>) -> Result<Vec<(u32, String, u32)>, String> {
+    expected
+        .into_iter()
+        .map(|checksum| {
+            let algorithm = checksum.algorithm.to_lowercase();
+            if algorithm != "sum32" && algorithm != "crc32" {
+                return Err(format!("Unknown checksum algorithm: {}", checksum.algorithm));
+            }
+            let address = parse_hex_address(&checksum.address)?;
+            let value = parse_hex_address(&checksum.value)
+                .map_err(|_| format!("Invalid checksum value: {}", checksum.value))?;
+            Ok((address, algorithm, value))
+        })
+        .collect()
+}
+
+/// Checks the document against its loaded hex image: the EPK string must be
+/// found at every ADDR_EPK, and every CALIBRATION_METHOD region must be fully
+/// contained in the image and match the checksum the ECU expects, given in
+/// `expected_checksums` by region address. A region without an expected
+/// checksum cannot be verified and fails the check.
+#[tauri::command]
+pub fn verify_epk(
+    module_name: Option<String>,
+    expected_checksums: Option<Vec<ExpectedChecksum>>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<EpkReport, String> {
+    let expected = parse_expected(expected_checksums.unwrap_or_default())?;
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let document = guard.document(doc_id.as_deref())?;
+    let (hex_path, image) = document
+        .hex_image
+        .as_ref()
+        .ok_or_else(|| "No hex image loaded".to_string())?;
+    let module = find_module(&document.a2l, module_name.as_deref())?;
+
+    let epk = module
+        .mod_par
+        .as_ref()
+        .and_then(|mod_par| mod_par.epk.as_ref())
+        .map(|epk| epk.identifier.clone());
+    let epk_checks: Vec<EpkCheck> = match (&epk, &module.mod_par) {
+        (Some(epk), Some(mod_par)) => mod_par
+            .addr_epk
+            .iter()
+            .map(|addr| check_epk(image, epk, addr.address))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let regions: Vec<ChecksumRegion> = module
+        .mod_par
+        .iter()
+        .flat_map(|mod_par| mod_par.calibration_method.iter())
+        .flat_map(|method| {
+            method
+                .calibration_handle
+                .iter()
+                .filter_map(|handle| check_region(image, &method.method, handle, &expected))
+        })
+        .collect();
+
+    let passed = epk.is_some()
+        && !epk_checks.is_empty()
+        && epk_checks.iter().all(|check| check.matches)
+        && regions.iter().all(|region| region.present && region.checksum_matches == Some(true));
+    Ok(EpkReport {
+        hex_path: hex_path.clone(),
+        epk,
+        epk_checks,
+        regions,
+        passed,
+    })
+}
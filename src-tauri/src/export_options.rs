@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+use crate::includes::include_origins;
+use crate::ordering::sort_modules;
+use crate::AppState;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExportOptions {
+    /// Spaces per nesting level; `None` keeps the layout produced by the
+    /// writer, which reproduces the original whitespace of parsed items.
+    indentation: Option<usize>,
+    /// "original", "new_items", "alphabetical" or "address".
+    sort_mode: String,
+    /// "lf" or "crlf".
+    line_endings: String,
+    /// "merged" writes included objects into the main file, "preserve"
+    /// keeps the `/include` lines and rewrites the include files on save.
+    pub(crate) include_mode: String,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            indentation: None,
+            sort_mode: "original".to_string(),
+            line_endings: "lf".to_string(),
+            include_mode: "merged".to_string(),
+        }
+    }
+}
+
+fn reindent(text: &str, width: usize) -> String {
+    // The writer's indentation unit is the smallest non-zero leading run.
+    let unit = text
+        .lines()
+        .map(|line| line.len() - line.trim_start_matches(' ').len())
+        .filter(|&indent| indent > 0)
+        .min()
+        .unwrap_or(1);
+    let mut out = String::with_capacity(text.len());
+    for line in text.lines() {
+        let trimmed = line.trim_start_matches(' ');
+        let level = (line.len() - trimmed.len()) / unit;
+        out.push_str(&" ".repeat(level * width));
+        out.push_str(trimmed);
+        out.push('\n');
+    }
+    out
+}
+
+/// Serializes the file according to the export options. Sorting is applied
+/// to a copy so the in-memory model keeps the order the user sees.
+pub(crate) fn render_a2l(a2l: &a2lfile::A2lFile, options: &ExportOptions) -> String {
+    let merged;
+    let a2l = if options.include_mode == "merged" && !include_origins(a2l).is_empty() {
+        let mut copy = a2l.clone();
+        copy.merge_includes();
+        merged = copy;
+        &merged
+    } else {
+        a2l
+    };
+    let mut text = match options.sort_mode.as_str() {
+        "alphabetical" => {
+            let mut sorted = a2l.clone();
+            sorted.sort();
+            sorted.write_to_string()
+        }
+        "address" => {
+            let mut sorted = a2l.clone();
+            // Only fails for an unknown order.
+            let _ = sort_modules(&mut sorted, "address");
+            sorted.write_to_string()
+        }
+        "new_items" => {
+            let mut sorted = a2l.clone();
+            sorted.sort_new_items();
+            sorted.write_to_string()
+        }
+        _ => a2l.write_to_string(),
+    };
+    if let Some(width) = options.indentation {
+        text = reindent(&text, width);
+    }
+    if options.line_endings == "crlf" {
+        text = text.replace("\r\n", "\n").replace('\n', "\r\n");
+    }
+    text
+}
+
+#[tauri::command]
+pub fn get_export_options(state: tauri::State<AppState>) -> Result<ExportOptions, String> {
+    Ok(state.export_options.lock().map_err(|_| "State lock poisoned")?.clone())
+}
+
+#[tauri::command]
+pub fn set_export_options(
+    indentation: Option<usize>,
+    sort_mode: String,
+    line_endings: String,
+    include_mode: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    if let Some(mode) = include_mode.as_deref().filter(|mode| !matches!(*mode, "merged" | "preserve")) {
+        return Err(format!("Unknown include mode: {mode}"));
+    }
+    if !matches!(sort_mode.as_str(), "original" | "new_items" | "alphabetical" | "address") {
+        return Err(format!("Unknown sort mode: {sort_mode}"));
+    }
+    if !matches!(line_endings.as_str(), "lf" | "crlf") {
+        return Err(format!("Unknown line ending style: {line_endings}"));
+    }
+    let mut options = state.export_options.lock().map_err(|_| "State lock poisoned")?;
+    // Callers that only set the formatting keep the current include mode.
+    let include_mode = include_mode.unwrap_or_else(|| options.include_mode.clone());
+    *options = ExportOptions {
+        indentation,
+        sort_mode,
+        line_endings,
+        include_mode,
+    };
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use a2lfile::{A2lObjectName, A2lObjectNameSetter};
+use serde::{Deserialize, Serialize};
+
+use crate::{find_module, find_module_mut, AppState};
+
+#[derive(Serialize, Deserialize)]
+pub struct FrameData {
+    name: String,
+    long_identifier: String,
+    /// ASAP2 time unit code of `rate` (e.g. 6 = 1 ms, 7 = 10 ms).
+    scaling_unit: u16,
+    rate: u32,
+    measurements: Vec<String>,
+}
+
+fn frame_data(frame: &a2lfile::Frame) -> FrameData {
+    FrameData {
+        name: frame.get_name().to_string(),
+        long_identifier: frame.long_identifier.clone(),
+        scaling_unit: frame.scaling_unit,
+        rate: frame.rate,
+        measurements: frame
+            .frame_measurement
+            .as_ref()
+            .map(|fm| fm.identifier_list.clone())
+            .unwrap_or_default(),
+    }
+}
+
+fn validate(module: &a2lfile::Module, data: &FrameData) -> Result<(), String> {
+    if data.name.trim().is_empty() {
+        return Err("Frame name must not be empty".to_string());
+    }
+    let missing: Vec<&str> = data
+        .measurements
+        .iter()
+        .filter(|name| module.measurement.get(name).is_none())
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("Unknown measurements: {}", missing.join(", ")));
+    }
+    Ok(())
+}
+
+fn apply(frame: &mut a2lfile::Frame, data: FrameData) {
+    frame.set_name(data.name);
+    frame.long_identifier = data.long_identifier;
+    frame.scaling_unit = data.scaling_unit;
+    frame.rate = data.rate;
+    frame.frame_measurement = (!data.measurements.is_empty()).then(|| {
+        let mut frame_measurement = a2lfile::FrameMeasurement::new();
+        frame_measurement.identifier_list = data.measurements;
+        frame_measurement
+    });
+}
+
+#[tauri::command]
+pub fn list_frames(
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<FrameData>, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let module = find_module(a2l, module_name.as_deref())?;
+    Ok(module.frame.iter().map(frame_data).collect())
+}
+
+#[tauri::command]
+pub fn create_frame(
+    module_name: Option<String>,
+    data: FrameData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("create_frame", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch(&module_id, "Frame", &data.name);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+    validate(module, &data)?;
+    if module.frame.get(&data.name).is_some() {
+        return Err(format!("Frame '{}' already exists", data.name));
+    }
+
+    let mut frame = a2lfile::Frame::new(data.name.clone(), String::new(), 0, 0);
+    apply(&mut frame, data);
+    module.frame.push(frame);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn update_frame(
+    module_name: Option<String>,
+    name: String,
+    data: FrameData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("update_frame", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch(&module_id, "Frame", &name);
+    edit.touch(&module_id, "Frame", &data.name);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+    validate(module, &data)?;
+    if data.name != name && module.frame.get(&data.name).is_some() {
+        return Err(format!("Frame '{}' already exists", data.name));
+    }
+
+    let frame = module
+        .frame
+        .iter_mut()
+        .find(|frame| frame.get_name() == name)
+        .ok_or_else(|| format!("Frame '{}' not found", name))?;
+    apply(frame, data);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_frame(
+    module_name: Option<String>,
+    name: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("delete_frame", doc_id.as_deref())?;
+    edit.touch_object("Frame", &name);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+
+    let before = module.frame.len();
+    module.frame.retain(|frame| frame.get_name() != name);
+    if module.frame.len() == before {
+        return Err(format!("Frame '{}' not found", name));
+    }
+    Ok(())
+}
@@ -0,0 +1,221 @@
+use std::fs;
+
+use serde::Serialize;
+
+use crate::AppState;
+
+/// Sparse memory image as contiguous segments, sorted by start address.
+/// Adjacent or overlapping writes are merged into one segment.
+#[derive(Default, Clone)]
+pub(crate) struct MemoryImage {
+    segments: Vec<(u32, Vec<u8>)>,
+}
+
+impl MemoryImage {
+    /// Writes `data` at `address`; later writes override earlier ones. Data
+    /// running past the end of the 32-bit address space wraps around to 0.
+    pub(crate) fn write(&mut self, address: u32, data: &[u8]) {
+        let room = (1u64 << 32) - u64::from(address);
+        if data.len() as u64 > room {
+            let (head, tail) = data.split_at(room as usize);
+            self.write(address, head);
+            self.write(0, tail);
+            return;
+        }
+        if data.is_empty() {
+            return;
+        }
+        let start = u64::from(address);
+        let end = start + data.len() as u64;
+        let segment_end = |(segment_start, bytes): &(u32, Vec<u8>)| u64::from(*segment_start) + bytes.len() as u64;
+        // Segments touching [start, end) are merged with the new data.
+        let first = self.segments.partition_point(|segment| segment_end(segment) < start);
+        let last = self.segments.partition_point(|(segment_start, _)| u64::from(*segment_start) <= end);
+        let merged_start = self.segments[first..last]
+            .first()
+            .map_or(start, |(segment_start, _)| start.min(u64::from(*segment_start)));
+        let merged_end = self.segments[first..last]
+            .last()
+            .map_or(end, |segment| end.max(segment_end(segment)));
+        let mut merged = vec![0u8; (merged_end - merged_start) as usize];
+        for (segment_start, bytes) in &self.segments[first..last] {
+            let offset = (u64::from(*segment_start) - merged_start) as usize;
+            merged[offset..offset + bytes.len()].copy_from_slice(bytes);
+        }
+        let offset = (start - merged_start) as usize;
+        merged[offset..offset + data.len()].copy_from_slice(data);
+        self.segments.splice(first..last, [(merged_start as u32, merged)]);
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.segments.iter().map(|(_, bytes)| bytes.len()).sum()
+    }
+
+    /// `len` bytes starting at `address`, or `None` if any of them is undefined.
+    pub(crate) fn read(&self, address: u32, len: u32) -> Option<Vec<u8>> {
+        if len == 0 {
+            return Some(Vec::new());
+        }
+        let start = u64::from(address);
+        let index = self
+            .segments
+            .partition_point(|(segment_start, _)| u64::from(*segment_start) <= start)
+            .checked_sub(1)?;
+        let (segment_start, bytes) = &self.segments[index];
+        let offset = (start - u64::from(*segment_start)) as usize;
+        bytes.get(offset..offset + len as usize).map(<[u8]>::to_vec)
+    }
+
+    /// Contiguous runs of defined bytes, in address order.
+    pub(crate) fn segments(&self) -> &[(u32, Vec<u8>)] {
+        &self.segments
+    }
+
+    /// Parses Intel HEX or Motorola S-record text, chosen by the first record.
+    pub(crate) fn parse(text: &str) -> Result<Self, String> {
+        let first = text.trim_start().chars().next();
+        match first {
+            Some(':') => Self::parse_intel_hex(text),
+            Some('S') => Self::parse_srecord(text),
+            _ => Err("Unknown hex file format".to_string()),
+        }
+    }
+
+    fn record_bytes(line_number: usize, hex: &str) -> Result<Vec<u8>, String> {
+        if hex.len() % 2 != 0 {
+            return Err(format!("Line {line_number}: odd number of hex digits"));
+        }
+        // Works on bytes so that non-ASCII text is an error rather than a slice
+        // panic at a character boundary.
+        hex.as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                std::str::from_utf8(pair)
+                    .ok()
+                    .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                    .ok_or_else(|| format!("Line {line_number}: invalid hex digits"))
+            })
+            .collect()
+    }
+
+    fn parse_intel_hex(text: &str) -> Result<Self, String> {
+        let mut image = Self::default();
+        let mut base = 0u32;
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let Some(record) = line.trim().strip_prefix(':') else {
+                continue;
+            };
+            let bytes = Self::record_bytes(line_number, record)?;
+            if bytes.len() < 5 || bytes.len() != usize::from(bytes[0]) + 5 {
+                return Err(format!("Line {line_number}: invalid record length"));
+            }
+            if bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0 {
+                return Err(format!("Line {line_number}: checksum mismatch"));
+            }
+            let offset = u32::from(u16::from_be_bytes([bytes[1], bytes[2]]));
+            let data = &bytes[4..bytes.len() - 1];
+            match bytes[3] {
+                0x00 => image.write(base.wrapping_add(offset), data),
+                0x01 => break,
+                0x02 if data.len() == 2 => base = u32::from(u16::from_be_bytes([data[0], data[1]])) << 4,
+                0x04 if data.len() == 2 => base = u32::from(u16::from_be_bytes([data[0], data[1]])) << 16,
+                _ => {}
+            }
+        }
+        Ok(image)
+    }
+
+    fn parse_srecord(text: &str) -> Result<Self, String> {
+        let mut image = Self::default();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            let (address_len, payload) = match line.get(..2) {
+                Some("S1") => (2, &line[2..]),
+                Some("S2") => (3, &line[2..]),
+                Some("S3") => (4, &line[2..]),
+                _ => continue,
+            };
+            let bytes = Self::record_bytes(line_number, payload)?;
+            if bytes.is_empty() || bytes.len() != usize::from(bytes[0]) + 1 || bytes.len() < address_len + 2 {
+                return Err(format!("Line {line_number}: invalid record length"));
+            }
+            if bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0xFF {
+                return Err(format!("Line {line_number}: checksum mismatch"));
+            }
+            let address = bytes[1..=address_len]
+                .iter()
+                .fold(0u32, |address, byte| (address << 8) | u32::from(*byte));
+            image.write(address, &bytes[address_len + 1..bytes.len() - 1]);
+        }
+        Ok(image)
+    }
+
+    pub(crate) fn to_intel_hex(&self) -> String {
+        fn record(out: &mut String, address: u16, record_type: u8, data: &[u8]) {
+            let mut checksum = data.len() as u8;
+            checksum = checksum.wrapping_add((address >> 8) as u8).wrapping_add(address as u8);
+            checksum = checksum.wrapping_add(record_type);
+            out.push_str(&format!(":{:02X}{:04X}{:02X}", data.len(), address, record_type));
+            for byte in data {
+                checksum = checksum.wrapping_add(*byte);
+                out.push_str(&format!("{byte:02X}"));
+            }
+            out.push_str(&format!("{:02X}\n", checksum.wrapping_neg()));
+        }
+
+        let mut out = String::new();
+        let mut upper: Option<u16> = None;
+        for (start, data) in &self.segments {
+            let mut address = *start;
+            for chunk in data.chunks(16) {
+                let chunk_upper = (address >> 16) as u16;
+                if upper != Some(chunk_upper) {
+                    record(&mut out, 0, 0x04, &chunk_upper.to_be_bytes());
+                    upper = Some(chunk_upper);
+                }
+                // Split chunks that would cross a 64 KiB boundary.
+                let room = (0x1_0000 - (address & 0xFFFF)) as usize;
+                let (head, tail) = chunk.split_at(chunk.len().min(room));
+                record(&mut out, address as u16, 0x00, head);
+                if !tail.is_empty() {
+                    let tail_address = address.wrapping_add(head.len() as u32);
+                    let tail_upper = (tail_address >> 16) as u16;
+                    record(&mut out, 0, 0x04, &tail_upper.to_be_bytes());
+                    upper = Some(tail_upper);
+                    record(&mut out, tail_address as u16, 0x00, tail);
+                }
+                address = address.wrapping_add(chunk.len() as u32);
+            }
+        }
+        record(&mut out, 0, 0x01, &[]);
+        out
+    }
+}
+
+#[derive(Serialize)]
+pub struct HexImageInfo {
+    path: String,
+    defined_bytes: usize,
+    ranges: Vec<(String, u32)>,
+}
+
+/// Loads an Intel HEX or S-record file as the memory image of a document.
+#[tauri::command]
+pub fn load_hex_image(path: String, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<HexImageInfo, String> {
+    let text = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let image = MemoryImage::parse(&text)?;
+    let info = HexImageInfo {
+        path: path.clone(),
+        defined_bytes: image.len(),
+        ranges: image
+            .segments()
+            .iter()
+            .map(|(start, data)| (format!("0x{:X}", start), data.len() as u32))
+            .collect(),
+    };
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    guard.document_mut(doc_id.as_deref())?.hex_image = Some((path, image));
+    Ok(info)
+}
@@ -0,0 +1,344 @@
+use a2lfile::{A2lObjectName, GenericIfData, GenericIfDataTaggedItem};
+use serde::Serialize;
+
+use crate::entity_copy::transfer;
+use crate::entity_source::{module_body, parse_snippet, single_object_name};
+use crate::AppState;
+
+#[derive(Serialize)]
+pub struct IfDataUpdate {
+    /// False when the module has no A2ML, i.e. the text was only parsed
+    /// generically.
+    validated: bool,
+}
+
+/// Node of a decoded IF_DATA block. Tagged items and blocks are named by their
+/// A2ML tag; positional parameters by their index.
+#[derive(Serialize)]
+pub struct IfDataNode {
+    name: String,
+    /// A2ML construct: block, struct, taggedstruct, taggedunion, sequence,
+    /// array, enum, string or the numeric type.
+    kind: String,
+    value: Option<String>,
+    children: Vec<IfDataNode>,
+}
+
+#[derive(Serialize)]
+pub struct IfDataTree {
+    /// False when the block was decoded but does not conform to the A2ML.
+    valid: bool,
+    nodes: Vec<IfDataNode>,
+}
+
+fn leaf(name: String, kind: &str, value: String) -> IfDataNode {
+    IfDataNode {
+        name,
+        kind: kind.to_string(),
+        value: Some(value),
+        children: Vec::new(),
+    }
+}
+
+fn number<T: std::fmt::Display + std::fmt::UpperHex>(
+    name: String,
+    kind: &str,
+    (value, is_hex): &(T, bool),
+) -> IfDataNode {
+    let text = if *is_hex {
+        format!("0x{:X}", value)
+    } else {
+        value.to_string()
+    };
+    leaf(name, kind, text)
+}
+
+fn positional(items: &[GenericIfData]) -> Vec<IfDataNode> {
+    items
+        .iter()
+        .filter(|item| !matches!(item, GenericIfData::None))
+        .enumerate()
+        .map(|(index, item)| ifdata_node(format!("[{index}]"), item))
+        .collect()
+}
+
+/// Tagged items in file order.
+fn tagged(items: &std::collections::HashMap<String, Vec<GenericIfDataTaggedItem>>) -> Vec<IfDataNode> {
+    let mut flat: Vec<&GenericIfDataTaggedItem> = items.values().flatten().collect();
+    flat.sort_by_key(|item| item.line);
+    flat.into_iter()
+        .map(|item| {
+            let mut node = ifdata_node(item.tag.clone(), &item.data);
+            if item.is_block {
+                node.kind = "block".to_string();
+            }
+            node
+        })
+        .collect()
+}
+
+fn branch(name: String, kind: &str, children: Vec<IfDataNode>) -> IfDataNode {
+    IfDataNode {
+        name,
+        kind: kind.to_string(),
+        value: None,
+        children,
+    }
+}
+
+fn ifdata_node(name: String, data: &GenericIfData) -> IfDataNode {
+    match data {
+        GenericIfData::None => branch(name, "none", Vec::new()),
+        GenericIfData::Char(_, value) => number(name, "char", value),
+        GenericIfData::Int(_, value) => number(name, "int", value),
+        GenericIfData::Long(_, value) => number(name, "long", value),
+        GenericIfData::Int64(_, value) => number(name, "int64", value),
+        GenericIfData::UChar(_, value) => number(name, "uchar", value),
+        GenericIfData::UInt(_, value) => number(name, "uint", value),
+        GenericIfData::ULong(_, value) => number(name, "ulong", value),
+        GenericIfData::UInt64(_, value) => number(name, "uint64", value),
+        GenericIfData::Float(_, value) => leaf(name, "float", value.to_string()),
+        GenericIfData::Double(_, value) => leaf(name, "double", value.to_string()),
+        GenericIfData::String(_, value) => leaf(name, "string", value.clone()),
+        GenericIfData::EnumItem(_, value) => leaf(name, "enum", value.clone()),
+        GenericIfData::Array(items) => branch(name, "array", positional(items)),
+        GenericIfData::Sequence(items) => branch(name, "sequence", positional(items)),
+        GenericIfData::TaggedStruct(items) => branch(name, "taggedstruct", tagged(items)),
+        GenericIfData::TaggedUnion(items) => branch(name, "taggedunion", tagged(items)),
+        GenericIfData::Struct(_, _, items) => branch(name, "struct", positional(items)),
+        GenericIfData::Block(_, _, items) => branch(name, "block", positional(items)),
+    }
+}
+
+/// The outer positional blocks and structs only wrap the content; lift it so
+/// tags such as XCP or CCP sit at the top of the tree.
+fn flatten(node: IfDataNode) -> Vec<IfDataNode> {
+    let wrapper = matches!(node.kind.as_str(), "block" | "struct" | "taggedunion" | "taggedstruct")
+        && node.name.starts_with('[');
+    if wrapper {
+        node.children.into_iter().flat_map(flatten).collect()
+    } else {
+        vec![node]
+    }
+}
+
+/// Line ranges of the IF_DATA blocks in `text` that sit at nesting `depth`
+/// (0 for module-level IF_DATA, 1 inside an object).
+fn if_data_blocks(text: &str, depth: usize) -> Vec<(usize, usize)> {
+    let mut blocks = Vec::new();
+    let mut level = 0usize;
+    let mut start = None;
+    for (index, line) in text.lines().enumerate() {
+        for token in line.split_whitespace().collect::<Vec<_>>().windows(2) {
+            match (token[0], token[1]) {
+                ("/begin", keyword) => {
+                    if keyword == "IF_DATA" && level == depth && start.is_none() {
+                        start = Some(index);
+                    }
+                    level += 1;
+                }
+                ("/end", keyword) => {
+                    level = level.saturating_sub(1);
+                    if keyword == "IF_DATA" && level == depth {
+                        if let Some(start) = start.take() {
+                            blocks.push((start, index));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    blocks
+}
+
+fn if_data_list<'a>(module: &'a a2lfile::Module, kind: &str, name: &str) -> Result<&'a Vec<a2lfile::IfData>, String> {
+    let found = match kind {
+        "Module" => Some(&module.if_data),
+        "Measurement" => module.measurement.get(name).map(|item| &item.if_data),
+        "Characteristic" => module.characteristic.get(name).map(|item| &item.if_data),
+        "AxisPts" => module.axis_pts.get(name).map(|item| &item.if_data),
+        "Function" => module.function.get(name).map(|item| &item.if_data),
+        "Group" => module.group.get(name).map(|item| &item.if_data),
+        "Frame" => module.frame.get(name).map(|item| &item.if_data),
+        "Blob" => module.blob.get(name).map(|item| &item.if_data),
+        "Instance" => module.instance.get(name).map(|item| &item.if_data),
+        _ => return Err(format!("Objects of kind {kind} have no IF_DATA")),
+    };
+    found.ok_or_else(|| format!("{kind} '{name}' not found"))
+}
+
+fn if_data_list_mut<'a>(
+    module: &'a mut a2lfile::Module,
+    kind: &str,
+    name: &str,
+) -> Option<&'a mut Vec<a2lfile::IfData>> {
+    match kind {
+        "Module" => Some(&mut module.if_data),
+        "Measurement" => module.measurement.get_mut(name).map(|item| &mut item.if_data),
+        "Characteristic" => module.characteristic.get_mut(name).map(|item| &mut item.if_data),
+        "AxisPts" => module.axis_pts.get_mut(name).map(|item| &mut item.if_data),
+        "Function" => module.function.get_mut(name).map(|item| &mut item.if_data),
+        "Group" => module.group.get_mut(name).map(|item| &mut item.if_data),
+        "Frame" => module.frame.get_mut(name).map(|item| &mut item.if_data),
+        "Blob" => module.blob.get_mut(name).map(|item| &mut item.if_data),
+        "Instance" => module.instance.get_mut(name).map(|item| &mut item.if_data),
+        _ => None,
+    }
+}
+
+/// Serialized text of the owner, reduced to what is needed to locate its
+/// IF_DATA blocks, and the nesting depth at which they appear.
+fn owner_source(module: &a2lfile::Module, kind: &str, name: &str) -> Result<(String, usize), String> {
+    let mut single = a2lfile::Module::new(module.get_name().to_string(), String::new());
+    if kind == "Module" {
+        single.if_data = module.if_data.clone();
+        return Ok((module_body(single), 0));
+    }
+    if !transfer(module, &mut single, kind, name) {
+        return Err(format!("{kind} '{name}' not found"));
+    }
+    Ok((module_body(single), 1))
+}
+
+fn owner_module<'a>(a2l: &'a a2lfile::A2lFile, kind: &str, name: &str) -> Result<&'a a2lfile::Module, String> {
+    a2l.project
+        .module
+        .iter()
+        .find(|module| {
+            if kind == "Module" {
+                module.get_name() == name
+            } else {
+                if_data_list(module, kind, name).is_ok()
+            }
+        })
+        .ok_or_else(|| format!("{kind} '{name}' not found"))
+}
+
+#[tauri::command]
+pub fn get_ifdata_text(
+    kind: String,
+    name: String,
+    index: usize,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<String, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let module = owner_module(a2l, &kind, &name)?;
+    let (source, depth) = owner_source(module, &kind, &name)?;
+    let (start, end) = *if_data_blocks(&source, depth)
+        .get(index)
+        .ok_or_else(|| format!("{kind} '{name}' has no IF_DATA {index}"))?;
+    let lines: Vec<&str> = source.lines().collect();
+    let indent = lines[start].len() - lines[start].trim_start().len();
+    Ok(lines[start..=end]
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or(line.trim_start()))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Replaces IF_DATA `index` of the owner with `text`. The text is parsed with
+/// the module's A2ML and rejected if it does not conform. An index equal to
+/// the current count appends a new block.
+#[tauri::command]
+pub fn set_ifdata_text(
+    kind: String,
+    name: String,
+    index: usize,
+    text: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<IfDataUpdate, String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("set_ifdata_text", doc_id.as_deref())?;
+    if kind == "Module" {
+        edit.touch_module(&name);
+    } else {
+        edit.touch_object(&kind, &name);
+    }
+    let a2l = edit.a2l_mut();
+    let module = owner_module(a2l, &kind, &name)?;
+    let module_name = module.get_name().to_string();
+    let a2ml = module.a2ml.as_ref().map(|a2ml| a2ml.a2ml_text.clone());
+    let count = if_data_list(module, &kind, &name)?.len();
+    if index > count {
+        return Err(format!("{kind} '{name}' has no IF_DATA {index}"));
+    }
+
+    let parsed_if_data = if kind == "Module" {
+        let parsed = parse_snippet(&text, a2ml.as_deref())?;
+        let mut blocks = parsed.if_data.into_iter();
+        match (blocks.next(), blocks.next()) {
+            (Some(block), None) => block,
+            _ => return Err("Text must contain exactly one IF_DATA block".to_string()),
+        }
+    } else {
+        // Splice the new block into the object's source so it is parsed in
+        // the same context as the original.
+        let (source, depth) = owner_source(module, &kind, &name)?;
+        let lines: Vec<&str> = source.lines().collect();
+        let blocks = if_data_blocks(&source, depth);
+        let (before, after) = match blocks.get(index) {
+            Some(&(start, end)) => (&lines[..start], &lines[end + 1..]),
+            None => (&lines[..lines.len() - 1], &lines[lines.len() - 1..]),
+        };
+        let spliced = [before.join("\n"), text.clone(), after.join("\n")].join("\n");
+        let mut parsed = parse_snippet(&spliced, a2ml.as_deref())?;
+        single_object_name(&parsed, &kind)?;
+        if_data_list_mut(&mut parsed, &kind, &name)
+            .filter(|list| list.len() == count.max(index + 1))
+            .map(|list| list.swap_remove(index))
+            .ok_or_else(|| "Text must contain exactly one IF_DATA block".to_string())?
+    };
+    if a2ml.is_some() && !parsed_if_data.ifdata_valid {
+        return Err("IF_DATA does not conform to the module's A2ML specification".to_string());
+    }
+
+    let module = a2l
+        .project
+        .module
+        .iter_mut()
+        .find(|module| module.get_name() == module_name)
+        .ok_or_else(|| format!("Module {module_name} not found"))?;
+    let list = if_data_list_mut(module, &kind, &name).ok_or_else(|| format!("{kind} '{name}' not found"))?;
+    if index == list.len() {
+        list.push(parsed_if_data);
+    } else {
+        list[index] = parsed_if_data;
+    }
+    Ok(IfDataUpdate {
+        validated: a2ml.is_some(),
+    })
+}
+
+/// IF_DATA `index` of the owner decoded with the module's A2ML into a tree of
+/// tags and parameters, for any protocol the A2ML describes (XCP, CCP,
+/// vendor blocks).
+#[tauri::command]
+pub fn get_ifdata_tree(
+    kind: String,
+    name: String,
+    index: usize,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<IfDataTree, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let module = owner_module(a2l, &kind, &name)?;
+    if module.a2ml.is_none() {
+        return Err(format!("Module {} has no A2ML specification", module.get_name()));
+    }
+    let if_data = if_data_list(module, &kind, &name)?
+        .get(index)
+        .ok_or_else(|| format!("{kind} '{name}' has no IF_DATA {index}"))?;
+    let items = if_data
+        .ifdata_items
+        .as_ref()
+        .ok_or_else(|| format!("IF_DATA {index} of {kind} '{name}' could not be decoded with the A2ML"))?;
+    Ok(IfDataTree {
+        valid: if_data.ifdata_valid,
+        nodes: flatten(ifdata_node("[0]".to_string(), items)),
+    })
+}
@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path};
+
+use a2lfile::{A2lObject, A2lObjectName};
+use serde::Serialize;
+
+use crate::entity_source::module_body;
+use crate::watcher::own_write;
+use crate::AppState;
+
+#[derive(Serialize)]
+pub struct IncludedObject {
+    kind: String,
+    name: String,
+}
+
+#[derive(Serialize)]
+pub struct IncludeFile {
+    file: String,
+    objects: Vec<IncludedObject>,
+}
+
+/// Module-level objects grouped by the `/include` file they were read from.
+pub(crate) fn include_origins(a2l: &a2lfile::A2lFile) -> BTreeMap<String, Vec<(String, String)>> {
+    let mut origins: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+    macro_rules! collect {
+        ($list:expr, $kind:literal) => {
+            for item in $list.iter() {
+                if let Some(file) = &item.get_layout().incfile {
+                    origins
+                        .entry(file.clone())
+                        .or_default()
+                        .push(($kind.to_string(), item.get_name().to_string()));
+                }
+            }
+        };
+    }
+    for module in a2l.project.module.iter() {
+        collect!(module.measurement, "Measurement");
+        collect!(module.characteristic, "Characteristic");
+        collect!(module.axis_pts, "AxisPts");
+        collect!(module.blob, "Blob");
+        collect!(module.instance, "Instance");
+        collect!(module.compu_method, "CompuMethod");
+        collect!(module.compu_tab, "CompuTab");
+        collect!(module.compu_vtab, "CompuVtab");
+        collect!(module.compu_vtab_range, "CompuVtabRange");
+        collect!(module.record_layout, "RecordLayout");
+        collect!(module.unit, "Unit");
+        collect!(module.function, "Function");
+        collect!(module.group, "Group");
+        collect!(module.frame, "Frame");
+        collect!(module.typedef_structure, "TypedefStructure");
+        collect!(module.typedef_measurement, "TypedefMeasurement");
+        collect!(module.typedef_characteristic, "TypedefCharacteristic");
+        collect!(module.typedef_axis, "TypedefAxis");
+        collect!(module.typedef_blob, "TypedefBlob");
+    }
+    origins
+}
+
+/// Include names are written relative to the main file; names that would
+/// leave its directory are refused.
+fn check_include_name(file: &str) -> Result<(), String> {
+    let relative = Path::new(file)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if file.trim().is_empty() || !relative {
+        return Err(format!("Include file '{file}' is not a path below the main file's directory"));
+    }
+    Ok(())
+}
+
+/// Renders every include file under the include name recorded at load time,
+/// with the objects of all modules that came from it. The main file itself
+/// keeps the `/include` lines the writer emits for these objects. Fails
+/// without side effects if the model cannot be saved this way.
+pub(crate) fn render_include_files(a2l: &a2lfile::A2lFile) -> Result<Vec<(String, String)>, String> {
+    if a2l.project.module.iter().any(|module| module.get_layout().incfile.is_some()) {
+        return Err("Modules loaded from include files can only be saved merged".to_string());
+    }
+    let mut rendered = Vec::new();
+    for file in include_origins(a2l).into_keys() {
+        check_include_name(&file)?;
+        let mut part = a2lfile::Module::new("INCLUDE".to_string(), String::new());
+        for module in a2l.project.module.iter() {
+            macro_rules! take {
+                ($list:ident) => {
+                    for item in module.$list.iter() {
+                        if item.get_layout().incfile.as_deref() == Some(file.as_str()) {
+                            let mut item = item.clone();
+                            item.get_layout_mut().incfile = None;
+                            part.$list.push(item);
+                        }
+                    }
+                };
+            }
+            take!(measurement);
+            take!(characteristic);
+            take!(axis_pts);
+            take!(blob);
+            take!(instance);
+            take!(compu_method);
+            take!(compu_tab);
+            take!(compu_vtab);
+            take!(compu_vtab_range);
+            take!(record_layout);
+            take!(unit);
+            take!(function);
+            take!(group);
+            take!(frame);
+            take!(typedef_structure);
+            take!(typedef_measurement);
+            take!(typedef_characteristic);
+            take!(typedef_axis);
+            take!(typedef_blob);
+        }
+        rendered.push((file, module_body(part) + "\n"));
+    }
+    Ok(rendered)
+}
+
+/// Writes the output of `render_include_files` next to `main_path`, as own
+/// writes the file watcher does not report.
+pub(crate) fn write_include_files(
+    state: &AppState,
+    main_path: &str,
+    files: Vec<(String, String)>,
+) -> Result<(), String> {
+    let dir = Path::new(main_path).parent().unwrap_or(Path::new("."));
+    for (file, body) in files {
+        let path = dir.join(file);
+        own_write(state, &path.to_string_lossy(), || fs::write(&path, body).map_err(|e| e.to_string()))?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_include_files(doc_id: Option<String>, state: tauri::State<AppState>) -> Result<Vec<IncludeFile>, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    Ok(include_origins(a2l)
+        .into_iter()
+        .map(|(file, objects)| IncludeFile {
+            file,
+            objects: objects
+                .into_iter()
+                .map(|(kind, name)| IncludedObject { kind, name })
+                .collect(),
+        })
+        .collect())
+}
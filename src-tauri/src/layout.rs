@@ -0,0 +1,207 @@
+use a2lfile::DataType;
+
+pub(crate) fn datatype_size(datatype: &DataType) -> u32 {
+    match datatype {
+        DataType::Ubyte | DataType::Sbyte => 1,
+        DataType::Uword | DataType::Sword | DataType::Float16Ieee => 2,
+        DataType::Ulong | DataType::Slong | DataType::Float32Ieee => 4,
+        DataType::AUint64 | DataType::AInt64 | DataType::Float64Ieee => 8,
+    }
+}
+
+/// Raw value range of `datatype`.
+pub(crate) fn datatype_range(datatype: &DataType) -> (f64, f64) {
+    match datatype {
+        DataType::Ubyte => (0.0, u8::MAX as f64),
+        DataType::Sbyte => (i8::MIN as f64, i8::MAX as f64),
+        DataType::Uword => (0.0, u16::MAX as f64),
+        DataType::Sword => (i16::MIN as f64, i16::MAX as f64),
+        DataType::Ulong => (0.0, u32::MAX as f64),
+        DataType::Slong => (i32::MIN as f64, i32::MAX as f64),
+        DataType::AUint64 => (0.0, u64::MAX as f64),
+        DataType::AInt64 => (i64::MIN as f64, i64::MAX as f64),
+        DataType::Float16Ieee => (-65504.0, 65504.0),
+        DataType::Float32Ieee => (f32::MIN as f64, f32::MAX as f64),
+        DataType::Float64Ieee => (f64::MIN, f64::MAX),
+    }
+}
+
+/// Product of `counts`; `None` when it does not fit into a `u32`.
+fn checked_product(counts: impl IntoIterator<Item = u32>) -> Option<u32> {
+    counts.into_iter().try_fold(1u32, |product, count| product.checked_mul(count))
+}
+
+/// Element count given by MATRIX_DIM, or `fallback` without one; `None` when
+/// the dimensions overflow a `u32`.
+pub(crate) fn matrix_dim_count(matrix_dim: &Option<a2lfile::MatrixDim>, fallback: u32) -> Option<u32> {
+    match matrix_dim.as_ref().filter(|dim| !dim.dim_list.is_empty()) {
+        Some(dim) => checked_product(dim.dim_list.iter().map(|&d| u32::from(d.max(1)))),
+        None => Some(fallback),
+    }
+}
+
+/// Number of stored values of a characteristic, i.e. the product of all
+/// axis lengths for curves and maps, or the block size for VAL_BLK / ASCII.
+/// `None` when the count overflows a `u32`.
+pub(crate) fn characteristic_value_count(c: &a2lfile::Characteristic) -> Option<u32> {
+    match c.characteristic_type {
+        a2lfile::CharacteristicType::Value => Some(1),
+        a2lfile::CharacteristicType::ValBlk | a2lfile::CharacteristicType::Ascii => {
+            matrix_dim_count(&c.matrix_dim, c.number.as_ref().map_or(1, |n| u32::from(n.number)))
+        }
+        _ => checked_product(c.axis_descr.iter().map(|axis| u32::from(axis.max_axis_points.max(1)))),
+    }
+}
+
+/// Bytes occupied by a characteristic in ECU memory, including axis points and
+/// axis point counters that the record layout stores inline. `None` when the
+/// record layout is missing or the size overflows a `u32`.
+pub(crate) fn characteristic_size(module: &a2lfile::Module, c: &a2lfile::Characteristic) -> Option<u32> {
+    let layout = module.record_layout.get(&c.deposit)?;
+    let value_size = if c.characteristic_type == a2lfile::CharacteristicType::Ascii {
+        1
+    } else {
+        datatype_size(&layout.fnc_values.as_ref()?.datatype)
+    };
+    let mut size = value_size.checked_mul(characteristic_value_count(c)?)?;
+
+    let inline_axes = [
+        (&layout.axis_pts_x, &layout.no_axis_pts_x),
+        (&layout.axis_pts_y, &layout.no_axis_pts_y),
+        (&layout.axis_pts_z, &layout.no_axis_pts_z),
+        (&layout.axis_pts_4, &layout.no_axis_pts_4),
+        (&layout.axis_pts_5, &layout.no_axis_pts_5),
+    ];
+    for (axis, (axis_pts, no_axis_pts)) in c.axis_descr.iter().zip(inline_axes) {
+        if let Some(axis_pts) = axis_pts {
+            let values = datatype_size(&axis_pts.datatype).checked_mul(u32::from(axis.max_axis_points))?;
+            size = size.checked_add(values)?;
+        }
+        if let Some(no_axis_pts) = no_axis_pts {
+            size = size.checked_add(datatype_size(&no_axis_pts.datatype))?;
+        }
+    }
+    Some(size)
+}
+
+pub(crate) fn axis_pts_size(module: &a2lfile::Module, a: &a2lfile::AxisPts) -> Option<u32> {
+    let layout = module.record_layout.get(&a.deposit_record)?;
+    let mut size: u32 = 0;
+    if let Some(axis_pts) = &layout.axis_pts_x {
+        size = datatype_size(&axis_pts.datatype).checked_mul(u32::from(a.max_axis_points))?;
+    }
+    if let Some(no_axis_pts) = &layout.no_axis_pts_x {
+        size = size.checked_add(datatype_size(&no_axis_pts.datatype))?;
+    }
+    Some(size)
+}
+
+/// `None` when the MATRIX_DIM or ARRAY_SIZE makes the size overflow a `u32`.
+pub(crate) fn measurement_size(m: &a2lfile::Measurement) -> Option<u32> {
+    let count = matrix_dim_count(&m.matrix_dim, m.array_size.as_ref().map_or(1, |a| u32::from(a.number)))?;
+    datatype_size(&m.datatype).checked_mul(count)
+}
+
+/// Bytes occupied by one element of the typedef `type_ref`; `None` when the
+/// typedef is missing or its size overflows a `u32`.
+pub(crate) fn typedef_size(module: &a2lfile::Module, type_ref: &str) -> Option<u32> {
+    if let Some(structure) = module.typedef_structure.get(type_ref) {
+        Some(structure.total_size)
+    } else if let Some(blob) = module.typedef_blob.get(type_ref) {
+        Some(blob.size)
+    } else if let Some(typedef) = module.typedef_measurement.get(type_ref) {
+        datatype_size(&typedef.datatype).checked_mul(matrix_dim_count(&typedef.matrix_dim, 1)?)
+    } else if let Some(typedef) = module.typedef_characteristic.get(type_ref) {
+        let layout = module.record_layout.get(&typedef.record_layout)?;
+        datatype_size(&layout.fnc_values.as_ref()?.datatype).checked_mul(matrix_dim_count(&typedef.matrix_dim, 1)?)
+    } else if let Some(typedef) = module.typedef_axis.get(type_ref) {
+        let layout = module.record_layout.get(&typedef.record_layout)?;
+        datatype_size(&layout.axis_pts_x.as_ref()?.datatype).checked_mul(u32::from(typedef.max_axis_points))
+    } else {
+        None
+    }
+}
+
+pub(crate) fn instance_size(module: &a2lfile::Module, instance: &a2lfile::Instance) -> Option<u32> {
+    let element = typedef_size(module, &instance.type_ref)?;
+    element.checked_mul(matrix_dim_count(&instance.matrix_dim, 1)?)
+}
+
+/// Bytes occupied by the object `kind`/`name` of `module`; `None` when the
+/// object does not exist, its record layout or typedef is missing or the size
+/// overflows a `u32`.
+pub(crate) fn object_size(module: &a2lfile::Module, kind: &str, name: &str) -> Option<u32> {
+    match kind {
+        "Measurement" => measurement_size(module.measurement.get(name)?),
+        "Characteristic" => characteristic_size(module, module.characteristic.get(name)?),
+        "AxisPts" => axis_pts_size(module, module.axis_pts.get(name)?),
+        "Blob" => module.blob.get(name).map(|b| b.size),
+        "Instance" => instance_size(module, module.instance.get(name)?),
+        _ => None,
+    }
+}
+
+/// Effective byte order of an object: its own BYTE_ORDER, else MOD_COMMON,
+/// else the ASAP2 default (MSB_LAST, i.e. little endian).
+pub(crate) fn is_big_endian(module: &a2lfile::Module, byte_order: &Option<a2lfile::ByteOrder>) -> bool {
+    let effective = byte_order
+        .as_ref()
+        .or_else(|| module.mod_common.as_ref().and_then(|mc| mc.byte_order.as_ref()))
+        .map(|bo| bo.byte_order);
+    matches!(
+        effective,
+        Some(a2lfile::ByteOrderEnum::BigEndian)
+            | Some(a2lfile::ByteOrderEnum::MsbFirst)
+            | Some(a2lfile::ByteOrderEnum::MsbFirstMswLast)
+    )
+}
+
+/// Encodes a raw (ECU-internal) value with the given datatype and byte order.
+pub(crate) fn encode_raw(datatype: &DataType, value: f64, big_endian: bool) -> Vec<u8> {
+    macro_rules! bytes {
+        ($v:expr) => {
+            if big_endian {
+                $v.to_be_bytes().to_vec()
+            } else {
+                $v.to_le_bytes().to_vec()
+            }
+        };
+    }
+    match datatype {
+        DataType::Ubyte => bytes!(value as u8),
+        DataType::Sbyte => bytes!(value as i8),
+        DataType::Uword => bytes!(value as u16),
+        DataType::Sword => bytes!(value as i16),
+        DataType::Ulong => bytes!(value as u32),
+        DataType::Slong => bytes!(value as i32),
+        DataType::AUint64 => bytes!(value as u64),
+        DataType::AInt64 => bytes!(value as i64),
+        DataType::Float16Ieee => bytes!(f16_bits(value as f32)),
+        DataType::Float32Ieee => bytes!(value as f32),
+        DataType::Float64Ieee => bytes!(value),
+    }
+}
+
+/// IEEE 754 binary16 bit pattern of `value` (round toward zero).
+fn f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xFF) as i32;
+    let mantissa = bits & 0x007F_FFFF;
+    if exponent == 0xFF {
+        let nan = if mantissa != 0 { 0x0200 } else { 0 };
+        return sign | 0x7C00 | nan;
+    }
+    let half_exponent = exponent - 127 + 15;
+    if half_exponent >= 0x1F {
+        sign | 0x7C00
+    } else if half_exponent <= 0 {
+        if half_exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x0080_0000;
+        sign | (mantissa >> (14 - half_exponent)) as u16
+    } else {
+        sign | ((half_exponent as u16) << 10) | (mantissa >> 13) as u16
+    }
+}
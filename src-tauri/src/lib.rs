@@ -1,16 +1,92 @@
+use std::borrow::Cow;
 use std::sync::Mutex;
 use std::fs;
 use goblin::elf::Elf;
+use rayon::prelude::*;
 
 use serde::{Serialize, Deserialize};
 use a2lfile::{A2lObjectName, A2lObjectNameSetter, Header, ItemList};
 
+mod a2l_json;
+mod a2l_keywords;
+mod access_policy;
+mod address_map;
+mod annotations;
+mod audit;
+mod autosave;
+mod axis_descr;
+mod bandwidth;
+mod bitfield;
+mod budgets;
+mod cleanup;
+pub mod cli;
+mod dataset;
+mod dbc;
+mod dedup;
+mod dependent_characteristics;
+mod diagnostics;
+mod documents;
+mod elf_characteristics;
+mod elf_groups;
+mod elf_symbols;
+mod entity_copy;
+mod entity_events;
+mod entity_source;
+mod epk;
+mod export_options;
+mod frames;
+mod hex;
+mod ifdata;
+mod includes;
+mod layout;
+mod limits;
+mod load_jobs;
+mod mdf;
+mod mod_common;
+mod mod_par;
+mod modules;
+mod name_lint;
+mod ordering;
+mod placement;
+mod reference_fixes;
+mod references;
+mod report;
+mod scripting;
+mod session;
+mod sizes;
+mod snapshots;
+mod subset;
+mod symbol_links;
+mod symbol_names;
+mod symbol_sources;
+mod table;
+mod templates;
+mod text_normalize;
+mod tool_compat;
+mod tool_export;
+mod transaction;
+mod typedefs;
+mod units;
+mod user_rights;
+mod variant_coding;
+mod version;
+mod watcher;
+mod working_sets;
+
 #[derive(Default)]
 struct AppState {
-    a2l: Mutex<Option<a2lfile::A2lFile>>,
+    documents: Mutex<documents::DocumentStore>,
+    reference_config: Mutex<references::ReferenceConfig>,
+    export_options: Mutex<export_options::ExportOptions>,
+    load_jobs: Mutex<load_jobs::LoadJobs>,
+    elf: Mutex<Option<elf_symbols::ElfIndex>>,
+    autosave: Mutex<autosave::Autosave>,
+    watcher: Mutex<watcher::FileWatcher>,
+    access_policy: Mutex<Vec<access_policy::AccessRule>>,
+    audit: Mutex<audit::AuditLog>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 struct A2lMetadata {
     project_name: String,
     project_long_identifier: String,
@@ -35,8 +111,8 @@ struct EntityUpdateResult {
 
 #[derive(Serialize, Clone)]
 struct A2lTreeDetail {
-    label: String,
-    value: String,
+    label: &'static str,
+    value: Cow<'static, str>,
 }
 
 #[derive(Serialize)]
@@ -75,25 +151,41 @@ trait A2lDetailProvider {
     fn details(&self) -> Vec<A2lTreeDetail>;
 }
 
-fn detail(label: &str, value: impl ToString) -> A2lTreeDetail {
+/// Shown for absent optional values.
+const NO_VALUE: &str = "—";
+
+fn detail(label: &'static str, value: impl ToString) -> A2lTreeDetail {
     A2lTreeDetail {
-        label: label.to_string(),
-        value: value.to_string(),
+        label,
+        value: Cow::Owned(value.to_string()),
     }
 }
 
-fn opt_detail<T: std::fmt::Debug>(label: &str, value: &Option<T>) -> A2lTreeDetail {
-    let rendered = value
-        .as_ref()
-        .map(|item| format!("{item:?}"))
-        .unwrap_or_else(|| "—".to_string());
-    detail(label, rendered)
+/// Most optional attributes are unset, so `None` renders without allocating.
+fn opt_detail<T: std::fmt::Debug>(label: &'static str, value: &Option<T>) -> A2lTreeDetail {
+    A2lTreeDetail {
+        label,
+        value: match value {
+            Some(item) => Cow::Owned(format!("{item:?}")),
+            None => Cow::Borrowed(NO_VALUE),
+        },
+    }
 }
 
-fn count_detail(label: &str, count: usize) -> A2lTreeDetail {
+fn count_detail(label: &'static str, count: usize) -> A2lTreeDetail {
     detail(label, count)
 }
 
+fn formula_detail(label: &'static str, formula: Option<(&String, &Vec<String>)>) -> A2lTreeDetail {
+    A2lTreeDetail {
+        label,
+        value: match formula {
+            Some((formula, list)) => Cow::Owned(format!("{formula} [{}]", list.join(", "))),
+            None => Cow::Borrowed(NO_VALUE),
+        },
+    }
+}
+
 fn limits_detail(lower: f64, upper: f64) -> A2lTreeDetail {
     detail("Limits", format!("{lower} .. {upper}"))
 }
@@ -105,9 +197,9 @@ impl A2lDetailProvider for a2lfile::Measurement {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             detail("Datatype", format!("{:?}", self.datatype)),
-            detail("Conversion", self.conversion.clone()),
+            detail("Conversion", &self.conversion),
             detail("Resolution", self.resolution),
             detail("Accuracy", self.accuracy),
             limits_detail(self.lower_limit, self.upper_limit),
@@ -143,16 +235,24 @@ impl A2lDetailProvider for a2lfile::Characteristic {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             detail("Type", format!("{:?}", self.characteristic_type)),
             detail("Address", format!("0x{:X}", self.address)),
-            detail("Deposit", self.deposit.clone()),
+            detail("Deposit", &self.deposit),
             detail("Max diff", self.max_diff),
-            detail("Conversion", self.conversion.clone()),
+            detail("Conversion", &self.conversion),
             limits_detail(self.lower_limit, self.upper_limit),
             opt_detail("Bit mask", &self.bit_mask),
             opt_detail("Byte order", &self.byte_order),
             opt_detail("Calibration access", &self.calibration_access),
+            formula_detail(
+                "Dependent characteristic",
+                self.dependent_characteristic.as_ref().map(|d| (&d.formula, &d.characteristic_list)),
+            ),
+            formula_detail(
+                "Virtual characteristic",
+                self.virtual_characteristic.as_ref().map(|v| (&v.formula, &v.characteristic_list)),
+            ),
             opt_detail("Display identifier", &self.display_identifier),
             opt_detail("Encoding", &self.encoding),
             opt_detail("Extended limits", &self.extended_limits),
@@ -181,12 +281,12 @@ impl A2lDetailProvider for a2lfile::AxisPts {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             detail("Address", format!("0x{:X}", self.address)),
-            detail("Input quantity", self.input_quantity.clone()),
-            detail("Deposit record", self.deposit_record.clone()),
+            detail("Input quantity", &self.input_quantity),
+            detail("Deposit record", &self.deposit_record),
             detail("Max diff", self.max_diff),
-            detail("Conversion", self.conversion.clone()),
+            detail("Conversion", &self.conversion),
             detail("Max axis points", self.max_axis_points),
             limits_detail(self.lower_limit, self.upper_limit),
             opt_detail("Byte order", &self.byte_order),
@@ -218,10 +318,10 @@ impl A2lDetailProvider for a2lfile::CompuMethod {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             detail("Conversion type", format!("{:?}", self.conversion_type)),
-            detail("Format", self.format.clone()),
-            detail("Unit", self.unit.clone()),
+            detail("Format", &self.format),
+            detail("Unit", &self.unit),
             opt_detail("Coeffs", &self.coeffs),
             opt_detail("Coeffs linear", &self.coeffs_linear),
             opt_detail("Compu tab ref", &self.compu_tab_ref),
@@ -239,7 +339,7 @@ impl A2lDetailProvider for a2lfile::CompuTab {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             detail("Conversion type", format!("{:?}", self.conversion_type)),
             detail("Value pairs", self.number_value_pairs),
             count_detail("Entries", self.tab_entry.len()),
@@ -256,7 +356,7 @@ impl A2lDetailProvider for a2lfile::CompuVtab {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             detail("Conversion type", format!("{:?}", self.conversion_type)),
             detail("Value pairs", self.number_value_pairs),
             count_detail("Entries", self.value_pairs.len()),
@@ -272,7 +372,7 @@ impl A2lDetailProvider for a2lfile::CompuVtabRange {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             detail("Value triples", self.number_value_triples),
             count_detail("Entries", self.value_triples.len()),
             opt_detail("Default value", &self.default_value),
@@ -322,7 +422,7 @@ impl A2lDetailProvider for a2lfile::Function {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             opt_detail("AR component", &self.ar_component),
             opt_detail("Def characteristic", &self.def_characteristic),
             opt_detail("Function version", &self.function_version),
@@ -344,7 +444,7 @@ impl A2lDetailProvider for a2lfile::Group {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             opt_detail("Function list", &self.function_list),
             opt_detail("Ref characteristic", &self.ref_characteristic),
             opt_detail("Ref measurement", &self.ref_measurement),
@@ -363,8 +463,8 @@ impl A2lDetailProvider for a2lfile::Unit {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
-            detail("Display", self.display.clone()),
+            detail("Long identifier", &self.long_identifier),
+            detail("Display", &self.display),
             detail("Unit type", format!("{:?}", self.unit_type)),
             opt_detail("Ref unit", &self.ref_unit),
             opt_detail("SI exponents", &self.si_exponents),
@@ -380,7 +480,7 @@ impl A2lDetailProvider for a2lfile::Frame {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             detail("Scaling unit", self.scaling_unit),
             detail("Rate", self.rate),
             opt_detail("Frame measurement", &self.frame_measurement),
@@ -396,7 +496,7 @@ impl A2lDetailProvider for a2lfile::Blob {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             detail("Start address", format!("0x{:X}", self.start_address)),
             detail("Size", self.size),
             opt_detail("Address type", &self.address_type),
@@ -419,8 +519,8 @@ impl A2lDetailProvider for a2lfile::Instance {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
-            detail("Type ref", self.type_ref.clone()),
+            detail("Long identifier", &self.long_identifier),
+            detail("Type ref", &self.type_ref),
             detail("Start address", format!("0x{:X}", self.start_address)),
             opt_detail("Address type", &self.address_type),
             opt_detail("Calibration access", &self.calibration_access),
@@ -442,12 +542,12 @@ impl A2lDetailProvider for a2lfile::Instance {
 impl A2lDetailProvider for a2lfile::Transformer {
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Version", self.version.clone()),
+            detail("Version", &self.version),
             detail("DLL (32-bit)", self.dllname_32bit.clone()),
             detail("DLL (64-bit)", self.dllname_64bit.clone()),
             detail("Timeout", self.timeout),
             detail("Trigger", format!("{:?}", self.trigger)),
-            detail("Inverse transformer", self.inverse_transformer.clone()),
+            detail("Inverse transformer", &self.inverse_transformer),
             opt_detail("In objects", &self.transformer_in_objects),
             opt_detail("Out objects", &self.transformer_out_objects),
         ]
@@ -461,11 +561,11 @@ impl A2lDetailProvider for a2lfile::TypedefAxis {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
-            detail("Input quantity", self.input_quantity.clone()),
-            detail("Record layout", self.record_layout.clone()),
+            detail("Long identifier", &self.long_identifier),
+            detail("Input quantity", &self.input_quantity),
+            detail("Record layout", &self.record_layout),
             detail("Max diff", self.max_diff),
-            detail("Conversion", self.conversion.clone()),
+            detail("Conversion", &self.conversion),
             detail("Max axis points", self.max_axis_points),
             limits_detail(self.lower_limit, self.upper_limit),
             opt_detail("Byte order", &self.byte_order),
@@ -486,7 +586,7 @@ impl A2lDetailProvider for a2lfile::TypedefBlob {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             detail("Size", self.size),
             opt_detail("Address type", &self.address_type),
         ]
@@ -500,11 +600,11 @@ impl A2lDetailProvider for a2lfile::TypedefCharacteristic {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             detail("Type", format!("{:?}", self.characteristic_type)),
-            detail("Record layout", self.record_layout.clone()),
+            detail("Record layout", &self.record_layout),
             detail("Max diff", self.max_diff),
-            detail("Conversion", self.conversion.clone()),
+            detail("Conversion", &self.conversion),
             limits_detail(self.lower_limit, self.upper_limit),
             opt_detail("Bit mask", &self.bit_mask),
             opt_detail("Byte order", &self.byte_order),
@@ -528,9 +628,9 @@ impl A2lDetailProvider for a2lfile::TypedefMeasurement {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             detail("Datatype", format!("{:?}", self.datatype)),
-            detail("Conversion", self.conversion.clone()),
+            detail("Conversion", &self.conversion),
             detail("Resolution", self.resolution),
             detail("Accuracy", self.accuracy),
             limits_detail(self.lower_limit, self.upper_limit),
@@ -555,7 +655,7 @@ impl A2lDetailProvider for a2lfile::TypedefStructure {
 
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Long identifier", self.long_identifier.clone()),
+            detail("Long identifier", &self.long_identifier),
             detail("Total size", self.total_size),
             opt_detail("Address type", &self.address_type),
             opt_detail("Consistent exchange", &self.consistent_exchange),
@@ -568,7 +668,7 @@ impl A2lDetailProvider for a2lfile::TypedefStructure {
 impl A2lDetailProvider for a2lfile::ModCommon {
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Comment", self.comment.clone()),
+            detail("Comment", &self.comment),
             opt_detail("Byte order", &self.byte_order),
             opt_detail("Data size", &self.data_size),
             opt_detail("Deposit", &self.deposit),
@@ -587,7 +687,7 @@ impl A2lDetailProvider for a2lfile::ModCommon {
 impl A2lDetailProvider for a2lfile::ModPar {
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("Comment", self.comment.clone()),
+            detail("Comment", &self.comment),
             opt_detail("CPU type", &self.cpu_type),
             opt_detail("Customer", &self.customer),
             opt_detail("Customer no", &self.customer_no),
@@ -642,7 +742,7 @@ impl A2lDetailProvider for a2lfile::IfData {
 impl A2lDetailProvider for a2lfile::UserRights {
     fn details(&self) -> Vec<A2lTreeDetail> {
         vec![
-            detail("User level", self.user_level_id.clone()),
+            detail("User level", &self.user_level_id),
             opt_detail("Read only", &self.read_only),
             count_detail("Ref groups", self.ref_group.len()),
         ]
@@ -711,7 +811,17 @@ fn collect_core_entities(a2l: &a2lfile::A2lFile) -> Vec<CoreEntity> {
     items
 }
 
-fn build_section_from_list<T: A2lObjectName + std::fmt::Debug + A2lDetailProvider>(
+fn named_tree_item<T: A2lObjectName + A2lDetailProvider>(module_name: &str, kind: &str, item: &T) -> A2lTreeItem {
+    A2lTreeItem {
+        id: format!("{module_name}::{kind}::{}", item.get_name()),
+        name: item.get_name().to_string(),
+        kind: kind.to_string(),
+        description: item.description(),
+        details: item.details(),
+    }
+}
+
+fn build_section_from_list<T: A2lObjectName + std::fmt::Debug + A2lDetailProvider + Sync>(
     module_name: &str,
     title: &str,
     kind: &str,
@@ -721,16 +831,8 @@ fn build_section_from_list<T: A2lObjectName + std::fmt::Debug + A2lDetailProvide
         return None;
     }
 
-    let entries = items
-        .iter()
-        .map(|item| A2lTreeItem {
-            id: format!("{module_name}::{kind}::{}", item.get_name()),
-            name: item.get_name().to_string(),
-            kind: kind.to_string(),
-            description: item.description(),
-            details: item.details(),
-        })
-        .collect();
+    let items: Vec<&T> = items.iter().collect();
+    let entries = items.par_iter().map(|item| named_tree_item(module_name, kind, *item)).collect();
 
     Some(A2lTreeSection {
         id: format!("{module_name}::{kind}"),
@@ -758,7 +860,7 @@ fn build_section_from_optional<T: std::fmt::Debug + A2lDetailProvider>(
     })
 }
 
-fn build_section_from_vec<T: std::fmt::Debug + A2lDetailProvider>(
+fn build_section_from_vec<T: std::fmt::Debug + A2lDetailProvider + Sync>(
     module_name: &str,
     title: &str,
     kind: &str,
@@ -771,7 +873,7 @@ fn build_section_from_vec<T: std::fmt::Debug + A2lDetailProvider>(
         id: format!("{module_name}::{kind}"),
         title: title.to_string(),
         items: items
-            .iter()
+            .par_iter()
             .enumerate()
             .map(|(index, item)| A2lTreeItem {
                 id: format!("{module_name}::{kind}::{index}"),
@@ -784,149 +886,141 @@ fn build_section_from_vec<T: std::fmt::Debug + A2lDetailProvider>(
     })
 }
 
-fn build_tree(a2l: &a2lfile::A2lFile) -> A2lTree {
-    let modules = a2l
-        .project
-        .module
-        .iter()
-        .map(|module| {
-            let module_name = module.get_name();
-            let mut sections = Vec::new();
-
-            if let Some(section) = build_section_from_list(module_name, "Measurements", "Measurement", &module.measurement) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Characteristics", "Characteristic", &module.characteristic) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Axis Points", "AxisPts", &module.axis_pts) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Compu Methods", "CompuMethod", &module.compu_method) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Compu Tables", "CompuTab", &module.compu_tab) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Compu VTabs", "CompuVtab", &module.compu_vtab) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(
-                module_name,
-                "Compu VTab Ranges",
-                "CompuVtabRange",
-                &module.compu_vtab_range,
-            ) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Record Layouts", "RecordLayout", &module.record_layout) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Functions", "Function", &module.function) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Groups", "Group", &module.group) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Units", "Unit", &module.unit) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Frames", "Frame", &module.frame) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Blobs", "Blob", &module.blob) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Instances", "Instance", &module.instance) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Transformers", "Transformer", &module.transformer) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Typedef Axis", "TypedefAxis", &module.typedef_axis) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(module_name, "Typedef Blob", "TypedefBlob", &module.typedef_blob) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(
+type SectionBuilder<'a> = &'a (dyn Fn() -> Option<A2lTreeSection> + Sync);
+
+fn build_module(module: &a2lfile::Module) -> A2lTreeModule {
+    let module_name = module.get_name();
+    // Sections are built concurrently, and the items of each list section in
+    // turn; `collect` keeps the order of the builders.
+    let builders: [SectionBuilder; 26] = [
+        &|| build_section_from_list(module_name, "Measurements", "Measurement", &module.measurement),
+        &|| build_section_from_list(module_name, "Characteristics", "Characteristic", &module.characteristic),
+        &|| build_section_from_list(module_name, "Axis Points", "AxisPts", &module.axis_pts),
+        &|| build_section_from_list(module_name, "Compu Methods", "CompuMethod", &module.compu_method),
+        &|| build_section_from_list(module_name, "Compu Tables", "CompuTab", &module.compu_tab),
+        &|| build_section_from_list(module_name, "Compu VTabs", "CompuVtab", &module.compu_vtab),
+        &|| build_section_from_list(module_name, "Compu VTab Ranges", "CompuVtabRange", &module.compu_vtab_range),
+        &|| build_section_from_list(module_name, "Record Layouts", "RecordLayout", &module.record_layout),
+        &|| build_section_from_list(module_name, "Functions", "Function", &module.function),
+        &|| build_section_from_list(module_name, "Groups", "Group", &module.group),
+        &|| build_section_from_list(module_name, "Units", "Unit", &module.unit),
+        &|| build_section_from_list(module_name, "Frames", "Frame", &module.frame),
+        &|| build_section_from_list(module_name, "Blobs", "Blob", &module.blob),
+        &|| build_section_from_list(module_name, "Instances", "Instance", &module.instance),
+        &|| build_section_from_list(module_name, "Transformers", "Transformer", &module.transformer),
+        &|| build_section_from_list(module_name, "Typedef Axis", "TypedefAxis", &module.typedef_axis),
+        &|| build_section_from_list(module_name, "Typedef Blob", "TypedefBlob", &module.typedef_blob),
+        &|| {
+            build_section_from_list(
                 module_name,
                 "Typedef Characteristic",
                 "TypedefCharacteristic",
                 &module.typedef_characteristic,
-            ) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(
-                module_name,
-                "Typedef Measurement",
-                "TypedefMeasurement",
-                &module.typedef_measurement,
-            ) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_list(
-                module_name,
-                "Typedef Structure",
-                "TypedefStructure",
-                &module.typedef_structure,
-            ) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_optional(module_name, "Mod Common", "ModCommon", module.mod_common.as_ref()) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_optional(module_name, "Mod Par", "ModPar", module.mod_par.as_ref()) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_optional(
-                module_name,
-                "Variant Coding",
-                "VariantCoding",
-                module.variant_coding.as_ref(),
-            ) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_optional(module_name, "A2ML", "A2ML", module.a2ml.as_ref()) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_vec(module_name, "IF_DATA", "IfData", &module.if_data) {
-                sections.push(section);
-            }
-            if let Some(section) = build_section_from_vec(module_name, "User Rights", "UserRights", &module.user_rights) {
-                sections.push(section);
-            }
+            )
+        },
+        &|| build_section_from_list(module_name, "Typedef Measurement", "TypedefMeasurement", &module.typedef_measurement),
+        &|| build_section_from_list(module_name, "Typedef Structure", "TypedefStructure", &module.typedef_structure),
+        &|| build_section_from_optional(module_name, "Mod Common", "ModCommon", module.mod_common.as_ref()),
+        &|| build_section_from_optional(module_name, "Mod Par", "ModPar", module.mod_par.as_ref()),
+        &|| build_section_from_optional(module_name, "Variant Coding", "VariantCoding", module.variant_coding.as_ref()),
+        &|| build_section_from_optional(module_name, "A2ML", "A2ML", module.a2ml.as_ref()),
+        &|| build_section_from_vec(module_name, "IF_DATA", "IfData", &module.if_data),
+        &|| build_section_from_vec(module_name, "User Rights", "UserRights", &module.user_rights),
+    ];
 
-            A2lTreeModule {
-                id: module_name.to_string(),
-                name: module_name.to_string(),
-                long_identifier: module.long_identifier.clone(),
-                sections,
-            }
-        })
-        .collect();
+    A2lTreeModule {
+        id: module_name.to_string(),
+        name: module_name.to_string(),
+        long_identifier: module.long_identifier.clone(),
+        sections: builders.par_iter().filter_map(|build| build()).collect(),
+    }
+}
+
+fn build_tree(a2l: &a2lfile::A2lFile) -> A2lTree {
+    let modules: Vec<&a2lfile::Module> = a2l.project.module.iter().collect();
+    A2lTree {
+        modules: modules.par_iter().map(|module| build_module(module)).collect(),
+    }
+}
+
+/// JSON of `tree`, with the modules serialized concurrently.
+fn serialize_tree(tree: &A2lTree) -> Result<String, String> {
+    let modules = tree
+        .modules
+        .par_iter()
+        .map(serde_json::to_string)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    let mut json = String::with_capacity(modules.iter().map(|m| m.len() + 1).sum::<usize>() + 16);
+    json.push_str("{\"modules\":[");
+    for (index, module) in modules.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        json.push_str(module);
+    }
+    json.push_str("]}");
+    Ok(json)
+}
 
-    A2lTree { modules }
+/// Parses A2L text held in memory. Files are loaded with `load_a2l_file`
+/// instead, which resolves `/include` directives relative to them. Text
+/// problems found by the normalization are returned along with the parser
+/// warnings.
+fn parse_a2l(contents: &str, normalization: Option<String>) -> Result<(a2lfile::A2lFile, Vec<String>), String> {
+    let loaded = a2lfile::load_from_string(contents, None, false).map_err(|error| error.to_string())?;
+    finish_parse(loaded, normalization)
+}
+
+/// Loads `path` through the parser's own file handling, which resolves
+/// `/include` directives relative to it.
+fn load_a2l_file(path: &str, normalization: Option<String>) -> Result<(a2lfile::A2lFile, Vec<String>), String> {
+    let loaded = a2lfile::load(path, None, false).map_err(|error| error.to_string())?;
+    finish_parse(loaded, normalization)
+}
+
+fn finish_parse(
+    (mut a2l, warnings): (a2lfile::A2lFile, Vec<a2lfile::A2lError>),
+    normalization: Option<String>,
+) -> Result<(a2lfile::A2lFile, Vec<String>), String> {
+    let mut warnings: Vec<String> = warnings.iter().map(|warning| warning.to_string()).collect();
+    if let Some(mode) = normalization {
+        let mode = text_normalize::NormalizationMode::parse(&mode)?;
+        let issues = text_normalize::normalize_file(&mut a2l, mode);
+        let normalized = mode != text_normalize::NormalizationMode::Report;
+        warnings.extend(issues.iter().map(|issue| issue.message(normalized)));
+    }
+    Ok((a2l, warnings))
 }
 
 #[tauri::command]
 fn load_a2l_from_string(
     contents: String,
+    normalization: Option<String>,
+    doc_id: Option<String>,
     state: tauri::State<AppState>,
 ) -> Result<A2lMetadata, String> {
-    let (a2l, warnings) = a2lfile::load_from_string(&contents, None, false)
-        .map_err(|error| error.to_string())?;
+    let (a2l, warnings) = parse_a2l(&contents, normalization)?;
 
     let metadata = build_metadata(&a2l, warnings.len());
-    *state.a2l.lock().map_err(|_| "State lock poisoned")? = Some(a2l);
+    let mut documents = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let id = documents.replace(doc_id.as_deref(), None, a2l)?;
+    documents.document_mut(Some(&id))?.diagnostics = diagnostics::from_warnings(&warnings);
 
     Ok(metadata)
 }
 
+/// Starts loading `path` in the background and returns the job ID at once.
+/// Progress and the result are reported through `load_jobs` events.
 #[tauri::command]
-fn load_a2l_from_path(path: String, state: tauri::State<AppState>) -> Result<A2lMetadata, String> {
-    let contents = fs::read_to_string(&path).map_err(|error| error.to_string())?;
-    load_a2l_from_string(contents, state)
+fn load_a2l_from_path(
+    path: String,
+    normalization: Option<String>,
+    doc_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<AppState>,
+) -> Result<String, String> {
+    load_jobs::start_load(app, &state, path, normalization, doc_id)
 }
 
 #[tauri::command]
@@ -934,10 +1028,13 @@ fn update_project_metadata(
     name: String,
     long_identifier: String,
     header_comment: Option<String>,
+    doc_id: Option<String>,
     state: tauri::State<AppState>,
 ) -> Result<A2lMetadata, String> {
-    let mut guard = state.a2l.lock().map_err(|_| "State lock poisoned")?;
-    let a2l = guard.as_mut().ok_or("No A2L loaded")?;
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("update_project_metadata", doc_id.as_deref())?;
+    edit.touch_project();
+    let a2l = edit.a2l_mut();
     a2l.project.name = name;
     a2l.project.long_identifier = long_identifier;
     match header_comment.map(|comment| comment.trim().to_string()) {
@@ -957,33 +1054,94 @@ fn update_project_metadata(
 }
 
 #[tauri::command]
-fn export_a2l(state: tauri::State<AppState>) -> Result<String, String> {
-    let guard = state.a2l.lock().map_err(|_| "State lock poisoned")?;
-    let a2l = guard.as_ref().ok_or("No A2L loaded")?;
-    Ok(a2l.write_to_string())
+fn export_a2l(doc_id: Option<String>, state: tauri::State<AppState>) -> Result<String, String> {
+    let options = state.export_options.lock().map_err(|_| "State lock poisoned")?.clone();
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    Ok(export_options::render_a2l(a2l, &options))
 }
 
 #[tauri::command]
-fn save_a2l_to_path(path: String, state: tauri::State<AppState>) -> Result<(), String> {
-    let guard = state.a2l.lock().map_err(|_| "State lock poisoned")?;
-    let a2l = guard.as_ref().ok_or("No A2L loaded")?;
-    let content = a2l.write_to_string();
-    fs::write(&path, content).map_err(|e| e.to_string())?;
+fn save_a2l_to_path(path: String, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<(), String> {
+    let options = state.export_options.lock().map_err(|_| "State lock poisoned")?.clone();
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let document = guard.document_mut(doc_id.as_deref())?;
+    // Render everything first so that a model that cannot be saved in the
+    // selected include mode leaves the files on disk untouched.
+    let include_files = if options.include_mode == "preserve" {
+        includes::render_include_files(&document.a2l)?
+    } else {
+        Vec::new()
+    };
+    let content = export_options::render_a2l(&document.a2l, &options);
+    watcher::own_write(&state, &path, || fs::write(&path, content).map_err(|e| e.to_string()))?;
+    includes::write_include_files(&state, &path, include_files)?;
+    document.path = Some(path);
+    document.saved_revision = document.revision;
+    document.base = Some(document.a2l.clone());
     Ok(())
 }
 
 #[tauri::command]
-fn list_core_entities(state: tauri::State<AppState>) -> Result<Vec<CoreEntity>, String> {
-    let guard = state.a2l.lock().map_err(|_| "State lock poisoned")?;
-    let a2l = guard.as_ref().ok_or("No A2L loaded")?;
+fn list_core_entities(doc_id: Option<String>, state: tauri::State<AppState>) -> Result<Vec<CoreEntity>, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
     Ok(collect_core_entities(a2l))
 }
 
+/// Single item of `list_a2l_tree` by its ID (`module::kind::name`), so the
+/// frontend can refresh one node after an entity event.
+#[tauri::command]
+fn get_entity_tree_item(id: String, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<A2lTreeItem, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+
+    let mut parts = id.splitn(3, "::");
+    let (Some(module_name), Some(kind), Some(name)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(format!("Invalid tree item ID: {id}"));
+    };
+    let module = find_module(a2l, Some(module_name))?;
+
+    macro_rules! lookup {
+        ($($kind:literal => $field:ident),* $(,)?) => {
+            match kind {
+                $($kind => module.$field.get(name).map(|item| named_tree_item(module_name, kind, item)),)*
+                _ => None,
+            }
+        };
+    }
+    lookup! {
+        "Measurement" => measurement,
+        "Characteristic" => characteristic,
+        "AxisPts" => axis_pts,
+        "CompuMethod" => compu_method,
+        "CompuTab" => compu_tab,
+        "CompuVtab" => compu_vtab,
+        "CompuVtabRange" => compu_vtab_range,
+        "RecordLayout" => record_layout,
+        "Function" => function,
+        "Group" => group,
+        "Unit" => unit,
+        "Frame" => frame,
+        "Blob" => blob,
+        "Instance" => instance,
+        "Transformer" => transformer,
+        "TypedefAxis" => typedef_axis,
+        "TypedefBlob" => typedef_blob,
+        "TypedefCharacteristic" => typedef_characteristic,
+        "TypedefMeasurement" => typedef_measurement,
+        "TypedefStructure" => typedef_structure,
+    }
+    .ok_or_else(|| format!("Tree item {id} not found"))
+}
+
 #[tauri::command]
-fn list_a2l_tree(state: tauri::State<AppState>) -> Result<A2lTree, String> {
-    let guard = state.a2l.lock().map_err(|_| "State lock poisoned")?;
-    let a2l = guard.as_ref().ok_or("No A2L loaded")?;
-    Ok(build_tree(a2l))
+fn list_a2l_tree(doc_id: Option<String>, state: tauri::State<AppState>) -> Result<tauri::ipc::Response, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let tree = build_tree(a2l);
+    drop(guard);
+    Ok(tauri::ipc::Response::new(serialize_tree(&tree)?))
 }
 
 #[tauri::command]
@@ -991,10 +1149,18 @@ fn update_entity_name(
     kind: String,
     name: String,
     new_name: String,
+    doc_id: Option<String>,
     state: tauri::State<AppState>,
 ) -> Result<EntityUpdateResult, String> {
-    let mut guard = state.a2l.lock().map_err(|_| "State lock poisoned")?;
-    let a2l = guard.as_mut().ok_or("No A2L loaded")?;
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("update_entity_name", doc_id.as_deref())?;
+    if kind == "Module" {
+        edit.touch_module(&name);
+        edit.touch_module(&new_name);
+    } else {
+        edit.touch_renamed(&kind, &name, &new_name);
+    }
+    let a2l = edit.a2l_mut();
 
     for module in a2l.project.module.iter_mut() {
         if kind == "Module" && module.get_name() == name {
@@ -1033,10 +1199,13 @@ fn update_entity_name(
 fn update_module_long_identifier(
     name: String,
     long_identifier: String,
+    doc_id: Option<String>,
     state: tauri::State<AppState>,
 ) -> Result<EntityUpdateResult, String> {
-    let mut guard = state.a2l.lock().map_err(|_| "State lock poisoned")?;
-    let a2l = guard.as_mut().ok_or("No A2L loaded")?;
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("update_module_long_identifier", doc_id.as_deref())?;
+    edit.touch_module(&name);
+    let a2l = edit.a2l_mut();
 
     for module in a2l.project.module.iter_mut() {
         if module.get_name() == name {
@@ -1112,6 +1281,41 @@ fn string_to_characteristic_type(s: &str) -> Option<a2lfile::CharacteristicType>
     }
 }
 
+fn parse_hex_address(s: &str) -> Result<u32, String> {
+    let clean = s.trim().trim_start_matches("0x").trim_start_matches("0X");
+    u32::from_str_radix(clean, 16).map_err(|_| format!("Invalid hex address: {s}"))
+}
+
+fn find_module<'a>(
+    a2l: &'a a2lfile::A2lFile,
+    module_name: Option<&str>,
+) -> Result<&'a a2lfile::Module, String> {
+    match module_name {
+        Some(name) => a2l
+            .project
+            .module
+            .iter()
+            .find(|m| m.get_name() == name)
+            .ok_or(format!("Module {} not found", name)),
+        None => a2l.project.module.first().ok_or("No modules in project".to_string()),
+    }
+}
+
+fn find_module_mut<'a>(
+    a2l: &'a mut a2lfile::A2lFile,
+    module_name: Option<&str>,
+) -> Result<&'a mut a2lfile::Module, String> {
+    match module_name {
+        Some(name) => a2l
+            .project
+            .module
+            .iter_mut()
+            .find(|m| m.get_name() == name)
+            .ok_or(format!("Module {} not found", name)),
+        None => a2l.project.module.first_mut().ok_or("No modules in project".to_string()),
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 struct MeasurementData {
     name: String,
@@ -1123,6 +1327,8 @@ struct MeasurementData {
     lower_limit: f64,
     upper_limit: f64,
     ecu_address: Option<String>,
+    bit_mask: Option<String>,
+    bit_operation: Option<bitfield::BitOperationData>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -1154,9 +1360,9 @@ struct AxisPtsData {
 }
 
 #[tauri::command]
-fn get_measurement(name: String, state: tauri::State<AppState>) -> Result<MeasurementData, String> {
-    let guard = state.a2l.lock().map_err(|_| "State lock poisoned")?;
-    let a2l = guard.as_ref().ok_or("No A2L loaded")?;
+fn get_measurement(name: String, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<MeasurementData, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
 
     for module in a2l.project.module.iter() {
         if let Some(m) = module.measurement.iter().find(|m| m.get_name() == name) {
@@ -1170,6 +1376,8 @@ fn get_measurement(name: String, state: tauri::State<AppState>) -> Result<Measur
                 lower_limit: m.lower_limit,
                 upper_limit: m.upper_limit,
                 ecu_address: m.ecu_address.as_ref().map(|a| format!("0x{:X}", a.address)),
+                bit_mask: m.bit_mask.as_ref().map(|b| format!("0x{:X}", b.mask)),
+                bit_operation: m.bit_operation.as_ref().map(bitfield::BitOperationData::from_a2l),
             });
         }
     }
@@ -1177,9 +1385,11 @@ fn get_measurement(name: String, state: tauri::State<AppState>) -> Result<Measur
 }
 
 #[tauri::command]
-fn update_measurement(name: String, data: MeasurementData, state: tauri::State<AppState>) -> Result<(), String> {
-    let mut guard = state.a2l.lock().map_err(|_| "State lock poisoned")?;
-    let a2l = guard.as_mut().ok_or("No A2L loaded")?;
+fn update_measurement(name: String, data: MeasurementData, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("update_measurement", doc_id.as_deref())?;
+    edit.touch_renamed("Measurement", &name, &data.name);
+    let a2l = edit.a2l_mut();
 
     let new_datatype = string_to_datatype(&data.datatype)
         .ok_or_else(|| format!("Invalid data type: {}", data.datatype))?;
@@ -1193,6 +1403,19 @@ fn update_measurement(name: String, data: MeasurementData, state: tauri::State<A
         _ => None
     };
 
+    let new_bit_mask = match data.bit_mask {
+        Some(s) if !s.trim().is_empty() => {
+             let clean = s.trim().trim_start_matches("0x").trim_start_matches("0X");
+             let mask_val = u64::from_str_radix(clean, 16).map_err(|_| "Invalid hex bit mask")?;
+             Some(a2lfile::BitMask::new(mask_val))
+        },
+        _ => None
+    };
+    let new_bit_operation = match &data.bit_operation {
+        Some(op) => op.to_a2l()?,
+        None => None
+    };
+
     for module in a2l.project.module.iter_mut() {
         if let Some(m) = module.measurement.iter_mut().find(|m| m.get_name() == name) {
             m.set_name(data.name);
@@ -1204,6 +1427,8 @@ fn update_measurement(name: String, data: MeasurementData, state: tauri::State<A
             m.lower_limit = data.lower_limit;
             m.upper_limit = data.upper_limit;
             m.ecu_address = new_address;
+            m.bit_mask = new_bit_mask;
+            m.bit_operation = new_bit_operation;
             return Ok(());
         }
     }
@@ -1211,9 +1436,9 @@ fn update_measurement(name: String, data: MeasurementData, state: tauri::State<A
 }
 
 #[tauri::command]
-fn get_characteristic(name: String, state: tauri::State<AppState>) -> Result<CharacteristicData, String> {
-    let guard = state.a2l.lock().map_err(|_| "State lock poisoned")?;
-    let a2l = guard.as_ref().ok_or("No A2L loaded")?;
+fn get_characteristic(name: String, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<CharacteristicData, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
 
     for module in a2l.project.module.iter() {
         if let Some(c) = module.characteristic.iter().find(|c| c.get_name() == name) {
@@ -1235,9 +1460,11 @@ fn get_characteristic(name: String, state: tauri::State<AppState>) -> Result<Cha
 }
 
 #[tauri::command]
-fn update_characteristic(name: String, data: CharacteristicData, state: tauri::State<AppState>) -> Result<(), String> {
-    let mut guard = state.a2l.lock().map_err(|_| "State lock poisoned")?;
-    let a2l = guard.as_mut().ok_or("No A2L loaded")?;
+fn update_characteristic(name: String, data: CharacteristicData, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("update_characteristic", doc_id.as_deref())?;
+    edit.touch_renamed("Characteristic", &name, &data.name);
+    let a2l = edit.a2l_mut();
 
     let new_type = string_to_characteristic_type(&data.characteristic_type)
         .ok_or_else(|| format!("Invalid characteristic type: {}", data.characteristic_type))?;
@@ -1273,9 +1500,9 @@ fn update_characteristic(name: String, data: CharacteristicData, state: tauri::S
 }
 
 #[tauri::command]
-fn get_axis_pts(name: String, state: tauri::State<AppState>) -> Result<AxisPtsData, String> {
-    let guard = state.a2l.lock().map_err(|_| "State lock poisoned")?;
-    let a2l = guard.as_ref().ok_or("No A2L loaded")?;
+fn get_axis_pts(name: String, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<AxisPtsData, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
 
     for module in a2l.project.module.iter() {
         if let Some(a) = module.axis_pts.iter().find(|a| a.get_name() == name) {
@@ -1297,9 +1524,11 @@ fn get_axis_pts(name: String, state: tauri::State<AppState>) -> Result<AxisPtsDa
 }
 
 #[tauri::command]
-fn update_axis_pts(name: String, data: AxisPtsData, state: tauri::State<AppState>) -> Result<(), String> {
-    let mut guard = state.a2l.lock().map_err(|_| "State lock poisoned")?;
-    let a2l = guard.as_mut().ok_or("No A2L loaded")?;
+fn update_axis_pts(name: String, data: AxisPtsData, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("update_axis_pts", doc_id.as_deref())?;
+    edit.touch_renamed("AxisPts", &name, &data.name);
+    let a2l = edit.a2l_mut();
 
     let clean_addr = data.address.trim().trim_start_matches("0x").trim_start_matches("0X");
     let new_addr_val = u32::from_str_radix(clean_addr, 16).map_err(|_| "Invalid hex address")?;
@@ -1326,15 +1555,15 @@ fn update_axis_pts(name: String, data: AxisPtsData, state: tauri::State<AppState
 struct ElfSymbol {
     name: String,
     address: u64,
+    /// Byte size; 0 when the symbol source does not record it.
     size: u64,
     bind: String,
     type_str: String,
     section: String,
 }
 
-#[tauri::command]
-fn load_elf_symbols(path: String) -> Result<Vec<ElfSymbol>, String> {
-    let buffer = fs::read(&path).map_err(|e| e.to_string())?;
+fn read_elf_symbols(path: &str) -> Result<Vec<ElfSymbol>, String> {
+    let buffer = fs::read(path).map_err(|e| e.to_string())?;
     let elf = Elf::parse(&buffer).map_err(|e| e.to_string())?;
     
     let mut symbols = Vec::new();
@@ -1372,14 +1601,32 @@ fn load_elf_symbols(path: String) -> Result<Vec<ElfSymbol>, String> {
     Ok(symbols)
 }
 
+/// Parses the ELF and keeps its symbols in the app state; the frontend pages
+/// through them with `query_elf_symbols`.
+#[tauri::command]
+fn load_elf_symbols(path: String, state: tauri::State<AppState>) -> Result<elf_symbols::ElfSummary, String> {
+    let symbols = read_elf_symbols(&path)?;
+    let index = elf_symbols::ElfIndex::new(path, symbols);
+    let summary = index.summary();
+    *state.elf.lock().map_err(|_| "State lock poisoned")? = Some(index);
+    Ok(summary)
+}
+
 #[tauri::command]
 fn create_measurements_from_elf(
     module_name: Option<String>,
     symbols: Vec<ElfSymbol>, 
+    rules: Option<symbol_names::NamingRules>,
+    template: Option<String>,
+    doc_id: Option<String>,
+    app: tauri::AppHandle,
     state: tauri::State<AppState>
 ) -> Result<EntityUpdateResult, String> {
-    let mut guard = state.a2l.lock().map_err(|_| "State lock poisoned")?;
-    let a2l = guard.as_mut().ok_or("No A2L loaded")?;
+    let template = templates::load_template(&app, template.as_deref(), "Measurement")?;
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("create_measurements_from_elf", doc_id.as_deref())?;
+    edit.touch_target_module(module_name.as_deref())?;
+    let a2l = edit.a2l_mut();
 
     let target_module = if let Some(name) = module_name {
         a2l.project.module.iter_mut().find(|m| m.get_name() == name)
@@ -1388,14 +1635,25 @@ fn create_measurements_from_elf(
         a2l.project.module.first_mut().ok_or("No modules in project")?
     };
 
-    for sym in symbols {
-        let mut m = a2lfile::Measurement::new(sym.name, a2lfile::DataType::Ubyte);
+    let existing = references::object_namespace(target_module);
+    let symbol_names: Vec<String> = symbols.iter().map(|sym| sym.name.clone()).collect();
+    let names = symbol_names::apply_rules(&rules.unwrap_or_default(), &symbol_names, &existing);
+
+    for (sym, name) in symbols.into_iter().zip(names) {
+        let mut m = a2lfile::Measurement::new(name.name().to_string(), a2lfile::DataType::Ubyte);
+        if name.name() != sym.name {
+            // Keep the link to the original symbol for address updates.
+            m.symbol_link = Some(a2lfile::SymbolLink::new(sym.name.clone(), 0));
+        }
         m.ecu_address = Some(a2lfile::EcuAddress::new(sym.address as u32));
         m.lower_limit = 0.0;
         m.upper_limit = 255.0; // Default UBYTE limits
         m.resolution = 1;
         m.accuracy = 0.0;
         m.conversion = "NO_COMPU_METHOD".to_string();
+        if let Some(template) = &template {
+            template.apply_to_measurement(&mut m);
+        }
         target_module.measurement.push(m);
     }
 
@@ -1410,24 +1668,166 @@ pub fn run() {
     tauri::Builder::default()
         .manage(AppState::default())
         .plugin(tauri_plugin_opener::init())
+        .setup(|app| {
+            autosave::start(app.handle().clone());
+            watcher::start(app.handle().clone());
+            entity_events::start(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             load_a2l_from_string,
             load_a2l_from_path,
+            load_jobs::cancel_load,
+            diagnostics::get_load_diagnostics,
             update_project_metadata,
             export_a2l,
+            ordering::sort_a2l,
             save_a2l_to_path,
+            a2l_json::export_a2l_json,
+            a2l_json::import_a2l_json,
             list_core_entities,
             list_a2l_tree,
+            get_entity_tree_item,
             update_entity_name,
             update_module_long_identifier,
+            modules::create_module,
+            modules::delete_module,
+            modules::rename_module,
+            modules::move_entities_to_module,
             get_measurement,
             update_measurement,
             get_characteristic,
             update_characteristic,
+            dependent_characteristics::get_characteristic_formulas,
+            dependent_characteristics::update_characteristic_formula,
             get_axis_pts,
             update_axis_pts,
             load_elf_symbols,
-            create_measurements_from_elf
+            elf_symbols::query_elf_symbols,
+            symbol_sources::load_symbol_file,
+            create_measurements_from_elf,
+            bitfield::create_bitfield_measurements,
+            elf_characteristics::create_characteristics_from_elf,
+            symbol_links::check_symbol_links,
+            version::convert_a2l_version,
+            elf_groups::group_measurements_by_elf_origin,
+            table::export_entities_table,
+            table::import_entities_table,
+            dbc::import_dbc,
+            mdf::cross_check_mdf,
+            templates::save_entity_template,
+            templates::list_entity_templates,
+            templates::delete_entity_template,
+            working_sets::create_working_set,
+            working_sets::delete_working_set,
+            working_sets::add_to_working_set,
+            working_sets::remove_from_working_set,
+            working_sets::list_working_sets,
+            tool_export::export_a2l_for_tool,
+            tool_compat::list_tool_profiles,
+            tool_compat::check_tool_compatibility,
+            access_policy::get_calibration_access_policy,
+            access_policy::apply_calibration_access_policy,
+            access_policy::find_access_policy_violations,
+            user_rights::list_user_rights,
+            user_rights::upsert_user_rights,
+            user_rights::delete_user_rights,
+            user_rights::get_effective_rights,
+            dataset::generate_dataset_template,
+            report::generate_report,
+            scripting::run_script,
+            address_map::build_address_map,
+            placement::auto_assign_addresses,
+            sizes::get_object_size,
+            sizes::find_matrix_dim_conflicts,
+            budgets::get_budget_config,
+            budgets::set_budget_config,
+            budgets::evaluate_budgets,
+            references::get_reference_config,
+            references::set_reference_config,
+            references::check_references,
+            name_lint::lint_names,
+            limits::recompute_limits,
+            references::find_references,
+            reference_fixes::suggest_reference_fixes,
+            reference_fixes::apply_reference_fix,
+            frames::list_frames,
+            frames::create_frame,
+            frames::update_frame,
+            frames::delete_frame,
+            units::list_units,
+            units::create_unit,
+            units::update_unit,
+            units::delete_unit,
+            units::check_unit_usage,
+            mod_common::get_mod_common,
+            mod_common::update_mod_common,
+            mod_par::get_mod_par,
+            mod_par::update_mod_par_identification,
+            mod_par::upsert_memory_segment,
+            mod_par::delete_memory_segment,
+            mod_par::upsert_memory_layout,
+            mod_par::delete_memory_layout,
+            mod_par::set_system_constant,
+            mod_par::delete_system_constant,
+            session::get_session,
+            session::update_session,
+            session::add_recent_file,
+            text_normalize::normalize_text_fields,
+            bandwidth::plan_raster_bandwidth,
+            export_options::get_export_options,
+            export_options::set_export_options,
+            documents::open_document,
+            documents::close_document,
+            documents::set_active_document,
+            documents::list_documents,
+            autosave::get_autosave_config,
+            autosave::set_autosave_config,
+            autosave::list_backups,
+            autosave::recover_backup,
+            autosave::delete_backup,
+            watcher::reload_external_changes,
+            snapshots::create_snapshot,
+            snapshots::restore_snapshot,
+            snapshots::delete_snapshot,
+            snapshots::list_snapshots,
+            entity_copy::copy_entities,
+            subset::extract_subset,
+            cleanup::find_unused_objects,
+            cleanup::remove_unused_objects,
+            dedup::find_duplicate_conversions,
+            dedup::deduplicate_conversions,
+            axis_descr::get_axis_descr,
+            axis_descr::update_axis_descr,
+            axis_descr::delete_axis_descr,
+            annotations::list_annotations,
+            annotations::add_annotation,
+            annotations::update_annotation,
+            annotations::delete_annotation,
+            entity_source::get_entity_source,
+            entity_source::apply_entity_source,
+            transaction::apply_transaction,
+            includes::list_include_files,
+            ifdata::get_ifdata_text,
+            ifdata::get_ifdata_tree,
+            ifdata::set_ifdata_text,
+            hex::load_hex_image,
+            epk::verify_epk,
+            variant_coding::get_variant_coding,
+            variant_coding::upsert_var_criterion,
+            variant_coding::delete_var_criterion,
+            variant_coding::upsert_var_characteristic,
+            variant_coding::delete_var_characteristic,
+            variant_coding::set_var_forbidden_combinations,
+            variant_coding::set_var_naming,
+            variant_coding::resolve_variant_addresses,
+            typedefs::list_structure_components,
+            typedefs::upsert_structure_component,
+            typedefs::delete_structure_component,
+            typedefs::expand_instance,
+            symbol_names::preview_symbol_names,
+            audit::get_change_log,
+            audit::export_change_log
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
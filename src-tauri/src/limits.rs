@@ -0,0 +1,267 @@
+use a2lfile::A2lObjectName;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::layout::datatype_range;
+use crate::working_sets::working_set_members;
+use crate::AppState;
+
+const LIMIT_KINDS: &[&str] = &["Measurement", "Characteristic", "AxisPts"];
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct LimitSelector {
+    module_name: Option<String>,
+    /// Subset of Measurement, Characteristic and AxisPts; all when empty.
+    kinds: Vec<String>,
+    /// Exact object names; combined with `pattern` as an alternative.
+    names: Vec<String>,
+    pattern: Option<String>,
+    /// Saved working set whose members are selected as well.
+    working_set: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct LimitChange {
+    module: String,
+    kind: String,
+    name: String,
+    old_lower: f64,
+    old_upper: f64,
+    new_lower: f64,
+    new_upper: f64,
+}
+
+#[derive(Serialize)]
+pub struct SkippedLimit {
+    module: String,
+    kind: String,
+    name: String,
+    reason: String,
+}
+
+#[derive(Serialize)]
+pub struct LimitReport {
+    changed: Vec<LimitChange>,
+    skipped: Vec<SkippedLimit>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    /// Replace the limits of every selected object.
+    All,
+    /// Replace only limits that are empty (0..0), inverted or outside the physical range.
+    Invalid,
+    /// Keep the limits but clamp them into the physical range.
+    Clamp,
+}
+
+impl Mode {
+    fn parse(mode: Option<&str>) -> Result<Self, String> {
+        match mode.unwrap_or("invalid") {
+            "all" => Ok(Self::All),
+            "invalid" => Ok(Self::Invalid),
+            "clamp" => Ok(Self::Clamp),
+            other => Err(format!("Unknown limit mode: {other}")),
+        }
+    }
+}
+
+/// Physical range of the raw interval `raw` under the conversion method
+/// `conversion`. Fails for conversions that cannot be evaluated in closed form.
+fn physical_range(module: &a2lfile::Module, conversion: &str, raw: (f64, f64)) -> Result<(f64, f64), String> {
+    if conversion == "NO_COMPU_METHOD" {
+        return Ok(raw);
+    }
+    let cm = module
+        .compu_method
+        .get(conversion)
+        .ok_or_else(|| format!("Conversion '{conversion}' not found"))?;
+    let (low, high) = match cm.conversion_type {
+        // Limits of verbal tables are given in raw values.
+        a2lfile::ConversionType::Identical | a2lfile::ConversionType::TabVerb => raw,
+        a2lfile::ConversionType::Linear => {
+            let c = cm.coeffs_linear.as_ref().ok_or("LINEAR conversion without COEFFS_LINEAR")?;
+            (c.a * raw.0 + c.b, c.a * raw.1 + c.b)
+        }
+        a2lfile::ConversionType::RatFunc => {
+            let c = cm.coeffs.as_ref().ok_or("RAT_FUNC conversion without COEFFS")?;
+            // ASAP2 defines RAT_FUNC as the inverse: raw = (a*x²+b*x+c)/(d*x²+e*x+f).
+            // Only the linear case b*x + c = f*raw with a = d = e = 0 is solvable here.
+            if c.a != 0.0 || c.d != 0.0 || c.e != 0.0 || c.b == 0.0 {
+                return Err("non-linear RAT_FUNC".to_string());
+            }
+            let phys = |r: f64| (c.f * r - c.c) / c.b;
+            (phys(raw.0), phys(raw.1))
+        }
+        a2lfile::ConversionType::TabIntp | a2lfile::ConversionType::TabNointp => {
+            let tab = cm
+                .compu_tab_ref
+                .as_ref()
+                .and_then(|r| module.compu_tab.get(&r.conversion_table))
+                .ok_or("conversion table not found")?;
+            let values = tab.tab_entry.iter().map(|entry| entry.out_val);
+            let low = values.clone().fold(f64::INFINITY, f64::min);
+            let high = values.fold(f64::NEG_INFINITY, f64::max);
+            if !low.is_finite() || !high.is_finite() {
+                return Err("empty conversion table".to_string());
+            }
+            (low, high)
+        }
+        a2lfile::ConversionType::Form => return Err("FORM conversions are not evaluated".to_string()),
+    };
+    Ok((low.min(high), low.max(high)))
+}
+
+fn new_limits(mode: Mode, current: (f64, f64), physical: (f64, f64)) -> Option<(f64, f64)> {
+    let (lower, upper) = current;
+    let next = match mode {
+        Mode::All => physical,
+        Mode::Invalid => {
+            let invalid = lower >= upper || lower < physical.0 || upper > physical.1;
+            if !invalid {
+                return None;
+            }
+            physical
+        }
+        Mode::Clamp => {
+            let clamped = (lower.clamp(physical.0, physical.1), upper.clamp(physical.0, physical.1));
+            if clamped.0 >= clamped.1 {
+                physical
+            } else {
+                clamped
+            }
+        }
+    };
+    (next != current).then_some(next)
+}
+
+/// Derives lower/upper limits from the raw datatype range passed through each
+/// object's conversion method.
+#[tauri::command]
+pub fn recompute_limits(
+    selector: Option<LimitSelector>,
+    mode: Option<String>,
+    doc_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<AppState>,
+) -> Result<LimitReport, String> {
+    let selector = selector.unwrap_or_default();
+    let working_set = working_set_members(&app, selector.working_set.as_deref())?;
+    let mode = Mode::parse(mode.as_deref())?;
+    for kind in &selector.kinds {
+        if !LIMIT_KINDS.contains(&kind.as_str()) {
+            return Err(format!("Limits cannot be recomputed for {kind}"));
+        }
+    }
+    let pattern = selector
+        .pattern
+        .as_deref()
+        .filter(|p| !p.is_empty())
+        .map(|p| Regex::new(p).map_err(|e| format!("Invalid pattern '{p}': {e}")))
+        .transpose()?;
+    let selects = |kind: &str, name: &str| {
+        let kind_ok = selector.kinds.is_empty() || selector.kinds.iter().any(|k| k == kind);
+        let name_ok = (selector.names.is_empty() && pattern.is_none() && working_set.is_none())
+            || selector.names.iter().any(|n| n == name)
+            || pattern.as_ref().is_some_and(|p| p.is_match(name))
+            || working_set.as_ref().is_some_and(|set| set.iter().any(|(k, n)| k == kind && n == name));
+        kind_ok && name_ok
+    };
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("recompute_limits", doc_id.as_deref())?;
+    edit.touch_all();
+    let a2l = edit.a2l_mut();
+    let mut report = LimitReport {
+        changed: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    for module in a2l.project.module.iter_mut() {
+        if selector.module_name.as_deref().is_some_and(|name| module.get_name() != name) {
+            continue;
+        }
+        let module_id = module.get_name().to_string();
+
+        // (kind, name, raw datatype, conversion, current limits), resolved before mutating.
+        let mut targets = Vec::new();
+        for m in module.measurement.iter() {
+            if selects("Measurement", m.get_name()) {
+                let raw = Some(m.datatype);
+                targets.push(("Measurement", m.get_name().to_string(), raw, m.conversion.clone(), (m.lower_limit, m.upper_limit)));
+            }
+        }
+        for c in module.characteristic.iter() {
+            if selects("Characteristic", c.get_name()) {
+                let raw = module
+                    .record_layout
+                    .get(&c.deposit)
+                    .and_then(|layout| layout.fnc_values.as_ref())
+                    .map(|fnc| fnc.datatype);
+                targets.push(("Characteristic", c.get_name().to_string(), raw, c.conversion.clone(), (c.lower_limit, c.upper_limit)));
+            }
+        }
+        for a in module.axis_pts.iter() {
+            if selects("AxisPts", a.get_name()) {
+                let raw = module
+                    .record_layout
+                    .get(&a.deposit_record)
+                    .and_then(|layout| layout.axis_pts_x.as_ref())
+                    .map(|axis| axis.datatype);
+                targets.push(("AxisPts", a.get_name().to_string(), raw, a.conversion.clone(), (a.lower_limit, a.upper_limit)));
+            }
+        }
+
+        for (kind, name, datatype, conversion, current) in targets {
+            let physical = datatype
+                .ok_or_else(|| "record layout has no datatype".to_string())
+                .and_then(|datatype| physical_range(module, &conversion, datatype_range(&datatype)));
+            let physical = match physical {
+                Ok(physical) => physical,
+                Err(reason) => {
+                    report.skipped.push(SkippedLimit {
+                        module: module_id.clone(),
+                        kind: kind.to_string(),
+                        name,
+                        reason,
+                    });
+                    continue;
+                }
+            };
+            let Some((lower, upper)) = new_limits(mode, current, physical) else {
+                continue;
+            };
+            match kind {
+                "Measurement" => {
+                    if let Some(m) = module.measurement.get_mut(&name) {
+                        m.lower_limit = lower;
+                        m.upper_limit = upper;
+                    }
+                }
+                "Characteristic" => {
+                    if let Some(c) = module.characteristic.get_mut(&name) {
+                        c.lower_limit = lower;
+                        c.upper_limit = upper;
+                    }
+                }
+                _ => {
+                    if let Some(a) = module.axis_pts.get_mut(&name) {
+                        a.lower_limit = lower;
+                        a.upper_limit = upper;
+                    }
+                }
+            }
+            report.changed.push(LimitChange {
+                module: module_id.clone(),
+                kind: kind.to_string(),
+                name,
+                old_lower: current.0,
+                old_upper: current.1,
+                new_lower: lower,
+                new_upper: upper,
+            });
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+use crate::diagnostics::{self, Diagnostic};
+use crate::{build_metadata, load_a2l_file, A2lMetadata, AppState};
+
+const PROGRESS_EVENT: &str = "a2l-load-progress";
+const WARNING_EVENT: &str = "a2l-load-warning";
+const FINISHED_EVENT: &str = "a2l-load-finished";
+
+/// Read granularity; also bounds how long a cancellation can go unnoticed
+/// while the file is being read.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// Cancellation flags of running load jobs, keyed by job ID.
+#[derive(Default)]
+pub(crate) struct LoadJobs {
+    running: HashMap<String, Arc<AtomicBool>>,
+    next_id: u64,
+}
+
+#[derive(Serialize, Clone)]
+struct LoadProgress {
+    job_id: String,
+    stage: String,
+    bytes_read: u64,
+    total_bytes: u64,
+}
+
+#[derive(Serialize, Clone)]
+struct LoadWarning {
+    job_id: String,
+    diagnostic: Diagnostic,
+}
+
+#[derive(Serialize, Clone)]
+struct LoadFinished {
+    job_id: String,
+    doc_id: Option<String>,
+    metadata: Option<A2lMetadata>,
+    error: Option<String>,
+    cancelled: bool,
+}
+
+enum LoadOutcome {
+    Loaded(String, A2lMetadata),
+    Cancelled,
+}
+
+fn emit_progress(app: &tauri::AppHandle, job_id: &str, stage: &str, bytes_read: u64, total_bytes: u64) {
+    let _ = app.emit(
+        PROGRESS_EVENT,
+        LoadProgress {
+            job_id: job_id.to_string(),
+            stage: stage.to_string(),
+            bytes_read,
+            total_bytes,
+        },
+    );
+}
+
+/// Reads `path` in chunks, reporting progress and checking for cancellation
+/// between them. Returns the file size, or `None` if the job was cancelled.
+/// The parser reads the file again afterwards, then from the OS cache.
+fn read_with_progress(
+    app: &tauri::AppHandle,
+    job_id: &str,
+    path: &str,
+    cancelled: &AtomicBool,
+) -> Result<Option<u64>, String> {
+    let mut file = File::open(path).map_err(|e| e.to_string())?;
+    let total = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let mut chunk = vec![0u8; CHUNK_SIZE];
+    let mut read = 0;
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        let count = file.read(&mut chunk).map_err(|e| e.to_string())?;
+        if count == 0 {
+            break;
+        }
+        read += count as u64;
+        emit_progress(app, job_id, "reading", read, total);
+    }
+    Ok(Some(read))
+}
+
+fn run_load(
+    app: &tauri::AppHandle,
+    job_id: &str,
+    path: String,
+    normalization: Option<String>,
+    doc_id: Option<String>,
+    cancelled: &AtomicBool,
+) -> Result<LoadOutcome, String> {
+    let Some(total) = read_with_progress(app, job_id, &path, cancelled)? else {
+        return Ok(LoadOutcome::Cancelled);
+    };
+    // The parser loads the file itself so that `/include` directives resolve
+    // relative to it. It cannot be interrupted; cancellation takes effect
+    // afterwards.
+    emit_progress(app, job_id, "parsing", 0, total);
+    let (a2l, warnings) = load_a2l_file(&path, normalization)?;
+    emit_progress(app, job_id, "parsing", total, total);
+    if cancelled.load(Ordering::Relaxed) {
+        return Ok(LoadOutcome::Cancelled);
+    }
+    let diagnostics = diagnostics::from_warnings(&warnings);
+    for diagnostic in &diagnostics {
+        let _ = app.emit(
+            WARNING_EVENT,
+            LoadWarning {
+                job_id: job_id.to_string(),
+                diagnostic: diagnostic.clone(),
+            },
+        );
+    }
+
+    let metadata = build_metadata(&a2l, warnings.len());
+    let state = app.state::<AppState>();
+    let mut documents = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let id = documents.replace(doc_id.as_deref(), Some(path), a2l)?;
+    documents.document_mut(Some(&id))?.diagnostics = diagnostics;
+    Ok(LoadOutcome::Loaded(id, metadata))
+}
+
+/// Spawns a blocking task that reads, parses and stores the file. The result
+/// is delivered as a `a2l-load-finished` event carrying the returned job ID.
+pub(crate) fn start_load(
+    app: tauri::AppHandle,
+    state: &AppState,
+    path: String,
+    normalization: Option<String>,
+    doc_id: Option<String>,
+) -> Result<String, String> {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let job_id = {
+        let mut jobs = state.load_jobs.lock().map_err(|_| "State lock poisoned")?;
+        jobs.next_id += 1;
+        let job_id = format!("load-{}", jobs.next_id);
+        jobs.running.insert(job_id.clone(), cancelled.clone());
+        job_id
+    };
+
+    let task_job_id = job_id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let outcome = run_load(&app, &task_job_id, path, normalization, doc_id, &cancelled);
+        if let Ok(mut jobs) = app.state::<AppState>().load_jobs.lock() {
+            jobs.running.remove(&task_job_id);
+        }
+        let finished = match outcome {
+            Ok(LoadOutcome::Loaded(doc_id, metadata)) => LoadFinished {
+                job_id: task_job_id,
+                doc_id: Some(doc_id),
+                metadata: Some(metadata),
+                error: None,
+                cancelled: false,
+            },
+            Ok(LoadOutcome::Cancelled) => LoadFinished {
+                job_id: task_job_id,
+                doc_id: None,
+                metadata: None,
+                error: None,
+                cancelled: true,
+            },
+            Err(error) => LoadFinished {
+                job_id: task_job_id,
+                doc_id: None,
+                metadata: None,
+                error: Some(error),
+                cancelled: false,
+            },
+        };
+        let _ = app.emit(FINISHED_EVENT, finished);
+    });
+    Ok(job_id)
+}
+
+#[tauri::command]
+pub fn cancel_load(job_id: String, state: tauri::State<AppState>) -> Result<(), String> {
+    let jobs = state.load_jobs.lock().map_err(|_| "State lock poisoned")?;
+    let flag = jobs
+        .running
+        .get(&job_id)
+        .ok_or_else(|| format!("Load job '{job_id}' is not running"))?;
+    flag.store(true, Ordering::Relaxed);
+    Ok(())
+}
@@ -0,0 +1,214 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+
+use a2lfile::A2lObjectName;
+use serde::Serialize;
+
+use crate::AppState;
+
+/// The HD block follows the 64-byte identification block.
+const HD_OFFSET: u64 = 64;
+/// CN types of master channels (time, angle, ...), which have no measurement.
+const MASTER_CHANNEL_TYPES: &[u8] = &[2, 3];
+/// Guards against link cycles in corrupt files.
+const MAX_BLOCKS: usize = 1_000_000;
+
+#[derive(Serialize)]
+pub struct MissingMeasurement {
+    module: String,
+    name: String,
+}
+
+#[derive(Serialize)]
+pub struct MdfCrossCheck {
+    version: String,
+    channel_count: usize,
+    matched: usize,
+    /// Channels recorded in the file without a MEASUREMENT of that name.
+    channels_without_measurement: Vec<String>,
+    /// MEASUREMENTs that were not recorded.
+    measurements_without_channel: Vec<MissingMeasurement>,
+}
+
+/// Header and links of one MF4 block; `data` is the offset of its data section.
+struct Block {
+    id: [u8; 4],
+    links: Vec<u64>,
+    data: u64,
+}
+
+struct Mf4Reader {
+    file: BufReader<File>,
+    blocks_read: usize,
+}
+
+impl Mf4Reader {
+    fn read_u64(&mut self) -> Result<u64, String> {
+        let mut bytes = [0u8; 8];
+        self.file.read_exact(&mut bytes).map_err(|e| e.to_string())?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn block(&mut self, offset: u64) -> Result<Block, String> {
+        self.blocks_read += 1;
+        if self.blocks_read > MAX_BLOCKS {
+            return Err("MF4 block structure is cyclic or too large".to_string());
+        }
+        self.file.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+        let mut header = [0u8; 8];
+        self.file.read_exact(&mut header).map_err(|e| e.to_string())?;
+        if &header[..2] != b"##" {
+            return Err(format!("No MF4 block at 0x{:X}", offset));
+        }
+        let _length = self.read_u64()?;
+        let link_count = self.read_u64()?;
+        let links = (0..link_count).map(|_| self.read_u64()).collect::<Result<Vec<_>, _>>()?;
+        Ok(Block {
+            id: [header[0], header[1], header[2], header[3]],
+            data: offset + 24 + link_count * 8,
+            links,
+        })
+    }
+
+    fn link(block: &Block, index: usize) -> u64 {
+        block.links.get(index).copied().unwrap_or(0)
+    }
+
+    /// Text of a TX block, or of an MD block with the XML left in place.
+    fn text(&mut self, offset: u64) -> Result<String, String> {
+        if offset == 0 {
+            return Ok(String::new());
+        }
+        self.file.seek(SeekFrom::Start(offset + 8)).map_err(|e| e.to_string())?;
+        let length = self.read_u64()?;
+        let link_count = self.read_u64()?;
+        let size = length.saturating_sub(24 + link_count * 8);
+        self.file
+            .seek(SeekFrom::Start(offset + 24 + link_count * 8))
+            .map_err(|e| e.to_string())?;
+        let mut bytes = Vec::with_capacity(size as usize);
+        (&mut self.file).take(size).read_to_end(&mut bytes).map_err(|e| e.to_string())?;
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        Ok(String::from_utf8_lossy(&bytes[..end]).trim().to_string())
+    }
+
+    fn channel_type(&mut self, block: &Block) -> Result<u8, String> {
+        self.file.seek(SeekFrom::Start(block.data)).map_err(|e| e.to_string())?;
+        let mut cn_type = [0u8; 1];
+        self.file.read_exact(&mut cn_type).map_err(|e| e.to_string())?;
+        Ok(cn_type[0])
+    }
+
+    /// Names of the channels in the CN list starting at `offset`, including
+    /// members of structure channels.
+    fn channels(&mut self, mut offset: u64, names: &mut Vec<String>) -> Result<(), String> {
+        while offset != 0 {
+            let cn = self.block(offset)?;
+            if &cn.id != b"##CN" {
+                return Err(format!("Expected CN block at 0x{:X}", offset));
+            }
+            if !MASTER_CHANNEL_TYPES.contains(&self.channel_type(&cn)?) {
+                let name = self.text(Self::link(&cn, 2))?;
+                if !name.is_empty() {
+                    names.push(name);
+                }
+            }
+            let composition = Self::link(&cn, 1);
+            if composition != 0 && &self.block(composition)?.id == b"##CN" {
+                self.channels(composition, names)?;
+            }
+            offset = Self::link(&cn, 0);
+        }
+        Ok(())
+    }
+}
+
+/// Format version and channel names of an MDF 4 file, in file order.
+fn read_mf4_channels(path: &str) -> Result<(String, Vec<String>), String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let mut reader = Mf4Reader {
+        file: BufReader::new(file),
+        blocks_read: 0,
+    };
+    let mut id = [0u8; 16];
+    reader.file.read_exact(&mut id).map_err(|_| "File is too short for MDF".to_string())?;
+    if !id.starts_with(b"MDF     ") && !id.starts_with(b"UnFinMF ") {
+        return Err(format!("{path} is not an MDF file"));
+    }
+    let version = String::from_utf8_lossy(&id[8..16]).trim().to_string();
+    if !version.starts_with('4') {
+        return Err(format!("MDF version {version} is not supported; only MF4 files can be checked"));
+    }
+
+    let hd = reader.block(HD_OFFSET)?;
+    if &hd.id != b"##HD" {
+        return Err("MF4 header block not found".to_string());
+    }
+    let mut names = Vec::new();
+    let mut dg_offset = Mf4Reader::link(&hd, 0);
+    while dg_offset != 0 {
+        let dg = reader.block(dg_offset)?;
+        let mut cg_offset = Mf4Reader::link(&dg, 1);
+        while cg_offset != 0 {
+            let cg = reader.block(cg_offset)?;
+            reader.channels(Mf4Reader::link(&cg, 1), &mut names)?;
+            cg_offset = Mf4Reader::link(&cg, 0);
+        }
+        dg_offset = Mf4Reader::link(&dg, 0);
+    }
+    Ok((version, names))
+}
+
+/// Channel name without the device or source suffix that recording tools
+/// append after a backslash (`Name\ETK:1`).
+fn base_name(channel: &str) -> &str {
+    channel.split('\\').next().unwrap_or(channel)
+}
+
+/// Compares the channels recorded in an MF4 file with the MEASUREMENTs of
+/// the document (or of `module_name`). Master channels are ignored.
+#[tauri::command]
+pub fn cross_check_mdf(
+    path: String,
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<MdfCrossCheck, String> {
+    let (version, channels) = read_mf4_channels(&path)?;
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+
+    let mut measurements: Vec<(&str, &str)> = Vec::new();
+    for module in a2l.project.module.iter() {
+        if module_name.as_deref().is_some_and(|name| module.get_name() != name) {
+            continue;
+        }
+        measurements.extend(module.measurement.iter().map(|m| (module.get_name(), m.get_name())));
+    }
+    if let (Some(name), true) = (&module_name, measurements.is_empty()) {
+        if !a2l.project.module.iter().any(|module| module.get_name() == name) {
+            return Err(format!("Module {name} not found"));
+        }
+    }
+
+    let known: HashSet<&str> = measurements.iter().map(|(_, name)| *name).collect();
+    let recorded: HashSet<&str> = channels.iter().map(|channel| base_name(channel)).collect();
+    let channels_without_measurement: BTreeSet<&String> =
+        channels.iter().filter(|channel| !known.contains(base_name(channel))).collect();
+    let measurements_without_channel = measurements
+        .iter()
+        .filter(|(_, name)| !recorded.contains(name))
+        .map(|(module, name)| MissingMeasurement {
+            module: module.to_string(),
+            name: name.to_string(),
+        })
+        .collect();
+    Ok(MdfCrossCheck {
+        version,
+        channel_count: channels.len(),
+        matched: channels.iter().filter(|channel| known.contains(base_name(channel))).count(),
+        channels_without_measurement: channels_without_measurement.into_iter().cloned().collect(),
+        measurements_without_channel,
+    })
+}
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{find_module, find_module_mut, AppState};
+
+/// Alignments are given in bytes; `None` leaves the ASAP2 default in effect.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ModCommonData {
+    /// False if the module has no MOD_COMMON yet; ignored on update.
+    present: bool,
+    comment: String,
+    byte_order: Option<String>,
+    data_size: Option<u16>,
+    deposit: Option<String>,
+    s_rec_layout: Option<String>,
+    alignment_byte: Option<u16>,
+    alignment_word: Option<u16>,
+    alignment_long: Option<u16>,
+    alignment_int64: Option<u16>,
+    alignment_float16_ieee: Option<u16>,
+    alignment_float32_ieee: Option<u16>,
+    alignment_float64_ieee: Option<u16>,
+}
+
+pub(crate) fn byte_order_to_string(byte_order: &a2lfile::ByteOrderEnum) -> String {
+    match byte_order {
+        a2lfile::ByteOrderEnum::LittleEndian => "LITTLE_ENDIAN",
+        a2lfile::ByteOrderEnum::BigEndian => "BIG_ENDIAN",
+        a2lfile::ByteOrderEnum::MsbFirst => "MSB_FIRST",
+        a2lfile::ByteOrderEnum::MsbLast => "MSB_LAST",
+        a2lfile::ByteOrderEnum::MsbFirstMswLast => "MSB_FIRST_MSW_LAST",
+        a2lfile::ByteOrderEnum::MsbLastMswFirst => "MSB_LAST_MSW_FIRST",
+    }
+    .to_string()
+}
+
+pub(crate) fn string_to_byte_order(s: &str) -> Option<a2lfile::ByteOrderEnum> {
+    match s.to_uppercase().as_str() {
+        "LITTLE_ENDIAN" => Some(a2lfile::ByteOrderEnum::LittleEndian),
+        "BIG_ENDIAN" => Some(a2lfile::ByteOrderEnum::BigEndian),
+        "MSB_FIRST" => Some(a2lfile::ByteOrderEnum::MsbFirst),
+        "MSB_LAST" => Some(a2lfile::ByteOrderEnum::MsbLast),
+        "MSB_FIRST_MSW_LAST" => Some(a2lfile::ByteOrderEnum::MsbFirstMswLast),
+        "MSB_LAST_MSW_FIRST" => Some(a2lfile::ByteOrderEnum::MsbLastMswFirst),
+        _ => None,
+    }
+}
+
+fn deposit_to_string(mode: &a2lfile::DepositMode) -> String {
+    match mode {
+        a2lfile::DepositMode::Absolute => "ABSOLUTE",
+        a2lfile::DepositMode::Difference => "DIFFERENCE",
+    }
+    .to_string()
+}
+
+fn string_to_deposit(s: &str) -> Option<a2lfile::DepositMode> {
+    match s.to_uppercase().as_str() {
+        "ABSOLUTE" => Some(a2lfile::DepositMode::Absolute),
+        "DIFFERENCE" => Some(a2lfile::DepositMode::Difference),
+        _ => None,
+    }
+}
+
+fn valid_alignment(name: &str, value: Option<u16>) -> Result<Option<u16>, String> {
+    match value {
+        Some(value) if !value.is_power_of_two() => Err(format!("{name} must be a power of two, got {value}")),
+        _ => Ok(value),
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+#[tauri::command]
+pub fn get_mod_common(
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<ModCommonData, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let module = find_module(a2l, module_name.as_deref())?;
+    let Some(mod_common) = module.mod_common.as_ref() else {
+        return Ok(ModCommonData::default());
+    };
+
+    Ok(ModCommonData {
+        present: true,
+        comment: mod_common.comment.clone(),
+        byte_order: mod_common.byte_order.as_ref().map(|bo| byte_order_to_string(&bo.byte_order)),
+        data_size: mod_common.data_size.as_ref().map(|ds| ds.size),
+        deposit: mod_common.deposit.as_ref().map(|d| deposit_to_string(&d.mode)),
+        s_rec_layout: mod_common.s_rec_layout.as_ref().map(|l| l.name.clone()),
+        alignment_byte: mod_common.alignment_byte.as_ref().map(|a| a.alignment_border),
+        alignment_word: mod_common.alignment_word.as_ref().map(|a| a.alignment_border),
+        alignment_long: mod_common.alignment_long.as_ref().map(|a| a.alignment_border),
+        alignment_int64: mod_common.alignment_int64.as_ref().map(|a| a.alignment_border),
+        alignment_float16_ieee: mod_common.alignment_float16_ieee.as_ref().map(|a| a.alignment_border),
+        alignment_float32_ieee: mod_common.alignment_float32_ieee.as_ref().map(|a| a.alignment_border),
+        alignment_float64_ieee: mod_common.alignment_float64_ieee.as_ref().map(|a| a.alignment_border),
+    })
+}
+
+/// Replaces the MOD_COMMON of the module, creating it if missing.
+#[tauri::command]
+pub fn update_mod_common(
+    module_name: Option<String>,
+    data: ModCommonData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let byte_order = non_empty(data.byte_order)
+        .map(|bo| string_to_byte_order(&bo).ok_or_else(|| format!("Invalid byte order: {bo}")))
+        .transpose()?;
+    let deposit = non_empty(data.deposit)
+        .map(|d| string_to_deposit(&d).ok_or_else(|| format!("Invalid deposit mode: {d}")))
+        .transpose()?;
+    let s_rec_layout = non_empty(data.s_rec_layout);
+    let alignment_byte = valid_alignment("ALIGNMENT_BYTE", data.alignment_byte)?;
+    let alignment_word = valid_alignment("ALIGNMENT_WORD", data.alignment_word)?;
+    let alignment_long = valid_alignment("ALIGNMENT_LONG", data.alignment_long)?;
+    let alignment_int64 = valid_alignment("ALIGNMENT_INT64", data.alignment_int64)?;
+    let alignment_float16_ieee = valid_alignment("ALIGNMENT_FLOAT16_IEEE", data.alignment_float16_ieee)?;
+    let alignment_float32_ieee = valid_alignment("ALIGNMENT_FLOAT32_IEEE", data.alignment_float32_ieee)?;
+    let alignment_float64_ieee = valid_alignment("ALIGNMENT_FLOAT64_IEEE", data.alignment_float64_ieee)?;
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("update_mod_common", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_module(&module_id);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+    if let Some(layout) = &s_rec_layout {
+        if module.record_layout.get(layout).is_none() {
+            return Err(format!("Record layout '{layout}' not found"));
+        }
+    }
+
+    let mod_common = module
+        .mod_common
+        .get_or_insert_with(|| a2lfile::ModCommon::new(String::new()));
+    mod_common.comment = data.comment;
+    mod_common.byte_order = byte_order.map(a2lfile::ByteOrder::new);
+    mod_common.data_size = data.data_size.map(a2lfile::DataSize::new);
+    mod_common.deposit = deposit.map(a2lfile::Deposit::new);
+    mod_common.s_rec_layout = s_rec_layout.map(a2lfile::SRecLayout::new);
+    mod_common.alignment_byte = alignment_byte.map(a2lfile::AlignmentByte::new);
+    mod_common.alignment_word = alignment_word.map(a2lfile::AlignmentWord::new);
+    mod_common.alignment_long = alignment_long.map(a2lfile::AlignmentLong::new);
+    mod_common.alignment_int64 = alignment_int64.map(a2lfile::AlignmentInt64::new);
+    mod_common.alignment_float16_ieee = alignment_float16_ieee.map(a2lfile::AlignmentFloat16Ieee::new);
+    mod_common.alignment_float32_ieee = alignment_float32_ieee.map(a2lfile::AlignmentFloat32Ieee::new);
+    mod_common.alignment_float64_ieee = alignment_float64_ieee.map(a2lfile::AlignmentFloat64Ieee::new);
+    Ok(())
+}
@@ -0,0 +1,452 @@
+use a2lfile::{A2lObjectName, A2lObjectNameSetter};
+use serde::{Deserialize, Serialize};
+
+use crate::{find_module, find_module_mut, parse_hex_address, AppState};
+
+#[derive(Serialize, Deserialize)]
+pub struct MemorySegmentData {
+    name: String,
+    long_identifier: String,
+    prg_type: String,
+    memory_type: String,
+    attribute: String,
+    address: String,
+    size: u32,
+    offsets: [i32; 5],
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MemoryLayoutData {
+    prg_type: String,
+    address: String,
+    size: u32,
+    offsets: [i32; 5],
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SystemConstantData {
+    name: String,
+    value: String,
+}
+
+#[derive(Serialize)]
+pub struct ModParData {
+    comment: String,
+    epk: Option<String>,
+    addr_epk: Vec<String>,
+    memory_segments: Vec<MemorySegmentData>,
+    memory_layouts: Vec<MemoryLayoutData>,
+    system_constants: Vec<SystemConstantData>,
+}
+
+fn prg_type_to_string(prg_type: &a2lfile::PrgType) -> String {
+    match prg_type {
+        a2lfile::PrgType::CalibrationVariables => "CALIBRATION_VARIABLES",
+        a2lfile::PrgType::Code => "CODE",
+        a2lfile::PrgType::Data => "DATA",
+        a2lfile::PrgType::ExcludeFromFlash => "EXCLUDE_FROM_FLASH",
+        a2lfile::PrgType::OfflineData => "OFFLINE_DATA",
+        a2lfile::PrgType::Reserved => "RESERVED",
+        a2lfile::PrgType::Seram => "SERAM",
+        a2lfile::PrgType::Variables => "VARIABLES",
+    }
+    .to_string()
+}
+
+fn string_to_prg_type(s: &str) -> Option<a2lfile::PrgType> {
+    match s.to_uppercase().as_str() {
+        "CALIBRATION_VARIABLES" => Some(a2lfile::PrgType::CalibrationVariables),
+        "CODE" => Some(a2lfile::PrgType::Code),
+        "DATA" => Some(a2lfile::PrgType::Data),
+        "EXCLUDE_FROM_FLASH" => Some(a2lfile::PrgType::ExcludeFromFlash),
+        "OFFLINE_DATA" => Some(a2lfile::PrgType::OfflineData),
+        "RESERVED" => Some(a2lfile::PrgType::Reserved),
+        "SERAM" => Some(a2lfile::PrgType::Seram),
+        "VARIABLES" => Some(a2lfile::PrgType::Variables),
+        _ => None,
+    }
+}
+
+fn memory_type_to_string(memory_type: &a2lfile::MemoryType) -> String {
+    match memory_type {
+        a2lfile::MemoryType::Eeprom => "EEPROM",
+        a2lfile::MemoryType::Eprom => "EPROM",
+        a2lfile::MemoryType::Flash => "FLASH",
+        a2lfile::MemoryType::Ram => "RAM",
+        a2lfile::MemoryType::Rom => "ROM",
+        a2lfile::MemoryType::Register => "REGISTER",
+        a2lfile::MemoryType::NotInEcu => "NOT_IN_ECU",
+    }
+    .to_string()
+}
+
+fn string_to_memory_type(s: &str) -> Option<a2lfile::MemoryType> {
+    match s.to_uppercase().as_str() {
+        "EEPROM" => Some(a2lfile::MemoryType::Eeprom),
+        "EPROM" => Some(a2lfile::MemoryType::Eprom),
+        "FLASH" => Some(a2lfile::MemoryType::Flash),
+        "RAM" => Some(a2lfile::MemoryType::Ram),
+        "ROM" => Some(a2lfile::MemoryType::Rom),
+        "REGISTER" => Some(a2lfile::MemoryType::Register),
+        "NOT_IN_ECU" => Some(a2lfile::MemoryType::NotInEcu),
+        _ => None,
+    }
+}
+
+fn memory_attribute_to_string(attribute: &a2lfile::MemoryAttribute) -> String {
+    match attribute {
+        a2lfile::MemoryAttribute::Intern => "INTERN",
+        a2lfile::MemoryAttribute::Extern => "EXTERN",
+    }
+    .to_string()
+}
+
+fn string_to_memory_attribute(s: &str) -> Option<a2lfile::MemoryAttribute> {
+    match s.to_uppercase().as_str() {
+        "INTERN" => Some(a2lfile::MemoryAttribute::Intern),
+        "EXTERN" => Some(a2lfile::MemoryAttribute::Extern),
+        _ => None,
+    }
+}
+
+fn prog_type_to_string(prog_type: &a2lfile::ProgType) -> String {
+    match prog_type {
+        a2lfile::ProgType::PrgCode => "PRG_CODE",
+        a2lfile::ProgType::PrgData => "PRG_DATA",
+        a2lfile::ProgType::PrgReserved => "PRG_RESERVED",
+    }
+    .to_string()
+}
+
+fn string_to_prog_type(s: &str) -> Option<a2lfile::ProgType> {
+    match s.to_uppercase().as_str() {
+        "PRG_CODE" => Some(a2lfile::ProgType::PrgCode),
+        "PRG_DATA" => Some(a2lfile::ProgType::PrgData),
+        "PRG_RESERVED" => Some(a2lfile::ProgType::PrgReserved),
+        _ => None,
+    }
+}
+
+fn mod_par_mut(module: &mut a2lfile::Module) -> &mut a2lfile::ModPar {
+    module
+        .mod_par
+        .get_or_insert_with(|| a2lfile::ModPar::new(String::new()))
+}
+
+/// MOD_PAR of `module` for edits of existing entries, which must not create
+/// an empty block when the entry is missing.
+fn existing_mod_par_mut(module: &mut a2lfile::Module) -> Result<&mut a2lfile::ModPar, String> {
+    let name = module.get_name().to_string();
+    module
+        .mod_par
+        .as_mut()
+        .ok_or_else(|| format!("Module {name} has no MOD_PAR"))
+}
+
+#[tauri::command]
+pub fn get_mod_par(module_name: Option<String>, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<ModParData, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let module = find_module(a2l, module_name.as_deref())?;
+    let Some(mod_par) = module.mod_par.as_ref() else {
+        return Ok(ModParData {
+            comment: String::new(),
+            epk: None,
+            addr_epk: Vec::new(),
+            memory_segments: Vec::new(),
+            memory_layouts: Vec::new(),
+            system_constants: Vec::new(),
+        });
+    };
+
+    Ok(ModParData {
+        comment: mod_par.comment.clone(),
+        epk: mod_par.epk.as_ref().map(|epk| epk.identifier.clone()),
+        addr_epk: mod_par
+            .addr_epk
+            .iter()
+            .map(|addr| format!("0x{:X}", addr.address))
+            .collect(),
+        memory_segments: mod_par
+            .memory_segment
+            .iter()
+            .map(|segment| MemorySegmentData {
+                name: segment.get_name().to_string(),
+                long_identifier: segment.long_identifier.clone(),
+                prg_type: prg_type_to_string(&segment.prg_type),
+                memory_type: memory_type_to_string(&segment.memory_type),
+                attribute: memory_attribute_to_string(&segment.attribute),
+                address: format!("0x{:X}", segment.address),
+                size: segment.size,
+                offsets: [
+                    segment.offset_1,
+                    segment.offset_2,
+                    segment.offset_3,
+                    segment.offset_4,
+                    segment.offset_5,
+                ],
+            })
+            .collect(),
+        memory_layouts: mod_par
+            .memory_layout
+            .iter()
+            .map(|layout| MemoryLayoutData {
+                prg_type: prog_type_to_string(&layout.prg_type),
+                address: format!("0x{:X}", layout.address),
+                size: layout.size,
+                offsets: [
+                    layout.offset_1,
+                    layout.offset_2,
+                    layout.offset_3,
+                    layout.offset_4,
+                    layout.offset_5,
+                ],
+            })
+            .collect(),
+        system_constants: mod_par
+            .system_constant
+            .iter()
+            .map(|constant| SystemConstantData {
+                name: constant.get_name().to_string(),
+                value: constant.value.clone(),
+            })
+            .collect(),
+    })
+}
+
+#[tauri::command]
+pub fn update_mod_par_identification(
+    module_name: Option<String>,
+    comment: String,
+    epk: Option<String>,
+    addr_epk: Vec<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let addresses = addr_epk
+        .iter()
+        .map(|addr| parse_hex_address(addr))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("update_mod_par_identification", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_module(&module_id);
+    let a2l = edit.a2l_mut();
+    let mod_par = mod_par_mut(find_module_mut(a2l, module_name.as_deref())?);
+
+    mod_par.comment = comment;
+    mod_par.epk = epk
+        .map(|epk| epk.trim().to_string())
+        .filter(|epk| !epk.is_empty())
+        .map(a2lfile::Epk::new);
+    mod_par.addr_epk = addresses.into_iter().map(a2lfile::AddrEpk::new).collect();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn upsert_memory_segment(
+    module_name: Option<String>,
+    original_name: Option<String>,
+    data: MemorySegmentData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let prg_type = string_to_prg_type(&data.prg_type)
+        .ok_or_else(|| format!("Invalid program type: {}", data.prg_type))?;
+    let memory_type = string_to_memory_type(&data.memory_type)
+        .ok_or_else(|| format!("Invalid memory type: {}", data.memory_type))?;
+    let attribute = string_to_memory_attribute(&data.attribute)
+        .ok_or_else(|| format!("Invalid memory attribute: {}", data.attribute))?;
+    let address = parse_hex_address(&data.address)?;
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("upsert_memory_segment", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_module(&module_id);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+    let mod_par = match original_name {
+        Some(_) => existing_mod_par_mut(module)?,
+        None => mod_par_mut(module),
+    };
+
+    let lookup = original_name.as_deref().unwrap_or(&data.name);
+    if original_name.is_some() && lookup != data.name && mod_par.memory_segment.iter().any(|s| s.get_name() == data.name) {
+        return Err(format!("Memory segment '{}' already exists", data.name));
+    }
+
+    if let Some(segment) = mod_par.memory_segment.iter_mut().find(|s| s.get_name() == lookup) {
+        segment.set_name(data.name);
+        segment.long_identifier = data.long_identifier;
+        segment.prg_type = prg_type;
+        segment.memory_type = memory_type;
+        segment.attribute = attribute;
+        segment.address = address;
+        segment.size = data.size;
+        [
+            segment.offset_1,
+            segment.offset_2,
+            segment.offset_3,
+            segment.offset_4,
+            segment.offset_5,
+        ] = data.offsets;
+        return Ok(());
+    }
+    if original_name.is_some() {
+        return Err(format!("Memory segment '{}' not found", lookup));
+    }
+
+    let [offset_1, offset_2, offset_3, offset_4, offset_5] = data.offsets;
+    let segment = a2lfile::MemorySegment::new(
+        data.name,
+        data.long_identifier,
+        prg_type,
+        memory_type,
+        attribute,
+        address,
+        data.size,
+        offset_1,
+        offset_2,
+        offset_3,
+        offset_4,
+        offset_5,
+    );
+    mod_par.memory_segment.push(segment);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_memory_segment(
+    module_name: Option<String>,
+    name: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("delete_memory_segment", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_module(&module_id);
+    let a2l = edit.a2l_mut();
+    let mod_par = existing_mod_par_mut(find_module_mut(a2l, module_name.as_deref())?)?;
+
+    let before = mod_par.memory_segment.len();
+    mod_par.memory_segment.retain(|segment| segment.get_name() != name);
+    if mod_par.memory_segment.len() == before {
+        return Err(format!("Memory segment '{}' not found", name));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn upsert_memory_layout(
+    module_name: Option<String>,
+    index: Option<usize>,
+    data: MemoryLayoutData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let prg_type = string_to_prog_type(&data.prg_type)
+        .ok_or_else(|| format!("Invalid program type: {}", data.prg_type))?;
+    let address = parse_hex_address(&data.address)?;
+    let [offset_1, offset_2, offset_3, offset_4, offset_5] = data.offsets;
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("upsert_memory_layout", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_module(&module_id);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+    let mod_par = match index {
+        Some(_) => existing_mod_par_mut(module)?,
+        None => mod_par_mut(module),
+    };
+
+    match index {
+        Some(index) => {
+            let layout = mod_par
+                .memory_layout
+                .get_mut(index)
+                .ok_or_else(|| format!("Memory layout {index} not found"))?;
+            layout.prg_type = prg_type;
+            layout.address = address;
+            layout.size = data.size;
+            layout.offset_1 = offset_1;
+            layout.offset_2 = offset_2;
+            layout.offset_3 = offset_3;
+            layout.offset_4 = offset_4;
+            layout.offset_5 = offset_5;
+        }
+        None => mod_par.memory_layout.push(a2lfile::MemoryLayout::new(
+            prg_type, address, data.size, offset_1, offset_2, offset_3, offset_4, offset_5,
+        )),
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_memory_layout(
+    module_name: Option<String>,
+    index: usize,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("delete_memory_layout", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_module(&module_id);
+    let a2l = edit.a2l_mut();
+    let mod_par = existing_mod_par_mut(find_module_mut(a2l, module_name.as_deref())?)?;
+
+    if index >= mod_par.memory_layout.len() {
+        return Err(format!("Memory layout {index} not found"));
+    }
+    mod_par.memory_layout.remove(index);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_system_constant(
+    module_name: Option<String>,
+    data: SystemConstantData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("set_system_constant", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_module(&module_id);
+    let a2l = edit.a2l_mut();
+    let mod_par = mod_par_mut(find_module_mut(a2l, module_name.as_deref())?);
+
+    if let Some(constant) = mod_par.system_constant.iter_mut().find(|c| c.get_name() == data.name) {
+        constant.value = data.value;
+    } else {
+        mod_par
+            .system_constant
+            .push(a2lfile::SystemConstant::new(data.name, data.value));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_system_constant(
+    module_name: Option<String>,
+    name: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("delete_system_constant", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_module(&module_id);
+    let a2l = edit.a2l_mut();
+    let mod_par = existing_mod_par_mut(find_module_mut(a2l, module_name.as_deref())?)?;
+
+    let before = mod_par.system_constant.len();
+    mod_par.system_constant.retain(|constant| constant.get_name() != name);
+    if mod_par.system_constant.len() == before {
+        return Err(format!("System constant '{}' not found", name));
+    }
+    Ok(())
+}
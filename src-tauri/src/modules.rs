@@ -0,0 +1,182 @@
+use a2lfile::{A2lObjectName, A2lObjectNameSetter};
+use serde::Serialize;
+
+use crate::cleanup::remove_object;
+use crate::entity_copy::{dependency_closure, transfer, DEPENDENCY_TARGETS};
+use crate::references::{for_each_reference, name_taken, object_exists, target_candidates, OBJECT_KINDS};
+use crate::{build_metadata, find_module, find_module_mut, A2lMetadata, AppState};
+
+#[derive(Serialize)]
+pub struct MoveResult {
+    moved: Vec<String>,
+    /// Referenced objects the target module lacked; they were copied and stay
+    /// in the source module as well.
+    copied_dependencies: Vec<String>,
+    /// Objects in the source module that still refer to moved objects.
+    dangling_references: Vec<String>,
+}
+
+#[tauri::command]
+pub fn create_module(
+    name: String,
+    long_identifier: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<A2lMetadata, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Module name must not be empty".to_string());
+    }
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("create_module", doc_id.as_deref())?;
+    if edit.a2l().project.module.iter().any(|m| m.get_name() == name) {
+        return Err(format!("Module {} already exists", name));
+    }
+    edit.touch_module(&name);
+    let a2l = edit.a2l_mut();
+    a2l.project.module.push(a2lfile::Module::new(name, long_identifier));
+    Ok(build_metadata(a2l, 0))
+}
+
+/// Renames a module. A2L references objects by name only, so no other part of
+/// the project refers to the module.
+#[tauri::command]
+pub fn rename_module(
+    name: String,
+    new_name: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<A2lMetadata, String> {
+    let new_name = new_name.trim().to_string();
+    if new_name.is_empty() {
+        return Err("Module name must not be empty".to_string());
+    }
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("rename_module", doc_id.as_deref())?;
+    let modules = &edit.a2l().project.module;
+    if modules.iter().all(|m| m.get_name() != name) {
+        return Err(format!("Module {} not found", name));
+    }
+    if new_name != name && modules.iter().any(|m| m.get_name() == new_name) {
+        return Err(format!("Module {} already exists", new_name));
+    }
+    edit.touch_module_objects(&name);
+    edit.touch_module_objects(&new_name);
+    let a2l = edit.a2l_mut();
+    if let Some(module) = a2l.project.module.iter_mut().find(|m| m.get_name() == name) {
+        module.set_name(new_name);
+    }
+    Ok(build_metadata(a2l, 0))
+}
+
+/// Deletes a module with all its objects. The last module cannot be deleted.
+#[tauri::command]
+pub fn delete_module(name: String, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<A2lMetadata, String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("delete_module", doc_id.as_deref())?;
+    let modules = &edit.a2l().project.module;
+    if modules.iter().all(|m| m.get_name() != name) {
+        return Err(format!("Module {} not found", name));
+    }
+    if modules.len() == 1 {
+        return Err("A project needs at least one module".to_string());
+    }
+    edit.touch_module_objects(&name);
+    let a2l = edit.a2l_mut();
+    a2l.project.module.retain(|m| m.get_name() != name);
+    Ok(build_metadata(a2l, 0))
+}
+
+/// Whether `kind`/`name` has the same content in both modules; the file
+/// layout is ignored.
+fn same_object(a: &a2lfile::Module, b: &a2lfile::Module, kind: &str, name: &str) -> bool {
+    let mut left = a2lfile::Module::new(String::new(), String::new());
+    let mut right = a2lfile::Module::new(String::new(), String::new());
+    transfer(a, &mut left, kind, name) && transfer(b, &mut right, kind, name) && left == right
+}
+
+/// Moves objects of `kind` to `target_module`. Conversions, record layouts and
+/// other referenced objects missing in the target are copied along; ones the
+/// target already has must be identical. Nothing is moved if any name is
+/// missing in the source, already taken in the target or a dependency
+/// conflicts.
+#[tauri::command]
+pub fn move_entities_to_module(
+    kind: String,
+    names: Vec<String>,
+    source_module: Option<String>,
+    target_module: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<MoveResult, String> {
+    if !OBJECT_KINDS.contains(&kind.as_str()) {
+        return Err(format!("Objects of kind {kind} cannot be moved"));
+    }
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("move_entities_to_module", doc_id.as_deref())?;
+
+    // Everything is staged on copies first, so a failing check leaves the
+    // document untouched.
+    let a2l = edit.a2l();
+    let source = find_module(a2l, source_module.as_deref())?;
+    let source_name = source.get_name().to_string();
+    if source_name == target_module {
+        return Err("Source and target module are the same".to_string());
+    }
+    let target = find_module(a2l, Some(&target_module))?;
+    if let Some(name) = names.iter().find(|name| !object_exists(source, &kind, name)) {
+        return Err(format!("{kind} '{name}' not found in module {source_name}"));
+    }
+    if let Some(name) = names.iter().find(|name| name_taken(target, &kind, name)) {
+        return Err(format!("{kind} '{name}' already exists in module {target_module}"));
+    }
+
+    let seeds: Vec<(String, String)> = names.iter().map(|name| (kind.clone(), name.clone())).collect();
+    let mut staging = a2lfile::Module::new(source_name.clone(), String::new());
+    let mut staged = Vec::new();
+    for (item_kind, name, dependency) in dependency_closure(source, &seeds, DEPENDENCY_TARGETS) {
+        if dependency && object_exists(target, &item_kind, &name) {
+            if !same_object(source, target, &item_kind, &name) {
+                return Err(format!("{item_kind} '{name}' differs between module {source_name} and {target_module}"));
+            }
+            continue;
+        }
+        if dependency && name_taken(target, &item_kind, &name) {
+            return Err(format!("Name '{name}' is already used in module {target_module}"));
+        }
+        if transfer(source, &mut staging, &item_kind, &name) {
+            staged.push((item_kind, name, dependency));
+        }
+    }
+    let mut remaining = source.clone();
+    for name in &names {
+        remove_object(&mut remaining, &kind, name);
+        if object_exists(&remaining, &kind, name) {
+            return Err(format!("{kind} '{name}' cannot be removed from module {source_name}"));
+        }
+    }
+
+    let mut result = MoveResult {
+        moved: names.clone(),
+        copied_dependencies: Vec::new(),
+        dangling_references: Vec::new(),
+    };
+    for_each_reference(&remaining, &mut |ref_kind, ref_name, field, target_kind, target| {
+        if target_candidates(target_kind).contains(&kind.as_str()) && names.iter().any(|name| name == target) {
+            result.dangling_references.push(format!("{ref_kind} '{ref_name}' ({field}) -> {target}"));
+        }
+    });
+
+    edit.touch_module_objects(&source_name);
+    edit.touch_module_objects(&target_module);
+    let a2l = edit.a2l_mut();
+    let target = find_module_mut(a2l, Some(&target_module))?;
+    for (item_kind, name, dependency) in &staged {
+        transfer(&staging, target, item_kind, name);
+        if *dependency {
+            result.copied_dependencies.push(format!("{item_kind} '{name}'"));
+        }
+    }
+    *find_module_mut(a2l, Some(&source_name))? = remaining;
+    Ok(result)
+}
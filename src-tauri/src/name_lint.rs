@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+
+use a2lfile::A2lObjectName;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::references::{name_taken, namespace, object_names, rename_object};
+use crate::AppState;
+
+const LINT_KINDS: &[&str] = &[
+    "Measurement",
+    "Characteristic",
+    "AxisPts",
+    "Blob",
+    "Instance",
+    "CompuMethod",
+    "CompuTab",
+    "CompuVtab",
+    "CompuVtabRange",
+    "RecordLayout",
+    "Unit",
+    "Function",
+    "Group",
+    "Frame",
+];
+
+#[derive(Deserialize)]
+pub struct NameRule {
+    /// Object kind the rule applies to, or "*" for all kinds.
+    kind: String,
+    #[serde(default)]
+    pattern: Option<String>,
+    #[serde(default)]
+    prefix: Option<String>,
+    #[serde(default)]
+    suffix: Option<String>,
+    #[serde(default)]
+    min_length: Option<usize>,
+    #[serde(default)]
+    max_length: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct NameViolation {
+    module: String,
+    kind: String,
+    name: String,
+    problems: Vec<String>,
+    suggestion: Option<String>,
+    renamed: bool,
+}
+
+#[derive(Serialize)]
+pub struct NameLintReport {
+    checked: usize,
+    violations: Vec<NameViolation>,
+    references_updated: usize,
+}
+
+struct CompiledRule<'a> {
+    rule: &'a NameRule,
+    pattern: Option<Regex>,
+}
+
+impl CompiledRule<'_> {
+    fn applies_to(&self, kind: &str) -> bool {
+        self.rule.kind == "*" || self.rule.kind == kind
+    }
+
+    fn problems(&self, name: &str) -> Vec<String> {
+        let rule = self.rule;
+        let length = name.chars().count();
+        let mut problems = Vec::new();
+        if let Some(pattern) = &self.pattern {
+            if !pattern.is_match(name) {
+                problems.push(format!("does not match {}", pattern.as_str()));
+            }
+        }
+        if let Some(prefix) = rule.prefix.as_deref().filter(|p| !name.starts_with(p)) {
+            problems.push(format!("missing prefix '{prefix}'"));
+        }
+        if let Some(suffix) = rule.suffix.as_deref().filter(|s| !name.ends_with(s)) {
+            problems.push(format!("missing suffix '{suffix}'"));
+        }
+        if let Some(min) = rule.min_length.filter(|&min| length < min) {
+            problems.push(format!("shorter than {min} characters"));
+        }
+        if let Some(max) = rule.max_length.filter(|&max| length > max) {
+            problems.push(format!("longer than {max} characters"));
+        }
+        problems
+    }
+
+    /// Adds missing affixes, replaces characters outside `[A-Za-z0-9_]` when a
+    /// pattern is set, and shortens the stem to the maximum length.
+    fn fix(&self, name: &str) -> String {
+        let rule = self.rule;
+        let prefix = rule.prefix.as_deref().unwrap_or("");
+        let suffix = rule.suffix.as_deref().unwrap_or("");
+        let mut stem = name.strip_prefix(prefix).unwrap_or(name);
+        stem = stem.strip_suffix(suffix).unwrap_or(stem);
+        let mut stem: String = if self.pattern.is_some() {
+            stem.chars().map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' }).collect()
+        } else {
+            stem.to_string()
+        };
+        if let Some(max) = rule.max_length {
+            let room = max.saturating_sub(prefix.chars().count() + suffix.chars().count());
+            stem = stem.chars().take(room).collect();
+        }
+        format!("{prefix}{stem}{suffix}")
+    }
+}
+
+/// Checks object names against `ruleset`. With `apply_fixes`, every violation
+/// that has an unambiguous fix is renamed and references to it are updated.
+#[tauri::command]
+pub fn lint_names(
+    ruleset: Vec<NameRule>,
+    apply_fixes: Option<bool>,
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<NameLintReport, String> {
+    let rules = ruleset
+        .iter()
+        .map(|rule| {
+            let pattern = rule
+                .pattern
+                .as_deref()
+                .filter(|p| !p.is_empty())
+                .map(|p| Regex::new(p).map_err(|e| format!("Invalid pattern '{p}': {e}")))
+                .transpose()?;
+            Ok(CompiledRule { rule, pattern })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let apply_fixes = apply_fixes.unwrap_or(false);
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("lint_names", doc_id.as_deref())?;
+    if apply_fixes {
+        edit.touch_all();
+    }
+    let a2l = edit.a2l_mut();
+    let mut report = NameLintReport {
+        checked: 0,
+        violations: Vec::new(),
+        references_updated: 0,
+    };
+
+    for module in a2l.project.module.iter_mut() {
+        if module_name.as_deref().is_some_and(|name| module.get_name() != name) {
+            continue;
+        }
+        let module_id = module.get_name().to_string();
+        let mut claimed = HashSet::new();
+        for kind in LINT_KINDS {
+            let kind_rules: Vec<&CompiledRule> = rules.iter().filter(|r| r.applies_to(kind)).collect();
+            if kind_rules.is_empty() {
+                continue;
+            }
+            for name in object_names(module, kind) {
+                report.checked += 1;
+                let problems: Vec<String> = kind_rules.iter().flat_map(|r| r.problems(&name)).collect();
+                if problems.is_empty() {
+                    continue;
+                }
+                let fixed = kind_rules.iter().fold(name.clone(), |current, r| r.fix(&current));
+                let suggestion = (fixed != name
+                    && kind_rules.iter().all(|r| r.problems(&fixed).is_empty())
+                    && !name_taken(module, kind, &fixed)
+                    && !claimed.contains(&(namespace(kind).to_string(), fixed.clone())))
+                .then_some(fixed);
+
+                let mut renamed = false;
+                if let Some(new_name) = &suggestion {
+                    claimed.insert((namespace(kind).to_string(), new_name.clone()));
+                    if apply_fixes {
+                        if let Some(count) = rename_object(module, kind, &name, new_name) {
+                            report.references_updated += count;
+                            renamed = true;
+                        }
+                    }
+                }
+                report.violations.push(NameViolation {
+                    module: module_id.clone(),
+                    kind: kind.to_string(),
+                    name,
+                    problems,
+                    suggestion,
+                    renamed,
+                });
+            }
+        }
+    }
+    Ok(report)
+}
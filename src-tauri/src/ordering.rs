@@ -0,0 +1,94 @@
+use a2lfile::{A2lObject, A2lObjectName, ItemList};
+
+use crate::{build_metadata, A2lMetadata, AppState};
+
+/// Sorts `list` by `key`, keeping the current order for equal keys. With
+/// `reset` the source positions are cleared so the writer follows the list
+/// order instead of the order of the original file.
+fn reorder<T, L, K: Ord>(list: &mut ItemList<T>, reset: bool, key: impl Fn(&T) -> K)
+where
+    T: A2lObject<L> + A2lObjectName + Clone,
+{
+    let mut items: Vec<T> = list.iter().cloned().collect();
+    items.sort_by_key(|item| key(item));
+    list.retain(|_| false);
+    for mut item in items {
+        if reset {
+            item.get_layout_mut().uid = 0;
+        }
+        list.push(item);
+    }
+}
+
+fn by_name<T: A2lObjectName>(item: &T) -> String {
+    item.get_name().to_string()
+}
+
+/// Objects with an address first, by address then name; the rest by name.
+fn by_address(address: Option<u32>, name: &str) -> (bool, Option<u32>, String) {
+    (address.is_none(), address, name.to_string())
+}
+
+/// Position in the loaded file; objects created since keep their relative
+/// order after all parsed ones.
+fn by_position<T: A2lObject<L>, L>(item: &T) -> (bool, u32) {
+    let uid = item.get_layout().uid;
+    (uid == 0, uid)
+}
+
+macro_rules! reorder_lists {
+    ($module:expr, $reset:expr, $key:expr; $($field:ident),*) => {
+        $(reorder(&mut $module.$field, $reset, $key);)*
+    };
+}
+
+/// Orders the objects of every module. `address` sorts objects with an ECU
+/// address by address and all other lists by name.
+pub(crate) fn sort_modules(a2l: &mut a2lfile::A2lFile, order: &str) -> Result<(), String> {
+    match order {
+        "alphabetical" => a2l.sort(),
+        "address" => {
+            for module in a2l.project.module.iter_mut() {
+                reorder(&mut module.measurement, true, |m| {
+                    by_address(m.ecu_address.as_ref().map(|a| a.address), m.get_name())
+                });
+                reorder(&mut module.characteristic, true, |c| by_address(Some(c.address), c.get_name()));
+                reorder(&mut module.axis_pts, true, |a| by_address(Some(a.address), a.get_name()));
+                reorder(&mut module.blob, true, |b| by_address(Some(b.start_address), b.get_name()));
+                reorder(&mut module.instance, true, |i| by_address(Some(i.start_address), i.get_name()));
+                reorder_lists!(module, true, by_name;
+                    compu_method, compu_tab, compu_vtab, compu_vtab_range, frame, function, group,
+                    record_layout, transformer, typedef_axis, typedef_blob, typedef_characteristic,
+                    typedef_measurement, typedef_structure, unit);
+            }
+        }
+        "original" => {
+            for module in a2l.project.module.iter_mut() {
+                reorder_lists!(module, false, by_position;
+                    measurement, characteristic, axis_pts, blob, instance, compu_method, compu_tab,
+                    compu_vtab, compu_vtab_range, frame, function, group, record_layout, transformer,
+                    typedef_axis, typedef_blob, typedef_characteristic, typedef_measurement,
+                    typedef_structure, unit);
+            }
+        }
+        _ => return Err(format!("Unknown sort order: {order}")),
+    }
+    Ok(())
+}
+
+/// Reorders the objects of each module in the document: "alphabetical",
+/// "address" or "original". "original" restores file order for objects whose
+/// position was not cleared by an earlier sort. Sorted output stays stable
+/// across saves because the writer then follows the list order.
+#[tauri::command]
+pub fn sort_a2l(order: String, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<A2lMetadata, String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("sort_a2l", doc_id.as_deref())?;
+    // Sorting only moves objects, so compare the written order.
+    let before = edit.a2l().write_to_string();
+    sort_modules(edit.a2l_mut(), &order)?;
+    if edit.a2l().write_to_string() != before {
+        edit.mark_changed();
+    }
+    Ok(build_metadata(edit.a2l(), 0))
+}
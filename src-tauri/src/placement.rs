@@ -0,0 +1,184 @@
+use a2lfile::A2lObjectName;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::address_map::collect_address_entries;
+use crate::layout::{axis_pts_size, characteristic_size};
+use crate::{find_module_mut, AppState};
+
+const PLACEMENT_KINDS: &[&str] = &["Characteristic", "AxisPts"];
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct PlacementSelector {
+    module_name: Option<String>,
+    /// Subset of Characteristic and AxisPts; both when empty.
+    kinds: Vec<String>,
+    /// Exact object names; combined with `pattern` as an alternative.
+    names: Vec<String>,
+    pattern: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct PlacedObject {
+    kind: String,
+    name: String,
+    old_address: String,
+    new_address: String,
+    size: u32,
+}
+
+#[derive(Serialize)]
+pub struct UnplacedObject {
+    kind: String,
+    name: String,
+    reason: String,
+}
+
+#[derive(Serialize)]
+pub struct PlacementReport {
+    placed: Vec<PlacedObject>,
+    skipped: Vec<UnplacedObject>,
+}
+
+fn align_up(address: u64, alignment: u64) -> u64 {
+    address.div_ceil(alignment) * alignment
+}
+
+/// Lowest aligned start in `[start, end)` where `size` bytes fit between the
+/// sorted, possibly overlapping `occupied` ranges.
+fn first_fit(occupied: &[(u64, u64)], start: u64, end: u64, size: u64, alignment: u64) -> Option<u64> {
+    let mut candidate = align_up(start, alignment);
+    for &(used_start, used_end) in occupied {
+        if candidate + size <= used_start {
+            break;
+        }
+        if used_end > candidate {
+            candidate = align_up(used_end, alignment);
+        }
+    }
+    (candidate + size <= end).then_some(candidate)
+}
+
+/// Places the selected characteristics and axis points that have no address
+/// (0) or overlap another object into the free space of `segment`. Objects
+/// already in the address map, in any module, count as occupied; the
+/// remaining candidates are packed first-fit in file order.
+#[tauri::command]
+pub fn auto_assign_addresses(
+    selector: Option<PlacementSelector>,
+    segment: String,
+    alignment: Option<u32>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<PlacementReport, String> {
+    let selector = selector.unwrap_or_default();
+    let alignment = u64::from(alignment.unwrap_or(1));
+    if alignment == 0 {
+        return Err("Alignment must be at least 1".to_string());
+    }
+    for kind in &selector.kinds {
+        if !PLACEMENT_KINDS.contains(&kind.as_str()) {
+            return Err(format!("Addresses cannot be assigned to {kind}"));
+        }
+    }
+    let pattern = selector
+        .pattern
+        .as_deref()
+        .filter(|p| !p.is_empty())
+        .map(|p| Regex::new(p).map_err(|e| format!("Invalid pattern '{p}': {e}")))
+        .transpose()?;
+    let selects = |kind: &str, name: &str| {
+        let kind_ok = selector.kinds.is_empty() || selector.kinds.iter().any(|k| k == kind);
+        let name_ok = (selector.names.is_empty() && pattern.is_none())
+            || selector.names.iter().any(|n| n == name)
+            || pattern.as_ref().is_some_and(|p| p.is_match(name));
+        kind_ok && name_ok
+    };
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("auto_assign_addresses", doc_id.as_deref())?;
+    edit.touch_target_module(selector.module_name.as_deref())?;
+    let a2l = edit.a2l_mut();
+    let entries = collect_address_entries(a2l);
+    let module = find_module_mut(a2l, selector.module_name.as_deref())?;
+    let module_id = module.get_name().to_string();
+    let (segment_start, segment_end) = module
+        .mod_par
+        .as_ref()
+        .and_then(|mod_par| mod_par.memory_segment.get(&segment))
+        .map(|s| (u64::from(s.address), u64::from(s.address) + u64::from(s.size)))
+        .ok_or_else(|| format!("Memory segment '{segment}' not found"))?;
+
+    let range = |address: u32, size: Option<u32>| {
+        let start = u64::from(address);
+        (start, start + u64::from(size.unwrap_or(1).max(1)))
+    };
+    let collides = |kind: &str, name: &str, address: u32, size: Option<u32>| {
+        let (start, end) = range(address, size);
+        entries.iter().any(|e| {
+            let (other_start, other_end) = range(e.address, e.size);
+            !(e.module == module_id && e.kind == kind && e.name == name) && start < other_end && other_start < end
+        })
+    };
+
+    // (kind, name, current address, size) of the objects to move.
+    let mut candidates = Vec::new();
+    for c in module.characteristic.iter() {
+        let size = characteristic_size(module, c);
+        if selects("Characteristic", c.get_name()) && (c.address == 0 || collides("Characteristic", c.get_name(), c.address, size)) {
+            candidates.push(("Characteristic", c.get_name().to_string(), c.address, size));
+        }
+    }
+    for a in module.axis_pts.iter() {
+        let size = axis_pts_size(module, a);
+        if selects("AxisPts", a.get_name()) && (a.address == 0 || collides("AxisPts", a.get_name(), a.address, size)) {
+            candidates.push(("AxisPts", a.get_name().to_string(), a.address, size));
+        }
+    }
+
+    let mut occupied: Vec<(u64, u64)> = entries
+        .iter()
+        .filter(|e| {
+            !(e.module == module_id && candidates.iter().any(|(kind, name, _, _)| e.kind == *kind && e.name == *name))
+        })
+        .filter(|e| e.address != 0)
+        .map(|e| range(e.address, e.size))
+        .collect();
+    occupied.sort();
+
+    let mut report = PlacementReport {
+        placed: Vec::new(),
+        skipped: Vec::new(),
+    };
+    for (kind, name, old_address, size) in candidates {
+        let skip = |reason: String| UnplacedObject {
+            kind: kind.to_string(),
+            name: name.clone(),
+            reason,
+        };
+        let Some(size) = size.filter(|&s| s > 0) else {
+            report.skipped.push(skip("size cannot be resolved".to_string()));
+            continue;
+        };
+        let Some(address) = first_fit(&occupied, segment_start, segment_end, u64::from(size), alignment) else {
+            report.skipped.push(skip(format!("no free {size} byte slot left in {segment}")));
+            continue;
+        };
+        occupied.push((address, address + u64::from(size)));
+        occupied.sort();
+        let address = address as u32;
+        match kind {
+            "Characteristic" => module.characteristic.get_mut(&name).map(|c| c.address = address),
+            _ => module.axis_pts.get_mut(&name).map(|a| a.address = address),
+        };
+        report.placed.push(PlacedObject {
+            kind: kind.to_string(),
+            name,
+            old_address: format!("0x{:X}", old_address),
+            new_address: format!("0x{:X}", address),
+            size,
+        });
+    }
+    Ok(report)
+}
@@ -0,0 +1,123 @@
+use a2lfile::A2lObjectName;
+use serde::Serialize;
+
+use crate::references::{
+    check_module_references, object_exists, object_names, rename_references, target_candidates, ReferenceSite,
+};
+use crate::{find_module_mut, AppState};
+
+const DEFAULT_MAX_SUGGESTIONS: usize = 3;
+
+#[derive(Serialize)]
+pub struct ReferenceCandidate {
+    kind: String,
+    name: String,
+    distance: usize,
+}
+
+#[derive(Serialize)]
+pub struct ReferenceFixSuggestion {
+    site: ReferenceSite,
+    /// Closest first; empty when no existing name is similar enough.
+    candidates: Vec<ReferenceCandidate>,
+}
+
+/// Edit distance over characters, ignoring case.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().flat_map(char::to_lowercase).collect();
+    let b: Vec<char> = b.chars().flat_map(char::to_lowercase).collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Largest distance still offered as a fix: a third of the name, at least 2.
+fn max_distance(target: &str) -> usize {
+    (target.chars().count() / 3).max(2)
+}
+
+/// Proposes existing names of a matching kind for each reference to an
+/// object that does not exist anywhere in the document. References that
+/// resolve in another module are left to the cross-module setting.
+#[tauri::command]
+pub fn suggest_reference_fixes(
+    max_suggestions: Option<usize>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<ReferenceFixSuggestion>, String> {
+    let config = state.reference_config.lock().map_err(|_| "State lock poisoned")?.clone();
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let limit = max_suggestions.unwrap_or(DEFAULT_MAX_SUGGESTIONS);
+
+    let mut suggestions = Vec::new();
+    for issue in check_module_references(a2l, &config) {
+        let site = issue.site;
+        let kinds = target_candidates(&site.target_kind);
+        if issue.resolved_module.is_some() || kinds.is_empty() {
+            continue;
+        }
+        let Some(module) = a2l.project.module.iter().find(|m| m.get_name() == site.module) else {
+            continue;
+        };
+        let threshold = max_distance(&site.target);
+        let mut candidates: Vec<ReferenceCandidate> = kinds
+            .iter()
+            .flat_map(|kind| {
+                object_names(module, kind).into_iter().map(|name| ReferenceCandidate {
+                    kind: kind.to_string(),
+                    distance: levenshtein(&site.target, &name),
+                    name,
+                })
+            })
+            .filter(|candidate| candidate.distance <= threshold)
+            .collect();
+        candidates.sort_by(|a, b| a.distance.cmp(&b.distance).then_with(|| a.name.cmp(&b.name)));
+        candidates.truncate(limit);
+        suggestions.push(ReferenceFixSuggestion { site, candidates });
+    }
+    Ok(suggestions)
+}
+
+/// Rewrites every reference to the missing `target` of `target_kind` in the
+/// module to `replacement`, which must exist. Returns the number of rewritten
+/// references.
+#[tauri::command]
+pub fn apply_reference_fix(
+    module_name: Option<String>,
+    target_kind: String,
+    target: String,
+    replacement: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<usize, String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("apply_reference_fix", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    for kind in target_candidates(&target_kind) {
+        edit.touch_with_referrers(&module_id, kind, &target);
+    }
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+    let candidates = target_candidates(&target_kind);
+    if candidates.iter().any(|kind| object_exists(module, kind, &target)) {
+        return Err(format!("{target_kind} '{target}' exists; use rename instead"));
+    }
+    let kind = candidates
+        .iter()
+        .find(|kind| object_exists(module, kind, &replacement))
+        .ok_or_else(|| format!("{target_kind} '{replacement}' does not exist"))?;
+    let count = rename_references(module, kind, &target, &replacement);
+    if count == 0 {
+        return Err(format!("No reference to {target_kind} '{target}' found"));
+    }
+    Ok(count)
+}
@@ -0,0 +1,618 @@
+use std::collections::HashSet;
+
+use a2lfile::{A2lObjectName, A2lObjectNameSetter};
+use serde::{Deserialize, Serialize};
+
+use crate::dependent_characteristics::formula_issues;
+use crate::AppState;
+
+/// Names that ASAP2 defines as "no reference" placeholders.
+const NULL_REFERENCES: &[&str] = &["NO_COMPU_METHOD", "NO_INPUT_QUANTITY", "NO_AXIS_PTS", ""];
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ReferenceConfig {
+    pub(crate) allow_cross_module: bool,
+}
+
+#[derive(Serialize, Clone)]
+pub(crate) struct ReferenceSite {
+    pub(crate) module: String,
+    pub(crate) kind: String,
+    pub(crate) name: String,
+    pub(crate) field: String,
+    pub(crate) target_kind: String,
+    pub(crate) target: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ReferenceIssue {
+    pub(crate) site: ReferenceSite,
+    pub(crate) severity: String,
+    pub(crate) message: String,
+    pub(crate) resolved_module: Option<String>,
+}
+
+/// Target kinds a reference may point at. Some ASAP2 fields accept several
+/// object kinds (e.g. COMPU_TAB_REF, INSTANCE type refs).
+pub(crate) fn target_candidates(target_kind: &str) -> &'static [&'static str] {
+    match target_kind {
+        "CompuMethod" => &["CompuMethod"],
+        "CompuTab" => &["CompuTab", "CompuVtab", "CompuVtabRange"],
+        "CompuVtab" => &["CompuVtab"],
+        "RecordLayout" => &["RecordLayout"],
+        "Unit" => &["Unit"],
+        "Measurement" => &["Measurement"],
+        "Characteristic" => &["Characteristic"],
+        "AxisPts" => &["AxisPts"],
+        "Function" => &["Function"],
+        "Group" => &["Group"],
+        "Typedef" => &[
+            "TypedefStructure",
+            "TypedefMeasurement",
+            "TypedefCharacteristic",
+            "TypedefAxis",
+            "TypedefBlob",
+        ],
+        "Calibratable" => &["Characteristic", "AxisPts", "Blob", "Instance"],
+        "Object" => &["Measurement", "Characteristic", "AxisPts", "Blob", "Instance"],
+        _ => &[],
+    }
+}
+
+/// Kinds of the named objects in a module, in the order `object_names` and
+/// `object_exists` know them.
+pub(crate) const OBJECT_KINDS: &[&str] = &[
+    "Measurement",
+    "Characteristic",
+    "AxisPts",
+    "Blob",
+    "Instance",
+    "CompuMethod",
+    "CompuTab",
+    "CompuVtab",
+    "CompuVtabRange",
+    "RecordLayout",
+    "Unit",
+    "Function",
+    "Group",
+    "Frame",
+    "TypedefStructure",
+    "TypedefMeasurement",
+    "TypedefCharacteristic",
+    "TypedefAxis",
+    "TypedefBlob",
+];
+
+pub(crate) fn object_exists(module: &a2lfile::Module, kind: &str, name: &str) -> bool {
+    match kind {
+        "Measurement" => module.measurement.get(name).is_some(),
+        "Characteristic" => module.characteristic.get(name).is_some(),
+        "AxisPts" => module.axis_pts.get(name).is_some(),
+        "Blob" => module.blob.get(name).is_some(),
+        "Instance" => module.instance.get(name).is_some(),
+        "CompuMethod" => module.compu_method.get(name).is_some(),
+        "CompuTab" => module.compu_tab.get(name).is_some(),
+        "CompuVtab" => module.compu_vtab.get(name).is_some(),
+        "CompuVtabRange" => module.compu_vtab_range.get(name).is_some(),
+        "RecordLayout" => module.record_layout.get(name).is_some(),
+        "Unit" => module.unit.get(name).is_some(),
+        "Function" => module.function.get(name).is_some(),
+        "Group" => module.group.get(name).is_some(),
+        "Frame" => module.frame.get(name).is_some(),
+        "TypedefStructure" => module.typedef_structure.get(name).is_some(),
+        "TypedefMeasurement" => module.typedef_measurement.get(name).is_some(),
+        "TypedefCharacteristic" => module.typedef_characteristic.get(name).is_some(),
+        "TypedefAxis" => module.typedef_axis.get(name).is_some(),
+        "TypedefBlob" => module.typedef_blob.get(name).is_some(),
+        _ => false,
+    }
+}
+
+/// Name space of `kind`: "Object" for the kinds that share one, otherwise the
+/// kind itself.
+pub(crate) fn namespace(kind: &str) -> &str {
+    if target_candidates("Object").contains(&kind) {
+        "Object"
+    } else {
+        kind
+    }
+}
+
+/// Whether `name` is used by an object of `kind` or of another kind in the
+/// same name space.
+pub(crate) fn name_taken(module: &a2lfile::Module, kind: &str, name: &str) -> bool {
+    match namespace(kind) {
+        "Object" => target_candidates("Object").iter().any(|kind| object_exists(module, kind, name)),
+        _ => object_exists(module, kind, name),
+    }
+}
+
+/// Names of all MEASUREMENT, CHARACTERISTIC, AXIS_PTS, BLOB and INSTANCE
+/// objects of `module`. ASAP2 requires these to be unique across kinds.
+pub(crate) fn object_namespace(module: &a2lfile::Module) -> HashSet<String> {
+    target_candidates("Object")
+        .iter()
+        .flat_map(|kind| object_names(module, kind))
+        .collect()
+}
+
+/// Names of all objects of `kind` in `module`, in file order.
+pub(crate) fn object_names(module: &a2lfile::Module, kind: &str) -> Vec<String> {
+    macro_rules! names {
+        ($list:ident) => {
+            module.$list.iter().map(|item| item.get_name().to_string()).collect()
+        };
+    }
+    match kind {
+        "Measurement" => names!(measurement),
+        "Characteristic" => names!(characteristic),
+        "AxisPts" => names!(axis_pts),
+        "Blob" => names!(blob),
+        "Instance" => names!(instance),
+        "CompuMethod" => names!(compu_method),
+        "CompuTab" => names!(compu_tab),
+        "CompuVtab" => names!(compu_vtab),
+        "CompuVtabRange" => names!(compu_vtab_range),
+        "RecordLayout" => names!(record_layout),
+        "Unit" => names!(unit),
+        "Function" => names!(function),
+        "Group" => names!(group),
+        "Frame" => names!(frame),
+        "TypedefStructure" => names!(typedef_structure),
+        "TypedefMeasurement" => names!(typedef_measurement),
+        "TypedefCharacteristic" => names!(typedef_characteristic),
+        "TypedefAxis" => names!(typedef_axis),
+        "TypedefBlob" => names!(typedef_blob),
+        _ => Vec::new(),
+    }
+}
+
+/// Renames `kind`/`old` to `new` and rewrites every reference to it in
+/// `module`. Returns the number of rewritten references, or `None` if the
+/// object does not exist.
+pub(crate) fn rename_object(module: &mut a2lfile::Module, kind: &str, old: &str, new: &str) -> Option<usize> {
+    macro_rules! rename {
+        ($list:ident) => {{
+            let item = module.$list.iter_mut().find(|item| item.get_name() == old)?;
+            item.set_name(new.to_string());
+        }};
+    }
+    match kind {
+        "Measurement" => rename!(measurement),
+        "Characteristic" => rename!(characteristic),
+        "AxisPts" => rename!(axis_pts),
+        "Blob" => rename!(blob),
+        "Instance" => rename!(instance),
+        "CompuMethod" => rename!(compu_method),
+        "CompuTab" => rename!(compu_tab),
+        "CompuVtab" => rename!(compu_vtab),
+        "CompuVtabRange" => rename!(compu_vtab_range),
+        "RecordLayout" => rename!(record_layout),
+        "Unit" => rename!(unit),
+        "Function" => rename!(function),
+        "Group" => rename!(group),
+        "Frame" => rename!(frame),
+        "TypedefStructure" => rename!(typedef_structure),
+        "TypedefMeasurement" => rename!(typedef_measurement),
+        "TypedefCharacteristic" => rename!(typedef_characteristic),
+        "TypedefAxis" => rename!(typedef_axis),
+        "TypedefBlob" => rename!(typedef_blob),
+        _ => return None,
+    }
+    Some(rename_references(module, kind, old, new))
+}
+
+/// Mutable counterpart of `for_each_reference` that replaces references to
+/// `kind`/`old` with `new`. Covers the same fields.
+pub(crate) fn rename_references(module: &mut a2lfile::Module, kind: &str, old: &str, new: &str) -> usize {
+    let mut count = 0;
+    let mut fix = |target_kind: &str, target: &mut String| {
+        if target == old && target_candidates(target_kind).contains(&kind) {
+            *target = new.to_string();
+            count += 1;
+        }
+    };
+    fn fix_list(fix: &mut impl FnMut(&str, &mut String), target_kind: &str, list: Option<&mut Vec<String>>) {
+        for target in list.into_iter().flatten() {
+            fix(target_kind, target);
+        }
+    }
+
+    for m in module.measurement.iter_mut() {
+        fix("CompuMethod", &mut m.conversion);
+        fix_list(&mut fix, "Function", m.function_list.as_mut().map(|l| &mut l.name_list));
+    }
+    for c in module.characteristic.iter_mut() {
+        fix("CompuMethod", &mut c.conversion);
+        fix("RecordLayout", &mut c.deposit);
+        for axis in c.axis_descr.iter_mut() {
+            fix("Measurement", &mut axis.input_quantity);
+            fix("CompuMethod", &mut axis.conversion);
+            if let Some(axis_pts_ref) = &mut axis.axis_pts_ref {
+                fix("AxisPts", &mut axis_pts_ref.axis_points);
+            }
+            if let Some(curve_axis_ref) = &mut axis.curve_axis_ref {
+                fix("Characteristic", &mut curve_axis_ref.curve_axis);
+            }
+        }
+        if let Some(comparison_quantity) = &mut c.comparison_quantity {
+            fix("Measurement", &mut comparison_quantity.name);
+        }
+        fix_list(&mut fix, "Characteristic", c.dependent_characteristic.as_mut().map(|l| &mut l.characteristic_list));
+        fix_list(&mut fix, "Characteristic", c.virtual_characteristic.as_mut().map(|l| &mut l.characteristic_list));
+        fix_list(&mut fix, "Function", c.function_list.as_mut().map(|l| &mut l.name_list));
+    }
+    for a in module.axis_pts.iter_mut() {
+        fix("Measurement", &mut a.input_quantity);
+        fix("RecordLayout", &mut a.deposit_record);
+        fix("CompuMethod", &mut a.conversion);
+        fix_list(&mut fix, "Function", a.function_list.as_mut().map(|l| &mut l.name_list));
+    }
+    for cm in module.compu_method.iter_mut() {
+        if let Some(compu_tab_ref) = &mut cm.compu_tab_ref {
+            fix("CompuTab", &mut compu_tab_ref.conversion_table);
+        }
+        if let Some(ref_unit) = &mut cm.ref_unit {
+            fix("Unit", &mut ref_unit.unit);
+        }
+        if let Some(status_string_ref) = &mut cm.status_string_ref {
+            fix("CompuVtab", &mut status_string_ref.conversion_table);
+        }
+    }
+    for unit in module.unit.iter_mut() {
+        if let Some(ref_unit) = &mut unit.ref_unit {
+            fix("Unit", &mut ref_unit.unit);
+        }
+    }
+    for f in module.function.iter_mut() {
+        fix_list(&mut fix, "Measurement", f.in_measurement.as_mut().map(|l| &mut l.identifier_list));
+        fix_list(&mut fix, "Measurement", f.out_measurement.as_mut().map(|l| &mut l.identifier_list));
+        fix_list(&mut fix, "Measurement", f.loc_measurement.as_mut().map(|l| &mut l.identifier_list));
+        fix_list(&mut fix, "Calibratable", f.def_characteristic.as_mut().map(|l| &mut l.identifier_list));
+        fix_list(&mut fix, "Calibratable", f.ref_characteristic.as_mut().map(|l| &mut l.identifier_list));
+        fix_list(&mut fix, "Function", f.sub_function.as_mut().map(|l| &mut l.identifier_list));
+    }
+    for g in module.group.iter_mut() {
+        fix_list(&mut fix, "Measurement", g.ref_measurement.as_mut().map(|l| &mut l.identifier_list));
+        fix_list(&mut fix, "Calibratable", g.ref_characteristic.as_mut().map(|l| &mut l.identifier_list));
+        fix_list(&mut fix, "Group", g.sub_group.as_mut().map(|l| &mut l.identifier_list));
+        fix_list(&mut fix, "Function", g.function_list.as_mut().map(|l| &mut l.name_list));
+    }
+    for frame in module.frame.iter_mut() {
+        fix_list(&mut fix, "Measurement", frame.frame_measurement.as_mut().map(|l| &mut l.identifier_list));
+    }
+    for instance in module.instance.iter_mut() {
+        fix("Typedef", &mut instance.type_ref);
+    }
+    for typedef in module.typedef_characteristic.iter_mut() {
+        fix("RecordLayout", &mut typedef.record_layout);
+        fix("CompuMethod", &mut typedef.conversion);
+    }
+    for typedef in module.typedef_measurement.iter_mut() {
+        fix("CompuMethod", &mut typedef.conversion);
+    }
+    for typedef in module.typedef_axis.iter_mut() {
+        fix("RecordLayout", &mut typedef.record_layout);
+        fix("CompuMethod", &mut typedef.conversion);
+        fix("Measurement", &mut typedef.input_quantity);
+    }
+    for typedef in module.typedef_structure.iter_mut() {
+        for component in typedef.structure_component.iter_mut() {
+            fix("Typedef", &mut component.component_type);
+        }
+    }
+    for user_rights in module.user_rights.iter_mut() {
+        for ref_group in user_rights.ref_group.iter_mut() {
+            fix_list(&mut fix, "Group", Some(&mut ref_group.identifier_list));
+        }
+    }
+    if let Some(variant_coding) = &mut module.variant_coding {
+        for characteristic in variant_coding.var_characteristic.iter_mut() {
+            let mut name = characteristic.get_name().to_string();
+            fix("Characteristic", &mut name);
+            if name != characteristic.get_name() {
+                characteristic.set_name(name);
+            }
+        }
+        for criterion in variant_coding.var_criterion.iter_mut() {
+            if let Some(var_measurement) = &mut criterion.var_measurement {
+                fix("Measurement", &mut var_measurement.name);
+            }
+            if let Some(selection) = &mut criterion.var_selection_characteristic {
+                fix("Characteristic", &mut selection.name);
+            }
+        }
+    }
+    count
+}
+
+fn target_exists(module: &a2lfile::Module, target_kind: &str, name: &str) -> bool {
+    target_candidates(target_kind)
+        .iter()
+        .any(|kind| object_exists(module, kind, name))
+}
+
+/// Calls `visit(kind, name, field, target_kind, target)` for every name
+/// reference held by an object of `module`.
+pub(crate) fn for_each_reference(
+    module: &a2lfile::Module,
+    visit: &mut dyn FnMut(&str, &str, &str, &str, &str),
+) {
+    for m in module.measurement.iter() {
+        let name = m.get_name();
+        visit("Measurement", name, "conversion", "CompuMethod", &m.conversion);
+        if let Some(function_list) = &m.function_list {
+            for target in &function_list.name_list {
+                visit("Measurement", name, "function_list", "Function", target);
+            }
+        }
+    }
+    for c in module.characteristic.iter() {
+        let name = c.get_name();
+        visit("Characteristic", name, "conversion", "CompuMethod", &c.conversion);
+        visit("Characteristic", name, "deposit", "RecordLayout", &c.deposit);
+        for axis in c.axis_descr.iter() {
+            visit("Characteristic", name, "axis_descr.input_quantity", "Measurement", &axis.input_quantity);
+            visit("Characteristic", name, "axis_descr.conversion", "CompuMethod", &axis.conversion);
+            if let Some(axis_pts_ref) = &axis.axis_pts_ref {
+                visit("Characteristic", name, "axis_descr.axis_pts_ref", "AxisPts", &axis_pts_ref.axis_points);
+            }
+            if let Some(curve_axis_ref) = &axis.curve_axis_ref {
+                visit("Characteristic", name, "axis_descr.curve_axis_ref", "Characteristic", &curve_axis_ref.curve_axis);
+            }
+        }
+        if let Some(comparison_quantity) = &c.comparison_quantity {
+            visit("Characteristic", name, "comparison_quantity", "Measurement", &comparison_quantity.name);
+        }
+        if let Some(dependent) = &c.dependent_characteristic {
+            for target in &dependent.characteristic_list {
+                visit("Characteristic", name, "dependent_characteristic", "Characteristic", target);
+            }
+        }
+        if let Some(virtual_characteristic) = &c.virtual_characteristic {
+            for target in &virtual_characteristic.characteristic_list {
+                visit("Characteristic", name, "virtual_characteristic", "Characteristic", target);
+            }
+        }
+        if let Some(function_list) = &c.function_list {
+            for target in &function_list.name_list {
+                visit("Characteristic", name, "function_list", "Function", target);
+            }
+        }
+    }
+    for a in module.axis_pts.iter() {
+        let name = a.get_name();
+        visit("AxisPts", name, "input_quantity", "Measurement", &a.input_quantity);
+        visit("AxisPts", name, "deposit_record", "RecordLayout", &a.deposit_record);
+        visit("AxisPts", name, "conversion", "CompuMethod", &a.conversion);
+        if let Some(function_list) = &a.function_list {
+            for target in &function_list.name_list {
+                visit("AxisPts", name, "function_list", "Function", target);
+            }
+        }
+    }
+    for cm in module.compu_method.iter() {
+        let name = cm.get_name();
+        if let Some(compu_tab_ref) = &cm.compu_tab_ref {
+            visit("CompuMethod", name, "compu_tab_ref", "CompuTab", &compu_tab_ref.conversion_table);
+        }
+        if let Some(ref_unit) = &cm.ref_unit {
+            visit("CompuMethod", name, "ref_unit", "Unit", &ref_unit.unit);
+        }
+        if let Some(status_string_ref) = &cm.status_string_ref {
+            visit("CompuMethod", name, "status_string_ref", "CompuVtab", &status_string_ref.conversion_table);
+        }
+    }
+    for unit in module.unit.iter() {
+        if let Some(ref_unit) = &unit.ref_unit {
+            visit("Unit", unit.get_name(), "ref_unit", "Unit", &ref_unit.unit);
+        }
+    }
+    for f in module.function.iter() {
+        let name = f.get_name();
+        let lists = [
+            ("in_measurement", "Measurement", f.in_measurement.as_ref().map(|l| &l.identifier_list)),
+            ("out_measurement", "Measurement", f.out_measurement.as_ref().map(|l| &l.identifier_list)),
+            ("loc_measurement", "Measurement", f.loc_measurement.as_ref().map(|l| &l.identifier_list)),
+            ("def_characteristic", "Calibratable", f.def_characteristic.as_ref().map(|l| &l.identifier_list)),
+            ("ref_characteristic", "Calibratable", f.ref_characteristic.as_ref().map(|l| &l.identifier_list)),
+            ("sub_function", "Function", f.sub_function.as_ref().map(|l| &l.identifier_list)),
+        ];
+        for (field, target_kind, list) in lists {
+            for target in list.into_iter().flatten() {
+                visit("Function", name, field, target_kind, target);
+            }
+        }
+    }
+    for g in module.group.iter() {
+        let name = g.get_name();
+        let lists = [
+            ("ref_measurement", "Measurement", g.ref_measurement.as_ref().map(|l| &l.identifier_list)),
+            ("ref_characteristic", "Calibratable", g.ref_characteristic.as_ref().map(|l| &l.identifier_list)),
+            ("sub_group", "Group", g.sub_group.as_ref().map(|l| &l.identifier_list)),
+            ("function_list", "Function", g.function_list.as_ref().map(|l| &l.name_list)),
+        ];
+        for (field, target_kind, list) in lists {
+            for target in list.into_iter().flatten() {
+                visit("Group", name, field, target_kind, target);
+            }
+        }
+    }
+    for frame in module.frame.iter() {
+        if let Some(frame_measurement) = &frame.frame_measurement {
+            for target in &frame_measurement.identifier_list {
+                visit("Frame", frame.get_name(), "frame_measurement", "Measurement", target);
+            }
+        }
+    }
+    for instance in module.instance.iter() {
+        visit("Instance", instance.get_name(), "type_ref", "Typedef", &instance.type_ref);
+    }
+    for typedef in module.typedef_characteristic.iter() {
+        let name = typedef.get_name();
+        visit("TypedefCharacteristic", name, "record_layout", "RecordLayout", &typedef.record_layout);
+        visit("TypedefCharacteristic", name, "conversion", "CompuMethod", &typedef.conversion);
+    }
+    for typedef in module.typedef_measurement.iter() {
+        visit("TypedefMeasurement", typedef.get_name(), "conversion", "CompuMethod", &typedef.conversion);
+    }
+    for typedef in module.typedef_axis.iter() {
+        let name = typedef.get_name();
+        visit("TypedefAxis", name, "record_layout", "RecordLayout", &typedef.record_layout);
+        visit("TypedefAxis", name, "conversion", "CompuMethod", &typedef.conversion);
+        visit("TypedefAxis", name, "input_quantity", "Measurement", &typedef.input_quantity);
+    }
+    for typedef in module.typedef_structure.iter() {
+        for component in typedef.structure_component.iter() {
+            visit("TypedefStructure", typedef.get_name(), "structure_component", "Typedef", &component.component_type);
+        }
+    }
+    for user_rights in module.user_rights.iter() {
+        for ref_group in &user_rights.ref_group {
+            for target in &ref_group.identifier_list {
+                visit("UserRights", &user_rights.user_level_id, "ref_group", "Group", target);
+            }
+        }
+    }
+    if let Some(variant_coding) = &module.variant_coding {
+        for characteristic in variant_coding.var_characteristic.iter() {
+            let name = characteristic.get_name();
+            visit("VarCharacteristic", name, "name", "Characteristic", name);
+        }
+        for criterion in variant_coding.var_criterion.iter() {
+            let name = criterion.get_name();
+            if let Some(var_measurement) = &criterion.var_measurement {
+                visit("VarCriterion", name, "var_measurement", "Measurement", &var_measurement.name);
+            }
+            if let Some(selection) = &criterion.var_selection_characteristic {
+                visit("VarCriterion", name, "var_selection_characteristic", "Characteristic", &selection.name);
+            }
+        }
+    }
+}
+
+pub(crate) fn is_null_reference(target: &str) -> bool {
+    NULL_REFERENCES.contains(&target)
+}
+
+pub(crate) fn check_module_references(
+    a2l: &a2lfile::A2lFile,
+    config: &ReferenceConfig,
+) -> Vec<ReferenceIssue> {
+    let modules: Vec<&a2lfile::Module> = a2l.project.module.iter().collect();
+    let mut issues = Vec::new();
+    for module in &modules {
+        let module_name = module.get_name();
+        for_each_reference(module, &mut |kind, name, field, target_kind, target| {
+            if is_null_reference(target) || target_exists(module, target_kind, target) {
+                return;
+            }
+            let site = ReferenceSite {
+                module: module_name.to_string(),
+                kind: kind.to_string(),
+                name: name.to_string(),
+                field: field.to_string(),
+                target_kind: target_kind.to_string(),
+                target: target.to_string(),
+            };
+            let other = modules
+                .iter()
+                .find(|other| other.get_name() != module_name && target_exists(other, target_kind, target))
+                .map(|other| other.get_name().to_string());
+            let (severity, message) = match (&other, config.allow_cross_module) {
+                (Some(_), true) => return,
+                (Some(other), false) => (
+                    "error",
+                    format!("{target_kind} '{target}' is only defined in module '{other}' and cross-module references are disabled"),
+                ),
+                (None, _) => ("error", format!("{target_kind} '{target}' does not exist")),
+            };
+            issues.push(ReferenceIssue {
+                site,
+                severity: severity.to_string(),
+                message,
+                resolved_module: other,
+            });
+        });
+        issues.extend(formula_issues(module));
+    }
+    issues
+}
+
+/// All places referring to the object `kind`/`name` that lives in
+/// `owner_module`. References from other modules are only reported when the
+/// configuration allows cross-module resolution and the referencing module
+/// does not define a same-named object of its own.
+pub(crate) fn collect_references(
+    a2l: &a2lfile::A2lFile,
+    owner_module: &str,
+    kind: &str,
+    name: &str,
+    config: &ReferenceConfig,
+) -> Vec<ReferenceSite> {
+    let mut sites = Vec::new();
+    for module in a2l.project.module.iter() {
+        let module_name = module.get_name();
+        let local = module_name == owner_module;
+        if !local && (!config.allow_cross_module || object_exists(module, kind, name)) {
+            continue;
+        }
+        for_each_reference(module, &mut |source_kind, source_name, field, target_kind, target| {
+            if target == name && target_candidates(target_kind).contains(&kind) {
+                sites.push(ReferenceSite {
+                    module: module_name.to_string(),
+                    kind: source_kind.to_string(),
+                    name: source_name.to_string(),
+                    field: field.to_string(),
+                    target_kind: target_kind.to_string(),
+                    target: target.to_string(),
+                });
+            }
+        });
+    }
+    sites
+}
+
+#[tauri::command]
+pub fn get_reference_config(state: tauri::State<AppState>) -> Result<ReferenceConfig, String> {
+    Ok(state.reference_config.lock().map_err(|_| "State lock poisoned")?.clone())
+}
+
+#[tauri::command]
+pub fn set_reference_config(config: ReferenceConfig, state: tauri::State<AppState>) -> Result<(), String> {
+    *state.reference_config.lock().map_err(|_| "State lock poisoned")? = config;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn check_references(doc_id: Option<String>, state: tauri::State<AppState>) -> Result<Vec<ReferenceIssue>, String> {
+    let config = state.reference_config.lock().map_err(|_| "State lock poisoned")?.clone();
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    Ok(check_module_references(a2l, &config))
+}
+
+#[tauri::command]
+pub fn find_references(
+    module_name: Option<String>,
+    kind: String,
+    name: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<ReferenceSite>, String> {
+    let config = state.reference_config.lock().map_err(|_| "State lock poisoned")?.clone();
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+
+    let owner = match module_name {
+        Some(module_name) => module_name,
+        None => a2l
+            .project
+            .module
+            .iter()
+            .find(|module| object_exists(module, &kind, &name))
+            .map(|module| module.get_name().to_string())
+            .ok_or_else(|| format!("{kind} '{name}' not found in any module"))?,
+    };
+    Ok(collect_references(a2l, &owner, &kind, &name, &config))
+}
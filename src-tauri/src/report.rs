@@ -0,0 +1,291 @@
+use std::fs;
+
+use a2lfile::A2lObjectName;
+use serde::{Deserialize, Serialize};
+
+use crate::axis_descr::attribute_to_string;
+use crate::{characteristic_type_to_string, datatype_to_string, AppState};
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct ReportOptions {
+    title: Option<String>,
+    /// Restricts the report to one module; all modules when empty.
+    module_name: Option<String>,
+    measurements: bool,
+    characteristics: bool,
+    conversions: bool,
+    /// Print layout: one page per section, no sticky navigation. Printing this
+    /// output from a browser produces the PDF deliverable.
+    printable: bool,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            title: None,
+            module_name: None,
+            measurements: true,
+            characteristics: true,
+            conversions: true,
+            printable: false,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ReportSummary {
+    modules: usize,
+    measurements: usize,
+    characteristics: usize,
+    conversions: usize,
+}
+
+const STYLE: &str = "body{font-family:sans-serif;margin:0;color:#222}\
+nav{position:fixed;top:0;bottom:0;width:220px;overflow:auto;background:#f4f4f4;padding:12px;font-size:13px}\
+nav a{display:block;color:#225;text-decoration:none;margin:2px 0}\
+main{margin-left:250px;padding:12px 24px}\
+table{border-collapse:collapse;width:100%;font-size:12px;margin-bottom:24px}\
+th,td{border:1px solid #ccc;padding:3px 6px;text-align:left;vertical-align:top}\
+th{background:#e8e8e8}td.num{text-align:right;font-family:monospace}";
+
+const PRINT_STYLE: &str = "body{font-family:sans-serif;color:#000}\
+nav{page-break-after:always}nav a{display:block;color:#000}\
+section{page-break-before:always}\
+table{border-collapse:collapse;width:100%;font-size:10px}\
+th,td{border:1px solid #999;padding:2px 4px;text-align:left;vertical-align:top}\
+thead{display:table-header-group}tr{page-break-inside:avoid}";
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn table(out: &mut String, headers: &[&str], rows: &[Vec<String>]) {
+    out.push_str("<table><thead><tr>");
+    for header in headers {
+        out.push_str(&format!("<th>{}</th>", escape(header)));
+    }
+    out.push_str("</tr></thead><tbody>\n");
+    for row in rows {
+        out.push_str("<tr>");
+        for cell in row {
+            out.push_str(&format!("<td>{}</td>", escape(cell)));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</tbody></table>\n");
+}
+
+fn limits(lower: f64, upper: f64) -> String {
+    format!("{lower} … {upper}")
+}
+
+fn measurement_rows(module: &a2lfile::Module) -> Vec<Vec<String>> {
+    module
+        .measurement
+        .iter()
+        .map(|m| {
+            vec![
+                m.get_name().to_string(),
+                m.long_identifier.clone(),
+                datatype_to_string(&m.datatype),
+                m.ecu_address.as_ref().map(|a| format!("0x{:X}", a.address)).unwrap_or_default(),
+                m.conversion.clone(),
+                m.phys_unit.as_ref().map(|u| u.unit.clone()).unwrap_or_default(),
+                limits(m.lower_limit, m.upper_limit),
+            ]
+        })
+        .collect()
+}
+
+fn characteristic_rows(module: &a2lfile::Module) -> Vec<Vec<String>> {
+    module
+        .characteristic
+        .iter()
+        .map(|c| {
+            let axes: Vec<String> = c
+                .axis_descr
+                .iter()
+                .map(|axis| {
+                    let reference = axis
+                        .axis_pts_ref
+                        .as_ref()
+                        .map(|r| r.axis_points.clone())
+                        .or_else(|| axis.curve_axis_ref.as_ref().map(|r| r.curve_axis.clone()));
+                    let mut text = format!(
+                        "{} [{}] {} ({})",
+                        attribute_to_string(&axis.attribute),
+                        axis.max_axis_points,
+                        axis.input_quantity,
+                        axis.conversion
+                    );
+                    if let Some(reference) = reference {
+                        text.push_str(&format!(" → {reference}"));
+                    }
+                    text
+                })
+                .collect();
+            vec![
+                c.get_name().to_string(),
+                c.long_identifier.clone(),
+                characteristic_type_to_string(&c.characteristic_type),
+                format!("0x{:X}", c.address),
+                c.deposit.clone(),
+                c.conversion.clone(),
+                limits(c.lower_limit, c.upper_limit),
+                axes.join("; "),
+            ]
+        })
+        .collect()
+}
+
+fn conversion_rows(module: &a2lfile::Module) -> Vec<Vec<String>> {
+    module
+        .compu_method
+        .iter()
+        .map(|cm| {
+            let definition = if let Some(c) = &cm.coeffs {
+                format!("RAT_FUNC a={} b={} c={} d={} e={} f={}", c.a, c.b, c.c, c.d, c.e, c.f)
+            } else if let Some(c) = &cm.coeffs_linear {
+                format!("LINEAR a={} b={}", c.a, c.b)
+            } else if let Some(formula) = &cm.formula {
+                formula.fx.clone()
+            } else if let Some(tab) = &cm.compu_tab_ref {
+                tab.conversion_table.clone()
+            } else {
+                String::new()
+            };
+            vec![
+                cm.get_name().to_string(),
+                cm.long_identifier.clone(),
+                format!("{:?}", cm.conversion_type),
+                cm.format.clone(),
+                cm.unit.clone(),
+                definition,
+            ]
+        })
+        .collect()
+}
+
+/// Renders the document as a self-contained HTML report.
+#[tauri::command]
+pub fn generate_report(
+    path: String,
+    options: Option<ReportOptions>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<ReportSummary, String> {
+    let options = options.unwrap_or_default();
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let modules: Vec<&a2lfile::Module> = a2l
+        .project
+        .module
+        .iter()
+        .filter(|m| options.module_name.as_deref().is_none_or(|name| m.get_name() == name))
+        .collect();
+    if modules.is_empty() {
+        return Err("No matching modules".to_string());
+    }
+
+    let title = options.title.clone().unwrap_or_else(|| a2l.project.get_name().to_string());
+    let mut summary = ReportSummary {
+        modules: modules.len(),
+        measurements: 0,
+        characteristics: 0,
+        conversions: 0,
+    };
+    let mut nav = String::from("<a href=\"#project\">Project</a>\n");
+    let mut body = String::new();
+
+    body.push_str(&format!("<section id=\"project\"><h1>{}</h1>\n", escape(&title)));
+    let mut project_rows = vec![
+        vec!["Project".to_string(), a2l.project.get_name().to_string()],
+        vec!["Description".to_string(), a2l.project.long_identifier.clone()],
+    ];
+    if let Some(version) = &a2l.asap2_version {
+        project_rows.push(vec![
+            "ASAP2 version".to_string(),
+            format!("{}.{}", version.version_no, version.upgrade_no),
+        ]);
+    }
+    if let Some(header) = &a2l.project.header {
+        project_rows.push(vec!["Comment".to_string(), header.comment.clone()]);
+        if let Some(version) = &header.version {
+            project_rows.push(vec!["Version".to_string(), version.version_identifier.clone()]);
+        }
+    }
+    for module in &modules {
+        project_rows.push(vec![
+            format!("Module {}", module.get_name()),
+            format!(
+                "{} measurements, {} characteristics, {} conversions",
+                module.measurement.len(),
+                module.characteristic.len(),
+                module.compu_method.len()
+            ),
+        ]);
+    }
+    table(&mut body, &["Property", "Value"], &project_rows);
+    body.push_str("</section>\n");
+
+    for module in &modules {
+        let id = module.get_name();
+        nav.push_str(&format!("<a href=\"#{0}\"><b>{1}</b></a>\n", escape(id), escape(id)));
+        body.push_str(&format!(
+            "<section id=\"{0}\"><h2>Module {1}</h2><p>{2}</p>\n",
+            escape(id),
+            escape(id),
+            escape(&module.long_identifier)
+        ));
+        if options.measurements {
+            nav.push_str(&format!("<a href=\"#{}-measurements\">Measurements</a>\n", escape(id)));
+            body.push_str(&format!("<h3 id=\"{}-measurements\">Measurements</h3>\n", escape(id)));
+            let rows = measurement_rows(module);
+            summary.measurements += rows.len();
+            table(
+                &mut body,
+                &["Name", "Description", "Datatype", "Address", "Conversion", "Unit", "Limits"],
+                &rows,
+            );
+        }
+        if options.characteristics {
+            nav.push_str(&format!("<a href=\"#{}-characteristics\">Characteristics</a>\n", escape(id)));
+            body.push_str(&format!("<h3 id=\"{}-characteristics\">Characteristics</h3>\n", escape(id)));
+            let rows = characteristic_rows(module);
+            summary.characteristics += rows.len();
+            table(
+                &mut body,
+                &["Name", "Description", "Type", "Address", "Record layout", "Conversion", "Limits", "Axes"],
+                &rows,
+            );
+        }
+        body.push_str("</section>\n");
+    }
+
+    if options.conversions {
+        nav.push_str("<a href=\"#conversions\">Conversion methods</a>\n");
+        body.push_str("<section id=\"conversions\"><h2>Appendix: conversion methods</h2>\n");
+        for module in &modules {
+            if modules.len() > 1 {
+                body.push_str(&format!("<h3>{}</h3>\n", escape(module.get_name())));
+            }
+            let rows = conversion_rows(module);
+            summary.conversions += rows.len();
+            table(&mut body, &["Name", "Description", "Type", "Format", "Unit", "Definition"], &rows);
+        }
+        body.push_str("</section>\n");
+    }
+
+    let style = if options.printable { PRINT_STYLE } else { STYLE };
+    let html = format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{}</title><style>{style}</style></head>\n\
+         <body><nav>\n{nav}</nav><main>\n{body}</main></body></html>\n",
+        escape(&title)
+    );
+    fs::write(&path, html).map_err(|e| e.to_string())?;
+    Ok(summary)
+}
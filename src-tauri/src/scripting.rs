@@ -0,0 +1,295 @@
+use std::cell::RefCell;
+use std::fs;
+use std::rc::Rc;
+
+use a2lfile::A2lObjectName;
+use rhai::{Array, Dynamic, Engine, EvalAltResult};
+use serde::Serialize;
+
+use crate::cleanup::remove_object;
+use crate::entity_copy::transfer;
+use crate::entity_source::module_body;
+use crate::references::{object_exists, object_names};
+use crate::{
+    build_metadata, characteristic_type_to_string, datatype_to_string, string_to_datatype, A2lMetadata, AppState,
+};
+
+/// Upper bound on evaluated operations so that a runaway loop cannot hang the app.
+const MAX_OPERATIONS: u64 = 50_000_000;
+
+#[derive(Serialize)]
+pub struct ScriptResult {
+    output: Vec<String>,
+    value: String,
+    metadata: A2lMetadata,
+}
+
+type Doc = Rc<RefCell<a2lfile::A2lFile>>;
+type ScriptError = Box<EvalAltResult>;
+
+fn get_field(module: &a2lfile::Module, kind: &str, name: &str, field: &str) -> Result<Option<Dynamic>, String> {
+    let symbol_link = |link: &Option<a2lfile::SymbolLink>| {
+        Dynamic::from(link.as_ref().map(|l| l.symbol_name.clone()).unwrap_or_default())
+    };
+    let value = match kind {
+        "Measurement" => {
+            let Some(m) = module.measurement.get(name) else { return Ok(None) };
+            match field {
+                "long_identifier" => Dynamic::from(m.long_identifier.clone()),
+                "datatype" => Dynamic::from(datatype_to_string(&m.datatype)),
+                "conversion" => Dynamic::from(m.conversion.clone()),
+                "lower_limit" => Dynamic::from_float(m.lower_limit),
+                "upper_limit" => Dynamic::from_float(m.upper_limit),
+                "address" => Dynamic::from_int(m.ecu_address.as_ref().map_or(0, |a| i64::from(a.address))),
+                "symbol_link" => symbol_link(&m.symbol_link),
+                other => return Err(format!("Unknown Measurement field: {other}")),
+            }
+        }
+        "Characteristic" => {
+            let Some(c) = module.characteristic.get(name) else { return Ok(None) };
+            match field {
+                "long_identifier" => Dynamic::from(c.long_identifier.clone()),
+                "type" => Dynamic::from(characteristic_type_to_string(&c.characteristic_type)),
+                "deposit" => Dynamic::from(c.deposit.clone()),
+                "conversion" => Dynamic::from(c.conversion.clone()),
+                "lower_limit" => Dynamic::from_float(c.lower_limit),
+                "upper_limit" => Dynamic::from_float(c.upper_limit),
+                "address" => Dynamic::from_int(i64::from(c.address)),
+                "symbol_link" => symbol_link(&c.symbol_link),
+                other => return Err(format!("Unknown Characteristic field: {other}")),
+            }
+        }
+        "AxisPts" => {
+            let Some(a) = module.axis_pts.get(name) else { return Ok(None) };
+            match field {
+                "long_identifier" => Dynamic::from(a.long_identifier.clone()),
+                "input_quantity" => Dynamic::from(a.input_quantity.clone()),
+                "deposit" => Dynamic::from(a.deposit_record.clone()),
+                "conversion" => Dynamic::from(a.conversion.clone()),
+                "lower_limit" => Dynamic::from_float(a.lower_limit),
+                "upper_limit" => Dynamic::from_float(a.upper_limit),
+                "address" => Dynamic::from_int(i64::from(a.address)),
+                "symbol_link" => symbol_link(&a.symbol_link),
+                other => return Err(format!("Unknown AxisPts field: {other}")),
+            }
+        }
+        "CompuMethod" => {
+            let Some(cm) = module.compu_method.get(name) else { return Ok(None) };
+            match field {
+                "long_identifier" => Dynamic::from(cm.long_identifier.clone()),
+                "format" => Dynamic::from(cm.format.clone()),
+                "unit" => Dynamic::from(cm.unit.clone()),
+                other => return Err(format!("Unknown CompuMethod field: {other}")),
+            }
+        }
+        other => return Err(format!("Fields of {other} are not accessible from scripts")),
+    };
+    Ok(Some(value))
+}
+
+fn as_string(value: Dynamic) -> Result<String, String> {
+    value.into_string().map_err(|t| format!("Expected a string, got {t}"))
+}
+
+fn as_float(value: Dynamic) -> Result<f64, String> {
+    value
+        .as_float()
+        .or_else(|_| value.as_int().map(|v| v as f64))
+        .map_err(|t| format!("Expected a number, got {t}"))
+}
+
+fn as_address(value: Dynamic) -> Result<u32, String> {
+    let address = value.as_int().map_err(|t| format!("Expected an integer, got {t}"))?;
+    u32::try_from(address).map_err(|_| format!("Address out of range: {address}"))
+}
+
+fn as_symbol_link(value: Dynamic) -> Result<Option<a2lfile::SymbolLink>, String> {
+    let name = as_string(value)?;
+    Ok((!name.is_empty()).then(|| a2lfile::SymbolLink::new(name, 0)))
+}
+
+/// Returns false if the object does not exist in `module`.
+fn set_field(module: &mut a2lfile::Module, kind: &str, name: &str, field: &str, value: Dynamic) -> Result<bool, String> {
+    match kind {
+        "Measurement" => {
+            let Some(m) = module.measurement.get_mut(name) else { return Ok(false) };
+            match field {
+                "long_identifier" => m.long_identifier = as_string(value)?,
+                "datatype" => {
+                    let text = as_string(value)?;
+                    m.datatype = string_to_datatype(&text).ok_or_else(|| format!("Invalid datatype: {text}"))?;
+                }
+                "conversion" => m.conversion = as_string(value)?,
+                "lower_limit" => m.lower_limit = as_float(value)?,
+                "upper_limit" => m.upper_limit = as_float(value)?,
+                "address" => m.ecu_address = Some(a2lfile::EcuAddress::new(as_address(value)?)),
+                "symbol_link" => m.symbol_link = as_symbol_link(value)?,
+                other => return Err(format!("Field {other} of Measurement is read-only or unknown")),
+            }
+        }
+        "Characteristic" => {
+            let Some(c) = module.characteristic.get_mut(name) else { return Ok(false) };
+            match field {
+                "long_identifier" => c.long_identifier = as_string(value)?,
+                "deposit" => c.deposit = as_string(value)?,
+                "conversion" => c.conversion = as_string(value)?,
+                "lower_limit" => c.lower_limit = as_float(value)?,
+                "upper_limit" => c.upper_limit = as_float(value)?,
+                "address" => c.address = as_address(value)?,
+                "symbol_link" => c.symbol_link = as_symbol_link(value)?,
+                other => return Err(format!("Field {other} of Characteristic is read-only or unknown")),
+            }
+        }
+        "AxisPts" => {
+            let Some(a) = module.axis_pts.get_mut(name) else { return Ok(false) };
+            match field {
+                "long_identifier" => a.long_identifier = as_string(value)?,
+                "input_quantity" => a.input_quantity = as_string(value)?,
+                "deposit" => a.deposit_record = as_string(value)?,
+                "conversion" => a.conversion = as_string(value)?,
+                "lower_limit" => a.lower_limit = as_float(value)?,
+                "upper_limit" => a.upper_limit = as_float(value)?,
+                "address" => a.address = as_address(value)?,
+                "symbol_link" => a.symbol_link = as_symbol_link(value)?,
+                other => return Err(format!("Field {other} of AxisPts is read-only or unknown")),
+            }
+        }
+        "CompuMethod" => {
+            let Some(cm) = module.compu_method.get_mut(name) else { return Ok(false) };
+            match field {
+                "long_identifier" => cm.long_identifier = as_string(value)?,
+                "format" => cm.format = as_string(value)?,
+                "unit" => cm.unit = as_string(value)?,
+                other => return Err(format!("Field {other} of CompuMethod is read-only or unknown")),
+            }
+        }
+        other => return Err(format!("Fields of {other} are not accessible from scripts")),
+    }
+    Ok(true)
+}
+
+fn remove(module: &mut a2lfile::Module, kind: &str, name: &str) -> bool {
+    if !object_exists(module, kind, name) {
+        return false;
+    }
+    remove_object(module, kind, name);
+    true
+}
+
+/// Registers the document API. Objects are looked up in all modules; the
+/// first module that has the object wins.
+fn register_api(engine: &mut Engine, doc: &Doc) {
+    let d = doc.clone();
+    engine.register_fn("modules", move || -> Array {
+        d.borrow().project.module.iter().map(|m| Dynamic::from(m.get_name().to_string())).collect()
+    });
+
+    let d = doc.clone();
+    engine.register_fn("names", move |kind: &str| -> Array {
+        let mut all = Array::new();
+        for module in d.borrow().project.module.iter() {
+            all.extend(object_names(module, kind).into_iter().map(Dynamic::from));
+        }
+        all
+    });
+
+    let d = doc.clone();
+    engine.register_fn("exists", move |kind: &str, name: &str| -> bool {
+        d.borrow().project.module.iter().any(|m| object_exists(m, kind, name))
+    });
+
+    let d = doc.clone();
+    engine.register_fn("get", move |kind: &str, name: &str, field: &str| -> Result<Dynamic, ScriptError> {
+        for module in d.borrow().project.module.iter() {
+            if let Some(value) = get_field(module, kind, name, field)? {
+                return Ok(value);
+            }
+        }
+        Err(format!("{kind} '{name}' not found").into())
+    });
+
+    let d = doc.clone();
+    engine.register_fn(
+        "set",
+        move |kind: &str, name: &str, field: &str, value: Dynamic| -> Result<(), ScriptError> {
+            for module in d.borrow_mut().project.module.iter_mut() {
+                if set_field(module, kind, name, field, value.clone())? {
+                    return Ok(());
+                }
+            }
+            Err(format!("{kind} '{name}' not found").into())
+        },
+    );
+
+    let d = doc.clone();
+    engine.register_fn("remove", move |kind: &str, name: &str| -> bool {
+        d.borrow_mut().project.module.iter_mut().any(|m| remove(m, kind, name))
+    });
+
+    let d = doc.clone();
+    engine.register_fn("source", move |kind: &str, name: &str| -> Result<String, ScriptError> {
+        for module in d.borrow().project.module.iter() {
+            let mut single = a2lfile::Module::new("SOURCE".to_string(), String::new());
+            if transfer(module, &mut single, kind, name) {
+                return Ok(module_body(single));
+            }
+        }
+        Err(format!("{kind} '{name}' not found").into())
+    });
+}
+
+/// Runs a Rhai script, read from the `.rhai` file `path` or given as `text`,
+/// against a copy of the document. The state lock is released while the
+/// script runs; the copy replaces the document only if the script finishes
+/// without error and the document was not changed in the meantime.
+#[tauri::command]
+pub fn run_script(
+    path: Option<String>,
+    text: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<ScriptResult, String> {
+    let source = match (path, text) {
+        (Some(path), None) => fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?,
+        (None, Some(text)) => text,
+        _ => return Err("Pass either a script path or script text".to_string()),
+    };
+
+    let (doc_id, revision, original) = {
+        let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+        let doc_id = guard.resolve_id(doc_id.as_deref())?.to_string();
+        let document = guard.document(Some(&doc_id))?;
+        (doc_id, document.revision, document.a2l.clone())
+    };
+
+    let doc: Doc = Rc::new(RefCell::new(original.clone()));
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    let out = output.clone();
+    engine.on_print(move |text| out.borrow_mut().push(text.to_string()));
+    let out = output.clone();
+    engine.on_debug(move |text, _, pos| out.borrow_mut().push(format!("[{pos}] {text}")));
+    register_api(&mut engine, &doc);
+
+    let value = engine.eval::<Dynamic>(&source).map_err(|e| e.to_string())?;
+    drop(engine);
+    let result = Rc::try_unwrap(doc).map_err(|_| "Script state still in use")?.into_inner();
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let document = guard.document(Some(&doc_id))?;
+    // A reload resets the revision, so the model is compared as well.
+    if document.revision != revision || document.a2l != original {
+        return Err("The document changed while the script was running; run it again".to_string());
+    }
+    let mut edit = guard.edit("run_script", Some(&doc_id))?;
+    if result != original {
+        edit.replace(result);
+    }
+    let output = output.borrow().clone();
+    Ok(ScriptResult {
+        output,
+        value: if value.is_unit() { String::new() } else { value.to_string() },
+        metadata: build_metadata(edit.a2l(), 0),
+    })
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+const SESSION_FILE: &str = "session.json";
+const MAX_RECENT_FILES: usize = 20;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RecentFile {
+    path: String,
+    kind: String,
+    opened_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Session {
+    recent_files: Vec<RecentFile>,
+    last_module: Option<String>,
+    column_layouts: HashMap<String, serde_json::Value>,
+    preferences: HashMap<String, serde_json::Value>,
+    file_settings: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+pub struct SessionUpdate {
+    last_module: Option<String>,
+    column_layouts: Option<HashMap<String, serde_json::Value>>,
+    preferences: Option<HashMap<String, serde_json::Value>>,
+    file_settings: Option<HashMap<String, serde_json::Value>>,
+}
+
+pub(crate) fn settings_path(app: &tauri::AppHandle, file_name: &str) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(file_name))
+}
+
+pub(crate) fn load_session(app: &tauri::AppHandle) -> Result<Session, String> {
+    let path = settings_path(app, SESSION_FILE)?;
+    match fs::read_to_string(&path) {
+        // A corrupt settings file must not keep the app from starting.
+        Ok(text) => Ok(serde_json::from_str(&text).unwrap_or_default()),
+        Err(_) => Ok(Session::default()),
+    }
+}
+
+pub(crate) fn store_session(app: &tauri::AppHandle, session: &Session) -> Result<(), String> {
+    let path = settings_path(app, SESSION_FILE)?;
+    let text = serde_json::to_string_pretty(session).map_err(|e| e.to_string())?;
+    fs::write(path, text).map_err(|e| e.to_string())
+}
+
+pub(crate) fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[tauri::command]
+pub fn get_session(app: tauri::AppHandle) -> Result<Session, String> {
+    load_session(&app)
+}
+
+#[tauri::command]
+pub fn update_session(update: SessionUpdate, app: tauri::AppHandle) -> Result<Session, String> {
+    let mut session = load_session(&app)?;
+    if let Some(last_module) = update.last_module {
+        session.last_module = (!last_module.is_empty()).then_some(last_module);
+    }
+    if let Some(column_layouts) = update.column_layouts {
+        session.column_layouts.extend(column_layouts);
+    }
+    if let Some(preferences) = update.preferences {
+        session.preferences.extend(preferences);
+    }
+    if let Some(file_settings) = update.file_settings {
+        session.file_settings.extend(file_settings);
+    }
+    store_session(&app, &session)?;
+    Ok(session)
+}
+
+#[tauri::command]
+pub fn add_recent_file(path: String, kind: String, app: tauri::AppHandle) -> Result<Session, String> {
+    if !matches!(kind.as_str(), "a2l" | "elf" | "map" | "hex") {
+        return Err(format!("Unknown file kind: {kind}"));
+    }
+    let mut session = load_session(&app)?;
+    session.recent_files.retain(|recent| recent.path != path);
+    session.recent_files.insert(
+        0,
+        RecentFile {
+            path,
+            kind,
+            opened_at: now_secs(),
+        },
+    );
+    session.recent_files.truncate(MAX_RECENT_FILES);
+    store_session(&app, &session)?;
+    Ok(session)
+}
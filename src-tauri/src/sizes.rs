@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use a2lfile::A2lObjectName;
+use serde::Serialize;
+
+use crate::layout::{characteristic_value_count, matrix_dim_count, object_size};
+use crate::references::object_exists;
+use crate::{find_module, AppState, ElfSymbol};
+
+#[derive(Serialize)]
+pub struct ObjectSize {
+    module: String,
+    kind: String,
+    name: String,
+    size: u32,
+    /// Values stored (MATRIX_DIM, ARRAY_SIZE, NUMBER or axis points); bytes
+    /// for blobs.
+    element_count: u32,
+}
+
+#[derive(Serialize)]
+pub struct MatrixDimConflict {
+    module: String,
+    kind: String,
+    name: String,
+    symbol: String,
+    matrix_dim: Vec<u16>,
+    size: u32,
+    symbol_size: u64,
+    /// Element count that would fill the symbol, if it divides evenly.
+    expected_elements: Option<u64>,
+}
+
+fn element_count(module: &a2lfile::Module, kind: &str, name: &str) -> Option<u32> {
+    match kind {
+        "Measurement" => {
+            let m = module.measurement.get(name)?;
+            matrix_dim_count(&m.matrix_dim, m.array_size.as_ref().map_or(1, |a| u32::from(a.number)))
+        }
+        "Characteristic" => characteristic_value_count(module.characteristic.get(name)?),
+        "AxisPts" => module.axis_pts.get(name).map(|a| u32::from(a.max_axis_points)),
+        "Blob" => module.blob.get(name).map(|b| b.size),
+        "Instance" => matrix_dim_count(&module.instance.get(name)?.matrix_dim, 1),
+        _ => None,
+    }
+}
+
+/// Byte size of an object from its datatype, MATRIX_DIM, ARRAY_SIZE, NUMBER,
+/// record layout or typedef. Without `module_name` the first module that has
+/// the object is used.
+#[tauri::command]
+pub fn get_object_size(
+    kind: String,
+    name: String,
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<ObjectSize, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let module = match module_name {
+        Some(_) => find_module(a2l, module_name.as_deref())?,
+        None => a2l
+            .project
+            .module
+            .iter()
+            .find(|module| object_exists(module, &kind, &name))
+            .ok_or_else(|| format!("{kind} '{name}' not found"))?,
+    };
+    if !object_exists(module, &kind, &name) {
+        return Err(format!("{kind} '{name}' not found"));
+    }
+    let size = object_size(module, &kind, &name).ok_or_else(|| {
+        format!("Size of {kind} '{name}' cannot be resolved (missing record layout or typedef, or overflow)")
+    })?;
+    Ok(ObjectSize {
+        module: module.get_name().to_string(),
+        element_count: element_count(module, &kind, &name).unwrap_or(1),
+        kind,
+        name,
+        size,
+    })
+}
+
+/// Objects with a MATRIX_DIM whose size differs from their ELF symbol, found
+/// through SYMBOL_LINK or, without one, by name. Symbols of unknown size and
+/// links with an offset are skipped.
+#[tauri::command]
+pub fn find_matrix_dim_conflicts(
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<MatrixDimConflict>, String> {
+    let elf = state.elf.lock().map_err(|_| "State lock poisoned")?;
+    let index = elf.as_ref().ok_or_else(|| "No ELF loaded".to_string())?;
+    let symbols: HashMap<&str, &ElfSymbol> = index.symbols().iter().rev().map(|s| (s.name.as_str(), s)).collect();
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+
+    let mut conflicts = Vec::new();
+    for module in a2l.project.module.iter() {
+        if module_name.as_deref().is_some_and(|name| module.get_name() != name) {
+            continue;
+        }
+        let mut candidates: Vec<(&str, &str, &Option<a2lfile::MatrixDim>, &Option<a2lfile::SymbolLink>)> = Vec::new();
+        candidates.extend(module.measurement.iter().map(|m| ("Measurement", m.get_name(), &m.matrix_dim, &m.symbol_link)));
+        candidates.extend(module.characteristic.iter().map(|c| ("Characteristic", c.get_name(), &c.matrix_dim, &c.symbol_link)));
+        candidates.extend(module.instance.iter().map(|i| ("Instance", i.get_name(), &i.matrix_dim, &i.symbol_link)));
+
+        for (kind, name, matrix_dim, link) in candidates {
+            let Some(matrix_dim) = matrix_dim.as_ref().filter(|dim| !dim.dim_list.is_empty()) else {
+                continue;
+            };
+            let symbol_name = match link {
+                Some(link) if link.offset != 0 => continue,
+                Some(link) => link.symbol_name.as_str(),
+                None => name,
+            };
+            let Some(symbol) = symbols.get(symbol_name).filter(|s| s.size > 0) else {
+                continue;
+            };
+            let Some(size) = object_size(module, kind, name) else {
+                continue;
+            };
+            if u64::from(size) == symbol.size {
+                continue;
+            }
+            let elements: u64 = matrix_dim.dim_list.iter().map(|&d| u64::from(d.max(1))).product();
+            let element_size = u64::from(size) / elements;
+            conflicts.push(MatrixDimConflict {
+                module: module.get_name().to_string(),
+                kind: kind.to_string(),
+                name: name.to_string(),
+                symbol: symbol_name.to_string(),
+                matrix_dim: matrix_dim.dim_list.clone(),
+                size,
+                symbol_size: symbol.size,
+                expected_elements: (element_size > 0 && symbol.size % element_size == 0)
+                    .then(|| symbol.size / element_size),
+            });
+        }
+    }
+    Ok(conflicts)
+}
@@ -0,0 +1,90 @@
+use serde::Serialize;
+
+use crate::session::now_secs;
+use crate::{build_metadata, A2lMetadata, AppState};
+
+/// Full in-memory copy of a document's model. Cloning is cheap compared to
+/// re-parsing and keeps restore independent of any later edits.
+pub(crate) struct Snapshot {
+    a2l: a2lfile::A2lFile,
+    created: u64,
+}
+
+#[derive(Serialize)]
+pub struct SnapshotInfo {
+    label: String,
+    created: u64,
+}
+
+#[tauri::command]
+pub fn create_snapshot(
+    label: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<SnapshotInfo, String> {
+    let label = label.trim().to_string();
+    if label.is_empty() {
+        return Err("Snapshot label must not be empty".to_string());
+    }
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let document = guard.document_mut(doc_id.as_deref())?;
+    let created = now_secs();
+    document.snapshots.insert(
+        label.clone(),
+        Snapshot {
+            a2l: document.a2l.clone(),
+            created,
+        },
+    );
+    Ok(SnapshotInfo { label, created })
+}
+
+/// Replaces the document model with the snapshot. The snapshot itself is kept
+/// so it can be restored again after further experiments.
+#[tauri::command]
+pub fn restore_snapshot(
+    label: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<A2lMetadata, String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard
+        .document(doc_id.as_deref())?
+        .snapshots
+        .get(&label)
+        .ok_or_else(|| format!("Snapshot '{label}' not found"))?
+        .a2l
+        .clone();
+    let mut edit = guard.edit("restore_snapshot", doc_id.as_deref())?;
+    if a2l != *edit.a2l() {
+        edit.replace(a2l);
+    }
+    Ok(build_metadata(edit.a2l(), 0))
+}
+
+#[tauri::command]
+pub fn delete_snapshot(label: String, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let document = guard.document_mut(doc_id.as_deref())?;
+    document
+        .snapshots
+        .remove(&label)
+        .map(|_| ())
+        .ok_or_else(|| format!("Snapshot '{label}' not found"))
+}
+
+#[tauri::command]
+pub fn list_snapshots(doc_id: Option<String>, state: tauri::State<AppState>) -> Result<Vec<SnapshotInfo>, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let document = guard.document(doc_id.as_deref())?;
+    let mut snapshots: Vec<SnapshotInfo> = document
+        .snapshots
+        .iter()
+        .map(|(label, snapshot)| SnapshotInfo {
+            label: label.clone(),
+            created: snapshot.created,
+        })
+        .collect();
+    snapshots.sort_by_key(|snapshot| snapshot.created);
+    Ok(snapshots)
+}
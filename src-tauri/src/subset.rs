@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+use std::fs;
+
+use a2lfile::A2lObjectName;
+use serde::Serialize;
+
+use crate::entity_copy::{dependency_closure, transfer};
+use crate::references::{object_exists, target_candidates};
+use crate::working_sets::working_set_members;
+use crate::{export_options, find_module, AppState};
+
+/// Reference kinds followed when building a subset. Unlike `copy_entities`,
+/// input quantities and functions are included so the result is self-contained.
+const SUBSET_TARGETS: &[&str] = &[
+    "CompuMethod",
+    "CompuTab",
+    "CompuVtab",
+    "RecordLayout",
+    "Unit",
+    "AxisPts",
+    "Characteristic",
+    "Measurement",
+    "Function",
+    "Typedef",
+];
+
+#[derive(Serialize)]
+pub struct SubsetResult {
+    counts: BTreeMap<String, usize>,
+    missing: Vec<String>,
+}
+
+/// Members of `group` and all of its sub groups, plus the groups themselves.
+pub(crate) fn group_members(module: &a2lfile::Module, group: &str, seeds: &mut Vec<(String, String)>) -> Result<(), String> {
+    let g = module
+        .group
+        .get(group)
+        .ok_or_else(|| format!("Group {group} not found"))?;
+    if seeds.iter().any(|(kind, name)| kind == "Group" && name == group) {
+        return Ok(());
+    }
+    seeds.push(("Group".to_string(), group.to_string()));
+    if let Some(ref_measurement) = &g.ref_measurement {
+        for name in &ref_measurement.identifier_list {
+            seeds.push(("Measurement".to_string(), name.clone()));
+        }
+    }
+    if let Some(ref_characteristic) = &g.ref_characteristic {
+        for name in &ref_characteristic.identifier_list {
+            seeds.push((resolve_kind(module, "Calibratable", name), name.clone()));
+        }
+    }
+    if let Some(sub_group) = &g.sub_group {
+        for name in &sub_group.identifier_list {
+            group_members(module, name, seeds)?;
+        }
+    }
+    Ok(())
+}
+
+fn resolve_kind(module: &a2lfile::Module, target_kind: &str, name: &str) -> String {
+    target_candidates(target_kind)
+        .iter()
+        .find(|kind| object_exists(module, kind, name))
+        .unwrap_or(&"Object")
+        .to_string()
+}
+
+/// Drops list entries of functions and groups that point outside the subset.
+fn prune_member_lists(module: &mut a2lfile::Module) {
+    let exists = |module: &a2lfile::Module, kinds: &str, name: &str| {
+        target_candidates(kinds)
+            .iter()
+            .any(|kind| object_exists(module, kind, name))
+    };
+    let snapshot = module.clone();
+    let prune = |list: &mut Vec<String>, kinds: &str| list.retain(|name| exists(&snapshot, kinds, name));
+
+    for f in module.function.iter_mut() {
+        if let Some(l) = &mut f.in_measurement {
+            prune(&mut l.identifier_list, "Measurement");
+        }
+        if let Some(l) = &mut f.out_measurement {
+            prune(&mut l.identifier_list, "Measurement");
+        }
+        if let Some(l) = &mut f.loc_measurement {
+            prune(&mut l.identifier_list, "Measurement");
+        }
+        if let Some(l) = &mut f.def_characteristic {
+            prune(&mut l.identifier_list, "Calibratable");
+        }
+        if let Some(l) = &mut f.ref_characteristic {
+            prune(&mut l.identifier_list, "Calibratable");
+        }
+        if let Some(l) = &mut f.sub_function {
+            prune(&mut l.identifier_list, "Function");
+        }
+    }
+    for g in module.group.iter_mut() {
+        if let Some(l) = &mut g.ref_measurement {
+            prune(&mut l.identifier_list, "Measurement");
+        }
+        if let Some(l) = &mut g.ref_characteristic {
+            prune(&mut l.identifier_list, "Calibratable");
+        }
+        if let Some(l) = &mut g.sub_group {
+            prune(&mut l.identifier_list, "Group");
+        }
+        if let Some(l) = &mut g.function_list {
+            prune(&mut l.name_list, "Function");
+        }
+    }
+}
+
+/// Writes a new A2L with the selected objects (and/or the members of `group`
+/// and `working_set`) of one module, plus their transitive dependencies. Project header,
+/// MOD_PAR and MOD_COMMON are carried over unchanged.
+#[tauri::command]
+pub fn extract_subset(
+    names: Vec<String>,
+    group: Option<String>,
+    working_set: Option<String>,
+    path: String,
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<AppState>,
+) -> Result<SubsetResult, String> {
+    let working_set = working_set_members(&app, working_set.as_deref())?;
+    let options = state.export_options.lock().map_err(|_| "State lock poisoned")?.clone();
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let module = find_module(a2l, module_name.as_deref())?;
+
+    let mut seeds: Vec<(String, String)> = names
+        .iter()
+        .map(|name| (resolve_kind(module, "Object", name), name.clone()))
+        .collect();
+    if let Some(group) = &group {
+        group_members(module, group, &mut seeds)?;
+    }
+    // Working sets span modules; only members of this module are taken.
+    seeds.extend(
+        working_set
+            .into_iter()
+            .flatten()
+            .filter(|(kind, name)| object_exists(module, kind, name)),
+    );
+    if seeds.is_empty() {
+        return Err("Nothing selected".to_string());
+    }
+
+    let mut subset_module = a2lfile::Module::new(module.get_name().to_string(), module.long_identifier.clone());
+    subset_module.mod_par = module.mod_par.clone();
+    subset_module.mod_common = module.mod_common.clone();
+
+    let mut counts = BTreeMap::new();
+    let mut missing = Vec::new();
+    for (kind, name, _) in dependency_closure(module, &seeds, SUBSET_TARGETS) {
+        if transfer(module, &mut subset_module, &kind, &name) {
+            *counts.entry(kind).or_insert(0) += 1;
+        } else {
+            missing.push(name);
+        }
+    }
+    prune_member_lists(&mut subset_module);
+
+    let mut subset = a2lfile::A2lFile::new(a2lfile::Project::new(
+        a2l.project.get_name().to_string(),
+        a2l.project.long_identifier.clone(),
+    ));
+    subset.asap2_version = a2l.asap2_version.clone();
+    subset.a2ml_version = a2l.a2ml_version.clone();
+    subset.project.header = a2l.project.header.clone();
+    subset.project.module.push(subset_module);
+
+    fs::write(&path, export_options::render_a2l(&subset, &options)).map_err(|e| e.to_string())?;
+    Ok(SubsetResult { counts, missing })
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use a2lfile::A2lObjectName;
+use serde::Serialize;
+
+use crate::layout::{axis_pts_size, characteristic_size, instance_size, measurement_size};
+use crate::{find_module, AppState, ElfSymbol};
+
+#[derive(Serialize)]
+pub struct SymbolLinkIssue {
+    module: String,
+    kind: String,
+    name: String,
+    symbol: String,
+    /// "missing", "address" or "size".
+    issue: String,
+    expected: Option<String>,
+    actual: Option<String>,
+    fixed: bool,
+}
+
+#[derive(Serialize)]
+pub struct SymbolLinkReport {
+    checked: usize,
+    issues: Vec<SymbolLinkIssue>,
+}
+
+impl SymbolLinkReport {
+    pub(crate) fn has_missing(&self) -> bool {
+        self.issues.iter().any(|issue| issue.issue == "missing")
+    }
+}
+
+enum Fix {
+    Keep,
+    Rewrite(u32),
+    Unlink,
+}
+
+struct Checker<'a> {
+    symbols: HashMap<&'a str, &'a ElfSymbol>,
+    module: String,
+    fix: bool,
+    /// With `fix`, also remove links to missing symbols.
+    unlink_missing: bool,
+    report: SymbolLinkReport,
+}
+
+impl Checker<'_> {
+    fn issue(&mut self, kind: &str, name: &str, symbol: &str, issue: &str, values: (Option<String>, Option<String>), fixed: bool) {
+        let (expected, actual) = values;
+        self.report.issues.push(SymbolLinkIssue {
+            module: self.module.clone(),
+            kind: kind.to_string(),
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            issue: issue.to_string(),
+            expected,
+            actual,
+            fixed,
+        });
+    }
+
+    /// Compares one linked object with its symbol. Objects that extend past the
+    /// end of their symbol are only reported; a known size of 0 on either side
+    /// is not checked.
+    fn check(&mut self, kind: &str, name: &str, link: &a2lfile::SymbolLink, address: Option<u32>, size: Option<u32>) -> Fix {
+        self.report.checked += 1;
+        let Some(symbol) = self.symbols.get(link.symbol_name.as_str()).copied() else {
+            let unlink = self.fix && self.unlink_missing;
+            self.issue(kind, name, &link.symbol_name, "missing", (None, None), unlink);
+            return if unlink { Fix::Unlink } else { Fix::Keep };
+        };
+        let mut fix = Fix::Keep;
+        let expected = (symbol.address as i64 + i64::from(link.offset)) as u32;
+        if address != Some(expected) {
+            self.issue(
+                kind,
+                name,
+                &link.symbol_name,
+                "address",
+                (Some(format!("0x{:X}", expected)), address.map(|a| format!("0x{:X}", a))),
+                self.fix,
+            );
+            if self.fix {
+                fix = Fix::Rewrite(expected);
+            }
+        }
+        if let Some(size) = size.filter(|&size| size > 0 && symbol.size > 0) {
+            let end = i64::from(link.offset) + i64::from(size);
+            if end > symbol.size as i64 {
+                self.issue(
+                    kind,
+                    name,
+                    &link.symbol_name,
+                    "size",
+                    (Some(symbol.size.to_string()), Some(size.to_string())),
+                    false,
+                );
+            }
+        }
+        fix
+    }
+}
+
+fn check_module(checker: &mut Checker, module: &mut a2lfile::Module) {
+    checker.module = module.get_name().to_string();
+    let characteristic_sizes: Vec<Option<u32>> =
+        module.characteristic.iter().map(|c| characteristic_size(module, c)).collect();
+    let axis_pts_sizes: Vec<Option<u32>> = module.axis_pts.iter().map(|a| axis_pts_size(module, a)).collect();
+    let instance_sizes: Vec<Option<u32>> = module.instance.iter().map(|i| instance_size(module, i)).collect();
+
+    for m in module.measurement.iter_mut() {
+        let Some(link) = &m.symbol_link else { continue };
+        let address = m.ecu_address.as_ref().map(|a| a.address);
+        match checker.check("Measurement", m.get_name(), link, address, measurement_size(m)) {
+            Fix::Keep => {}
+            Fix::Rewrite(address) => m.ecu_address = Some(a2lfile::EcuAddress::new(address)),
+            Fix::Unlink => m.symbol_link = None,
+        }
+    }
+    for (c, size) in module.characteristic.iter_mut().zip(characteristic_sizes) {
+        let Some(link) = &c.symbol_link else { continue };
+        match checker.check("Characteristic", c.get_name(), link, Some(c.address), size) {
+            Fix::Keep => {}
+            Fix::Rewrite(address) => c.address = address,
+            Fix::Unlink => c.symbol_link = None,
+        }
+    }
+    for (a, size) in module.axis_pts.iter_mut().zip(axis_pts_sizes) {
+        let Some(link) = &a.symbol_link else { continue };
+        match checker.check("AxisPts", a.get_name(), link, Some(a.address), size) {
+            Fix::Keep => {}
+            Fix::Rewrite(address) => a.address = address,
+            Fix::Unlink => a.symbol_link = None,
+        }
+    }
+    for b in module.blob.iter_mut() {
+        let Some(link) = &b.symbol_link else { continue };
+        match checker.check("Blob", b.get_name(), link, Some(b.start_address), Some(b.size)) {
+            Fix::Keep => {}
+            Fix::Rewrite(address) => b.start_address = address,
+            Fix::Unlink => b.symbol_link = None,
+        }
+    }
+    for (i, size) in module.instance.iter_mut().zip(instance_sizes) {
+        let Some(link) = &i.symbol_link else { continue };
+        match checker.check("Instance", i.get_name(), link, Some(i.start_address), size) {
+            Fix::Keep => {}
+            Fix::Rewrite(address) => i.start_address = address,
+            Fix::Unlink => i.symbol_link = None,
+        }
+    }
+}
+
+/// Checks (and with `fix`, repairs) the SYMBOL_LINKs of `a2l` against `symbols`.
+/// Links to missing symbols are only removed with `unlink_missing`. Fails if
+/// `module_name` names no module.
+pub(crate) fn check_links(
+    a2l: &mut a2lfile::A2lFile,
+    symbols: &[ElfSymbol],
+    module_name: Option<&str>,
+    fix: bool,
+    unlink_missing: bool,
+) -> Result<SymbolLinkReport, String> {
+    if module_name.is_some() {
+        find_module(a2l, module_name)?;
+    }
+    let mut checker = Checker {
+        // First occurrence wins for duplicate (e.g. file-local) names.
+        symbols: symbols.iter().rev().map(|s| (s.name.as_str(), s)).collect(),
+        module: String::new(),
+        fix,
+        unlink_missing,
+        report: SymbolLinkReport {
+            checked: 0,
+            issues: Vec::new(),
+        },
+    };
+    for module in a2l.project.module.iter_mut() {
+        if module_name.is_none_or(|name| module.get_name() == name) {
+            check_module(&mut checker, module);
+        }
+    }
+    Ok(checker.report)
+}
+
+/// Verifies every SYMBOL_LINK against the loaded ELF (or other symbol source).
+/// With `fix`, mismatching addresses are rewritten from the symbol and links to
+/// missing symbols are removed; objects larger than their symbol are never
+/// changed.
+#[tauri::command]
+pub fn check_symbol_links(
+    module_name: Option<String>,
+    fix: Option<bool>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<SymbolLinkReport, String> {
+    let elf = state.elf.lock().map_err(|_| "State lock poisoned")?;
+    let index = elf.as_ref().ok_or_else(|| "No ELF loaded".to_string())?;
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("check_symbol_links", doc_id.as_deref())?;
+    let fix = fix.unwrap_or(false);
+    if fix {
+        edit.touch_all();
+    }
+    check_links(edit.a2l_mut(), index.symbols(), module_name.as_deref(), fix, fix)
+}
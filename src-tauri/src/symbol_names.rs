@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+/// Transformation pipeline from ELF symbol names to A2L identifiers. Steps run
+/// in field order: prefix stripping, replacements, project prefix, length
+/// limit.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct NamingRules {
+    /// Removed from the start of the symbol, first match only (e.g. `_g`, `m_`).
+    #[serde(default)]
+    strip_prefixes: Vec<String>,
+    /// Literal replacements applied in order (e.g. `::` -> `_`, `.` -> `_`).
+    #[serde(default)]
+    replacements: Vec<(String, String)>,
+    #[serde(default)]
+    prefix: String,
+    max_length: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct NamePreview {
+    symbol: String,
+    name: String,
+    truncated: bool,
+    /// The transformed name clashed with another one and was made unique.
+    deduplicated: bool,
+}
+
+impl NamePreview {
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+fn transform(rules: &NamingRules, symbol: &str) -> String {
+    let mut name = symbol;
+    if let Some(stripped) = rules
+        .strip_prefixes
+        .iter()
+        .filter(|prefix| !prefix.is_empty())
+        .find_map(|prefix| name.strip_prefix(prefix.as_str()))
+    {
+        name = stripped;
+    }
+    let mut name = name.to_string();
+    for (from, to) in rules.replacements.iter().filter(|(from, _)| !from.is_empty()) {
+        name = name.replace(from.as_str(), to);
+    }
+    format!("{}{}", rules.prefix, name)
+}
+
+fn truncate(name: &str, max_length: Option<usize>) -> String {
+    match max_length {
+        Some(max) if name.chars().count() > max => name.chars().take(max).collect(),
+        _ => name.to_string(),
+    }
+}
+
+/// Applies `rules` to all symbols. Names that collide with each other or with
+/// `existing` get a numeric suffix; the stem is shortened so that the name
+/// with its suffix still fits the length limit.
+pub(crate) fn apply_rules(rules: &NamingRules, symbols: &[String], existing: &HashSet<String>) -> Vec<NamePreview> {
+    let mut taken = existing.clone();
+    symbols
+        .iter()
+        .map(|symbol| {
+            let full = transform(rules, symbol);
+            let mut name = truncate(&full, rules.max_length);
+            let mut truncated = name != full;
+            let mut deduplicated = false;
+            let mut counter = 2;
+            while taken.contains(&name) {
+                let suffix = format!("_{counter}");
+                let stem_length = rules
+                    .max_length
+                    .map(|max| max.saturating_sub(suffix.chars().count()))
+                    .unwrap_or(usize::MAX);
+                let stem = truncate(&full, Some(stem_length));
+                truncated |= stem != full;
+                name = format!("{stem}{suffix}");
+                deduplicated = true;
+                counter += 1;
+            }
+            taken.insert(name.clone());
+            NamePreview {
+                symbol: symbol.clone(),
+                name,
+                truncated,
+                deduplicated,
+            }
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn preview_symbol_names(rules: NamingRules, symbols: Vec<String>) -> Vec<NamePreview> {
+    apply_rules(&rules, &symbols, &HashSet::new())
+}
@@ -0,0 +1,256 @@
+use std::fs;
+
+use pdb::FallibleIterator;
+
+use crate::elf_symbols::{ElfIndex, ElfSummary};
+use crate::{AppState, ElfSymbol};
+
+fn symbol(name: &str, address: u64, size: u64, bind: &str, type_str: &str, section: &str) -> ElfSymbol {
+    ElfSymbol {
+        name: name.to_string(),
+        address,
+        size,
+        bind: bind.to_string(),
+        type_str: type_str.to_string(),
+        section: section.to_string(),
+    }
+}
+
+fn parse_hex(text: &str) -> Option<u64> {
+    let clean = text.trim().replace('\'', "");
+    let digits = clean.strip_prefix("0x").or_else(|| clean.strip_prefix("0X")).unwrap_or(&clean);
+    u64::from_str_radix(digits, 16).ok()
+}
+
+fn is_identifier(text: &str) -> bool {
+    text.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '$' | ':'))
+}
+
+fn is_input_section(name: &str) -> bool {
+    name.starts_with('.') || name == "COMMON"
+}
+
+/// GNU ld map: symbol lines (`<address> <name>`) inside the memory map, sized
+/// by the distance to the next symbol of the same input section, the last one
+/// by the end of the input section from its size column.
+fn parse_gcc_map(text: &str) -> Vec<ElfSymbol> {
+    let mut symbols = Vec::new();
+    let mut section = String::new();
+    // Input section currently open: (start, end).
+    let mut input_section: Option<(u64, u64)> = None;
+    // An input section name too long for its column; address and size follow
+    // on the next line.
+    let mut wrapped_input = false;
+    let mut in_map = false;
+    let mut pending: Vec<ElfSymbol> = Vec::new();
+
+    let flush = |pending: &mut Vec<ElfSymbol>, end: Option<u64>, symbols: &mut Vec<ElfSymbol>| {
+        pending.sort_by_key(|s| s.address);
+        for i in 0..pending.len() {
+            let next = pending.get(i + 1).map(|s| s.address).or(end);
+            if let Some(next) = next {
+                pending[i].size = next.saturating_sub(pending[i].address);
+            }
+        }
+        symbols.append(pending);
+    };
+    let input_range = |address: &str, size: &str| parse_hex(address).zip(parse_hex(size)).map(|(a, s)| (a, a + s));
+
+    for line in text.lines() {
+        if line.starts_with("Linker script and memory map") {
+            in_map = true;
+            continue;
+        }
+        if !in_map {
+            continue;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let continues_input = std::mem::take(&mut wrapped_input);
+        match tokens.as_slice() {
+            // Second line of a wrapped input section: `   0xADDR   0xSIZE object.o`
+            [address, size, ..] if continues_input && address.starts_with("0x") && size.starts_with("0x") => {
+                input_section = input_range(address, size);
+            }
+            // Output section header: `.data 0x... 0x...` at column 0.
+            [name, ..] if name.starts_with('.') && !line.starts_with(' ') => {
+                flush(&mut pending, input_section.map(|(_, end)| end), &mut symbols);
+                input_section = None;
+                section = name.to_string();
+            }
+            // Input section: ` .data.foo 0xADDR 0xSIZE object.o`
+            [name, address, size, ..] if is_input_section(name) && line.starts_with(' ') => {
+                flush(&mut pending, input_section.map(|(_, end)| end), &mut symbols);
+                input_section = input_range(address, size);
+            }
+            // Input section with a long name: ` .data.a_very_long_name`
+            [name] if is_input_section(name) && line.starts_with(' ') => {
+                flush(&mut pending, input_section.map(|(_, end)| end), &mut symbols);
+                input_section = None;
+                wrapped_input = true;
+            }
+            // Symbol: `                0xADDR                name`
+            [address, name] if address.starts_with("0x") && is_identifier(name) => {
+                if let Some(address) = parse_hex(address) {
+                    pending.push(symbol(name, address, 0, "GLOBAL", "OBJECT", &section));
+                }
+            }
+            _ => {}
+        }
+    }
+    flush(&mut pending, input_section.map(|(_, end)| end), &mut symbols);
+    symbols
+}
+
+/// IAR ILINK map: the ENTRY LIST table (`name address size type scope object`).
+/// Long names wrap onto their own line.
+fn parse_iar_map(text: &str) -> Vec<ElfSymbol> {
+    let mut symbols = Vec::new();
+    let mut in_entries = false;
+    let mut wrapped_name: Option<String> = None;
+    for line in text.lines() {
+        if line.contains("ENTRY LIST") {
+            in_entries = true;
+            continue;
+        }
+        if !in_entries {
+            continue;
+        }
+        let mut tokens: Vec<String> = line.split_whitespace().map(str::to_string).collect();
+        if let Some(name) = wrapped_name.take() {
+            tokens.insert(0, name);
+        }
+        match tokens.as_slice() {
+            [name] if is_identifier(name) => wrapped_name = Some(name.clone()),
+            [name, address, size, kind, scope, ..] if is_identifier(name) => {
+                let Some(address) = parse_hex(address) else {
+                    continue;
+                };
+                let size = parse_hex(size).or_else(|| size.parse().ok()).unwrap_or(0);
+                let type_str = if kind == "Code" { "FUNC" } else { "OBJECT" };
+                let bind = if scope == "Lc" { "LOCAL" } else { "GLOBAL" };
+                symbols.push(symbol(name, address, size, bind, type_str, ""));
+            }
+            _ => {}
+        }
+    }
+    symbols
+}
+
+/// Green Hills map: `section address+size name` lines of the symbol listings.
+fn parse_ghs_map(text: &str) -> Vec<ElfSymbol> {
+    let mut symbols: Vec<ElfSymbol> = Vec::new();
+    for line in text.lines() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let [section, location, name, ..] = tokens.as_slice() else {
+            continue;
+        };
+        if !section.starts_with('.') || !is_identifier(name) {
+            continue;
+        }
+        let Some((address, size)) = location.split_once('+') else {
+            continue;
+        };
+        if let (Some(address), Some(size)) = (parse_hex(address), parse_hex(size)) {
+            let type_str = if section.starts_with(".text") { "FUNC" } else { "OBJECT" };
+            symbols.push(symbol(name, address, size, "GLOBAL", type_str, section));
+        }
+    }
+    symbols
+}
+
+/// Data and public symbols of a PDB, with addresses as RVAs.
+fn parse_pdb(path: &str) -> Result<Vec<ElfSymbol>, String> {
+    let file = fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut pdb = pdb::PDB::open(file).map_err(|e| e.to_string())?;
+    let address_map = pdb.address_map().map_err(|e| e.to_string())?;
+    let sections = pdb.sections().map_err(|e| e.to_string())?.unwrap_or_default();
+    let section_name = |section: u16| {
+        sections
+            .get(usize::from(section).wrapping_sub(1))
+            .map(|header| header.name().to_string())
+            .unwrap_or_default()
+    };
+
+    let global_symbols = pdb.global_symbols().map_err(|e| e.to_string())?;
+    let mut iter = global_symbols.iter();
+    let mut symbols = Vec::new();
+    while let Some(raw) = iter.next().map_err(|e| e.to_string())? {
+        let (name, offset, bind, type_str) = match raw.parse() {
+            Ok(pdb::SymbolData::Data(data)) => (
+                data.name.to_string(),
+                data.offset,
+                if data.global { "GLOBAL" } else { "LOCAL" },
+                "OBJECT",
+            ),
+            Ok(pdb::SymbolData::Public(public)) => (
+                public.name.to_string(),
+                public.offset,
+                "GLOBAL",
+                if public.function { "FUNC" } else { "OBJECT" },
+            ),
+            _ => continue,
+        };
+        let Some(rva) = offset.to_rva(&address_map) else {
+            continue;
+        };
+        // The symbol records carry no size; 0 leaves it unknown, which the
+        // size and symbol link checks skip.
+        symbols.push(symbol(&name, u64::from(rva.0), 0, bind, type_str, &section_name(offset.section)));
+    }
+    Ok(symbols)
+}
+
+fn detect_map_format(text: &str) -> Option<&'static str> {
+    if text.contains("ENTRY LIST") {
+        Some("iar")
+    } else if text.contains("Linker script and memory map") {
+        Some("gcc")
+    } else if text.contains("Global Symbols") || text.contains("Green Hills") {
+        Some("ghs")
+    } else {
+        None
+    }
+}
+
+pub(crate) fn read_symbol_file(path: &str, format: Option<&str>) -> Result<Vec<ElfSymbol>, String> {
+    let format = match format {
+        Some(format) if format != "auto" => format.to_lowercase(),
+        _ if path.to_lowercase().ends_with(".pdb") => "pdb".to_string(),
+        _ => String::new(),
+    };
+    let mut symbols = if format == "pdb" {
+        parse_pdb(path)?
+    } else {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let format = if format.is_empty() {
+            detect_map_format(&text).ok_or("Unrecognized map file format")?
+        } else {
+            format.as_str()
+        };
+        match format {
+            "gcc" => parse_gcc_map(&text),
+            "iar" => parse_iar_map(&text),
+            "ghs" => parse_ghs_map(&text),
+            other => return Err(format!("Unsupported symbol file format: {other}")),
+        }
+    };
+    symbols.sort_by(|a, b| a.name.cmp(&b.name).then(a.address.cmp(&b.address)));
+    symbols.dedup_by(|a, b| a.name == b.name && a.address == b.address);
+    Ok(symbols)
+}
+
+/// Loads a linker map (GCC, IAR, Green Hills) or PDB as the current symbol
+/// source, replacing a previously loaded ELF.
+#[tauri::command]
+pub fn load_symbol_file(
+    path: String,
+    format: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<ElfSummary, String> {
+    let symbols = read_symbol_file(&path, format.as_deref())?;
+    let index = ElfIndex::new(path, symbols);
+    let summary = index.summary();
+    *state.elf.lock().map_err(|_| "State lock poisoned")? = Some(index);
+    Ok(summary)
+}
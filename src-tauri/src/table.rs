@@ -0,0 +1,801 @@
+use std::collections::HashMap;
+use std::fs;
+
+use a2lfile::A2lObjectName;
+use calamine::{open_workbook_auto, Reader};
+use rust_xlsxwriter::Workbook;
+use serde::Serialize;
+
+use crate::references::{name_taken, object_exists};
+use crate::{
+    characteristic_type_to_string, datatype_to_string, string_to_characteristic_type,
+    string_to_datatype, AppState,
+};
+use crate::working_sets::working_set_members;
+
+pub(crate) const TABLE_COLUMNS: &[&str] = &[
+    "module",
+    "kind",
+    "name",
+    "long_identifier",
+    "type",
+    "address",
+    "datatype",
+    "conversion",
+    "unit",
+    "lower_limit",
+    "upper_limit",
+    "symbol_link",
+    "input_quantity",
+    "max_axis_points",
+];
+
+/// Kinds `import_entities_table` can create and patch.
+const IMPORT_KINDS: &[&str] = &["Measurement", "Characteristic", "AxisPts"];
+
+struct TableRow {
+    module: String,
+    kind: &'static str,
+    name: String,
+    long_identifier: String,
+    object_type: String,
+    address: Option<u32>,
+    datatype: String,
+    conversion: String,
+    unit: String,
+    lower_limit: f64,
+    upper_limit: f64,
+    symbol_link: Option<String>,
+    /// AXIS_PTS only.
+    input_quantity: String,
+    max_axis_points: Option<u16>,
+}
+
+impl TableRow {
+    fn cell(&self, column: &str) -> String {
+        match column {
+            "module" => self.module.clone(),
+            "kind" => self.kind.to_string(),
+            "name" => self.name.clone(),
+            "long_identifier" => self.long_identifier.clone(),
+            "type" => self.object_type.clone(),
+            "address" => self.address.map(|addr| format!("0x{addr:X}")).unwrap_or_default(),
+            "datatype" => self.datatype.clone(),
+            "conversion" => self.conversion.clone(),
+            "unit" => self.unit.clone(),
+            "lower_limit" => self.lower_limit.to_string(),
+            "upper_limit" => self.upper_limit.to_string(),
+            "symbol_link" => self.symbol_link.clone().unwrap_or_default(),
+            "input_quantity" => self.input_quantity.clone(),
+            "max_axis_points" => self.max_axis_points.map(|count| count.to_string()).unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+}
+
+fn resolve_unit(module: &a2lfile::Module, phys_unit: &Option<a2lfile::PhysUnit>, conversion: &str) -> String {
+    if let Some(phys_unit) = phys_unit {
+        return phys_unit.unit.clone();
+    }
+    module
+        .compu_method
+        .get(conversion)
+        .map(|compu_method| compu_method.unit.clone())
+        .unwrap_or_default()
+}
+
+fn collect_rows(a2l: &a2lfile::A2lFile, kinds: &[String]) -> Vec<TableRow> {
+    let wants = |kind: &str| kinds.is_empty() || kinds.iter().any(|k| k == kind);
+    let mut rows = Vec::new();
+    for module in a2l.project.module.iter() {
+        let module_name = module.get_name();
+        if wants("Measurement") {
+            for m in module.measurement.iter() {
+                rows.push(TableRow {
+                    module: module_name.to_string(),
+                    kind: "Measurement",
+                    name: m.get_name().to_string(),
+                    long_identifier: m.long_identifier.clone(),
+                    object_type: String::new(),
+                    address: m.ecu_address.as_ref().map(|a| a.address),
+                    datatype: datatype_to_string(&m.datatype),
+                    conversion: m.conversion.clone(),
+                    unit: resolve_unit(module, &m.phys_unit, &m.conversion),
+                    lower_limit: m.lower_limit,
+                    upper_limit: m.upper_limit,
+                    symbol_link: m.symbol_link.as_ref().map(|link| link.symbol_name.clone()),
+                    input_quantity: String::new(),
+                    max_axis_points: None,
+                });
+            }
+        }
+        if wants("Characteristic") {
+            for c in module.characteristic.iter() {
+                rows.push(TableRow {
+                    module: module_name.to_string(),
+                    kind: "Characteristic",
+                    name: c.get_name().to_string(),
+                    long_identifier: c.long_identifier.clone(),
+                    object_type: characteristic_type_to_string(&c.characteristic_type),
+                    address: Some(c.address),
+                    datatype: c.deposit.clone(),
+                    conversion: c.conversion.clone(),
+                    unit: resolve_unit(module, &c.phys_unit, &c.conversion),
+                    lower_limit: c.lower_limit,
+                    upper_limit: c.upper_limit,
+                    symbol_link: c.symbol_link.as_ref().map(|link| link.symbol_name.clone()),
+                    input_quantity: String::new(),
+                    max_axis_points: None,
+                });
+            }
+        }
+        if wants("AxisPts") {
+            for a in module.axis_pts.iter() {
+                rows.push(TableRow {
+                    module: module_name.to_string(),
+                    kind: "AxisPts",
+                    name: a.get_name().to_string(),
+                    long_identifier: a.long_identifier.clone(),
+                    object_type: String::new(),
+                    address: Some(a.address),
+                    datatype: a.deposit_record.clone(),
+                    conversion: a.conversion.clone(),
+                    unit: resolve_unit(module, &a.phys_unit, &a.conversion),
+                    lower_limit: a.lower_limit,
+                    upper_limit: a.upper_limit,
+                    symbol_link: a.symbol_link.as_ref().map(|link| link.symbol_name.clone()),
+                    input_quantity: a.input_quantity.clone(),
+                    max_axis_points: Some(a.max_axis_points),
+                });
+            }
+        }
+    }
+    rows
+}
+
+pub(crate) fn csv_escape(value: &str) -> String {
+    if value.contains([',', ';', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn write_csv(path: &str, columns: &[String], rows: &[TableRow]) -> Result<(), String> {
+    let mut out = String::new();
+    out.push_str(&columns.iter().map(|c| csv_escape(c)).collect::<Vec<_>>().join(","));
+    out.push('\n');
+    for row in rows {
+        let cells: Vec<String> = columns.iter().map(|c| csv_escape(&row.cell(c))).collect();
+        out.push_str(&cells.join(","));
+        out.push('\n');
+    }
+    fs::write(path, out).map_err(|e| e.to_string())
+}
+
+fn write_xlsx(path: &str, columns: &[String], rows: &[TableRow]) -> Result<(), String> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    for (col, column) in columns.iter().enumerate() {
+        worksheet
+            .write_string(0, col as u16, column)
+            .map_err(|e| e.to_string())?;
+    }
+    for (index, row) in rows.iter().enumerate() {
+        let row_no = index as u32 + 1;
+        for (col, column) in columns.iter().enumerate() {
+            let col_no = col as u16;
+            let result = match column.as_str() {
+                "lower_limit" => worksheet.write_number(row_no, col_no, row.lower_limit),
+                "upper_limit" => worksheet.write_number(row_no, col_no, row.upper_limit),
+                _ => worksheet.write_string(row_no, col_no, row.cell(column)),
+            };
+            result.map_err(|e| e.to_string())?;
+        }
+    }
+    workbook.save(path).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn export_entities_table(
+    path: String,
+    format: String,
+    kinds: Vec<String>,
+    columns: Vec<String>,
+    working_set: Option<String>,
+    doc_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<AppState>,
+) -> Result<usize, String> {
+    let working_set = working_set_members(&app, working_set.as_deref())?;
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+
+    let columns = if columns.is_empty() {
+        TABLE_COLUMNS.iter().map(|c| c.to_string()).collect()
+    } else {
+        columns
+    };
+    if let Some(unknown) = columns.iter().find(|c| !TABLE_COLUMNS.contains(&c.as_str())) {
+        return Err(format!("Unknown column: {unknown}"));
+    }
+
+    let mut rows = collect_rows(a2l, &kinds);
+    if let Some(members) = &working_set {
+        rows.retain(|row| members.iter().any(|(kind, name)| kind == row.kind && *name == row.name));
+    }
+    match format.to_lowercase().as_str() {
+        "csv" => write_csv(&path, &columns, &rows)?,
+        "xlsx" => write_xlsx(&path, &columns, &rows)?,
+        other => return Err(format!("Unsupported table format: {other}")),
+    }
+    Ok(rows.len())
+}
+
+#[derive(Serialize)]
+pub struct FieldChange {
+    field: String,
+    old_value: String,
+    new_value: String,
+}
+
+#[derive(Serialize)]
+pub struct PlannedChange {
+    action: String,
+    kind: String,
+    name: String,
+    changes: Vec<FieldChange>,
+}
+
+#[derive(Serialize)]
+pub struct TableImportResult {
+    dry_run: bool,
+    planned: Vec<PlannedChange>,
+    errors: Vec<String>,
+}
+
+/// Field delimiter of a CSV table: ';' if the header line has more semicolons
+/// than commas outside quotes, as in Excel exports for locales with a decimal
+/// comma, otherwise ','.
+fn csv_delimiter(text: &str) -> char {
+    let mut in_quotes = false;
+    let (mut commas, mut semicolons) = (0, 0);
+    for ch in text.chars() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => commas += 1,
+            ';' if !in_quotes => semicolons += 1,
+            '\n' if !in_quotes => break,
+            _ => {}
+        }
+    }
+    if semicolons > commas {
+        ';'
+    } else {
+        ','
+    }
+}
+
+pub(crate) fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let delimiter = csv_delimiter(text);
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ch if ch == delimiter && !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(ch),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|row| row.iter().any(|cell| !cell.trim().is_empty()));
+    rows
+}
+
+fn read_xlsx(path: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut workbook = open_workbook_auto(path).map_err(|e| e.to_string())?;
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or("Workbook has no worksheets")?
+        .map_err(|e| e.to_string())?;
+    Ok(range
+        .rows()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect())
+}
+
+pub(crate) fn parse_number_or_hex(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        u64::from_str_radix(hex, 16).ok()
+    } else {
+        value.parse::<u64>().ok()
+    }
+}
+
+type ImportRow = HashMap<String, String>;
+
+fn rows_to_records(
+    table: Vec<Vec<String>>,
+    mapping: &HashMap<String, String>,
+) -> Result<Vec<ImportRow>, String> {
+    let mut iter = table.into_iter();
+    let header = iter.next().ok_or("Table is empty")?;
+    let fields: Vec<String> = header
+        .iter()
+        .map(|column| {
+            let column = column.trim();
+            mapping
+                .get(column)
+                .cloned()
+                .unwrap_or_else(|| column.to_lowercase())
+        })
+        .collect();
+    if !fields.iter().any(|field| field == "name") {
+        return Err("Table has no 'name' column".to_string());
+    }
+    Ok(iter
+        .map(|row| {
+            fields
+                .iter()
+                .zip(row)
+                .filter(|(field, _)| TABLE_COLUMNS.contains(&field.as_str()))
+                .map(|(field, value)| (field.clone(), value.trim().to_string()))
+                .collect()
+        })
+        .collect())
+}
+
+fn record_change(changes: &mut Vec<FieldChange>, field: &str, old_value: String, new_value: &str) -> bool {
+    if old_value == new_value {
+        return false;
+    }
+    changes.push(FieldChange {
+        field: field.to_string(),
+        old_value,
+        new_value: new_value.to_string(),
+    });
+    true
+}
+
+/// Also accepts a decimal comma, as written by semicolon-separated tables.
+fn parse_limit(field: &str, value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .or_else(|_| value.replace(',', ".").parse::<f64>())
+        .map_err(|_| format!("Invalid {field}: {value}"))
+}
+
+/// Non-empty cells of `row` in column order, so that the reported changes do
+/// not depend on hash order.
+fn cells(row: &ImportRow) -> impl Iterator<Item = (&'static str, &String)> + '_ {
+    TABLE_COLUMNS
+        .iter()
+        .filter_map(|field| row.get(*field).filter(|value| !value.is_empty()).map(|value| (*field, value)))
+}
+
+fn parse_address(value: &str) -> Result<u32, String> {
+    let address = parse_number_or_hex(value).ok_or_else(|| format!("Invalid address: {value}"))?;
+    u32::try_from(address).map_err(|_| format!("Address {value} does not fit into 32 bits"))
+}
+
+fn patch_measurement(m: &mut a2lfile::Measurement, row: &ImportRow) -> Result<Vec<FieldChange>, String> {
+    let mut changes = Vec::new();
+    for (field, value) in cells(row) {
+        match field {
+            "long_identifier" => {
+                if record_change(&mut changes, field, m.long_identifier.clone(), value) {
+                    m.long_identifier = value.clone();
+                }
+            }
+            "address" => {
+                let old = m.ecu_address.as_ref().map(|a| format!("0x{:X}", a.address)).unwrap_or_default();
+                let address = parse_address(value)?;
+                if record_change(&mut changes, field, old, &format!("0x{address:X}")) {
+                    m.ecu_address = Some(a2lfile::EcuAddress::new(address));
+                }
+            }
+            "datatype" => {
+                let datatype = string_to_datatype(value).ok_or_else(|| format!("Invalid data type: {value}"))?;
+                if record_change(&mut changes, field, datatype_to_string(&m.datatype), &datatype_to_string(&datatype)) {
+                    m.datatype = datatype;
+                }
+            }
+            "conversion" => {
+                if record_change(&mut changes, field, m.conversion.clone(), value) {
+                    m.conversion = value.clone();
+                }
+            }
+            "unit" => {
+                let old = m.phys_unit.as_ref().map(|u| u.unit.clone()).unwrap_or_default();
+                if record_change(&mut changes, field, old, value) {
+                    m.phys_unit = Some(a2lfile::PhysUnit::new(value.clone()));
+                }
+            }
+            "lower_limit" => {
+                let limit = parse_limit(field, value)?;
+                if record_change(&mut changes, field, m.lower_limit.to_string(), &limit.to_string()) {
+                    m.lower_limit = limit;
+                }
+            }
+            "upper_limit" => {
+                let limit = parse_limit(field, value)?;
+                if record_change(&mut changes, field, m.upper_limit.to_string(), &limit.to_string()) {
+                    m.upper_limit = limit;
+                }
+            }
+            "symbol_link" => {
+                let old = m.symbol_link.as_ref().map(|l| l.symbol_name.clone()).unwrap_or_default();
+                if record_change(&mut changes, field, old, value) {
+                    m.symbol_link = Some(a2lfile::SymbolLink::new(value.clone(), 0));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(changes)
+}
+
+fn patch_characteristic(c: &mut a2lfile::Characteristic, row: &ImportRow) -> Result<Vec<FieldChange>, String> {
+    let mut changes = Vec::new();
+    for (field, value) in cells(row) {
+        match field {
+            "long_identifier" => {
+                if record_change(&mut changes, field, c.long_identifier.clone(), value) {
+                    c.long_identifier = value.clone();
+                }
+            }
+            "type" => {
+                let new_type = string_to_characteristic_type(value)
+                    .ok_or_else(|| format!("Invalid characteristic type: {value}"))?;
+                let old = characteristic_type_to_string(&c.characteristic_type);
+                if record_change(&mut changes, field, old, &characteristic_type_to_string(&new_type)) {
+                    c.characteristic_type = new_type;
+                }
+            }
+            "address" => {
+                let address = parse_address(value)?;
+                if record_change(&mut changes, field, format!("0x{:X}", c.address), &format!("0x{address:X}")) {
+                    c.address = address;
+                }
+            }
+            // The export writes the record layout into the datatype column.
+            "datatype" => {
+                if record_change(&mut changes, field, c.deposit.clone(), value) {
+                    c.deposit = value.clone();
+                }
+            }
+            "conversion" => {
+                if record_change(&mut changes, field, c.conversion.clone(), value) {
+                    c.conversion = value.clone();
+                }
+            }
+            "unit" => {
+                let old = c.phys_unit.as_ref().map(|u| u.unit.clone()).unwrap_or_default();
+                if record_change(&mut changes, field, old, value) {
+                    c.phys_unit = Some(a2lfile::PhysUnit::new(value.clone()));
+                }
+            }
+            "lower_limit" => {
+                let limit = parse_limit(field, value)?;
+                if record_change(&mut changes, field, c.lower_limit.to_string(), &limit.to_string()) {
+                    c.lower_limit = limit;
+                }
+            }
+            "upper_limit" => {
+                let limit = parse_limit(field, value)?;
+                if record_change(&mut changes, field, c.upper_limit.to_string(), &limit.to_string()) {
+                    c.upper_limit = limit;
+                }
+            }
+            "symbol_link" => {
+                let old = c.symbol_link.as_ref().map(|l| l.symbol_name.clone()).unwrap_or_default();
+                if record_change(&mut changes, field, old, value) {
+                    c.symbol_link = Some(a2lfile::SymbolLink::new(value.clone(), 0));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(changes)
+}
+
+fn parse_axis_points(value: &str) -> Result<u16, String> {
+    value
+        .parse::<u16>()
+        .map_err(|_| format!("Invalid max_axis_points: {value}"))
+}
+
+fn patch_axis_pts(a: &mut a2lfile::AxisPts, row: &ImportRow) -> Result<Vec<FieldChange>, String> {
+    let mut changes = Vec::new();
+    for (field, value) in cells(row) {
+        match field {
+            "long_identifier" => {
+                if record_change(&mut changes, field, a.long_identifier.clone(), value) {
+                    a.long_identifier = value.clone();
+                }
+            }
+            "address" => {
+                let address = parse_address(value)?;
+                if record_change(&mut changes, field, format!("0x{:X}", a.address), &format!("0x{address:X}")) {
+                    a.address = address;
+                }
+            }
+            // The export writes the record layout into the datatype column.
+            "datatype" => {
+                if record_change(&mut changes, field, a.deposit_record.clone(), value) {
+                    a.deposit_record = value.clone();
+                }
+            }
+            "conversion" => {
+                if record_change(&mut changes, field, a.conversion.clone(), value) {
+                    a.conversion = value.clone();
+                }
+            }
+            "unit" => {
+                let old = a.phys_unit.as_ref().map(|u| u.unit.clone()).unwrap_or_default();
+                if record_change(&mut changes, field, old, value) {
+                    a.phys_unit = Some(a2lfile::PhysUnit::new(value.clone()));
+                }
+            }
+            "lower_limit" => {
+                let limit = parse_limit(field, value)?;
+                if record_change(&mut changes, field, a.lower_limit.to_string(), &limit.to_string()) {
+                    a.lower_limit = limit;
+                }
+            }
+            "upper_limit" => {
+                let limit = parse_limit(field, value)?;
+                if record_change(&mut changes, field, a.upper_limit.to_string(), &limit.to_string()) {
+                    a.upper_limit = limit;
+                }
+            }
+            "symbol_link" => {
+                let old = a.symbol_link.as_ref().map(|l| l.symbol_name.clone()).unwrap_or_default();
+                if record_change(&mut changes, field, old, value) {
+                    a.symbol_link = Some(a2lfile::SymbolLink::new(value.clone(), 0));
+                }
+            }
+            "input_quantity" => {
+                if record_change(&mut changes, field, a.input_quantity.clone(), value) {
+                    a.input_quantity = value.clone();
+                }
+            }
+            "max_axis_points" => {
+                let count = parse_axis_points(value)?;
+                if record_change(&mut changes, field, a.max_axis_points.to_string(), &count.to_string()) {
+                    a.max_axis_points = count;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(changes)
+}
+
+fn new_measurement(name: &str, row: &ImportRow) -> Result<a2lfile::Measurement, String> {
+    let datatype = match row.get("datatype").filter(|v| !v.is_empty()) {
+        Some(value) => string_to_datatype(value).ok_or_else(|| format!("Invalid data type: {value}"))?,
+        None => a2lfile::DataType::Ubyte,
+    };
+    let mut m = a2lfile::Measurement::new(name.to_string(), datatype);
+    m.conversion = "NO_COMPU_METHOD".to_string();
+    m.resolution = 1;
+    Ok(m)
+}
+
+fn new_characteristic(name: &str, row: &ImportRow) -> Result<a2lfile::Characteristic, String> {
+    let deposit = row
+        .get("datatype")
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| format!("Characteristic '{name}' needs a record layout in the datatype column"))?;
+    let address = row
+        .get("address")
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| format!("Characteristic '{name}' needs an address"))?;
+    let address = parse_address(address)?;
+    Ok(a2lfile::Characteristic::new(
+        name.to_string(),
+        String::new(),
+        a2lfile::CharacteristicType::Value,
+        address,
+        deposit.clone(),
+        0.0,
+        "NO_COMPU_METHOD".to_string(),
+        0.0,
+        0.0,
+    ))
+}
+
+fn new_axis_pts(name: &str, row: &ImportRow) -> Result<a2lfile::AxisPts, String> {
+    let cell = |column: &str, what: &str| {
+        row.get(column)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| format!("AxisPts '{name}' needs {what}"))
+    };
+    let deposit_record = cell("datatype", "a record layout in the datatype column")?;
+    let address = parse_address(cell("address", "an address")?)?;
+    let max_axis_points = parse_axis_points(cell("max_axis_points", "max_axis_points")?)?;
+    let input_quantity = row
+        .get("input_quantity")
+        .filter(|v| !v.is_empty())
+        .cloned()
+        .unwrap_or_else(|| "NO_INPUT_QUANTITY".to_string());
+    Ok(a2lfile::AxisPts::new(
+        name.to_string(),
+        String::new(),
+        address,
+        input_quantity,
+        deposit_record.clone(),
+        0.0,
+        "NO_COMPU_METHOD".to_string(),
+        max_axis_points,
+        0.0,
+        0.0,
+    ))
+}
+
+fn apply_row(
+    a2l: &mut a2lfile::A2lFile,
+    module_name: Option<&str>,
+    default_kind: &str,
+    mode: &str,
+    row: &ImportRow,
+) -> Result<Option<PlannedChange>, String> {
+    let name = row.get("name").filter(|n| !n.is_empty()).ok_or("Row without name")?;
+    let kind = row
+        .get("kind")
+        .filter(|k| !k.is_empty())
+        .map(|k| k.as_str())
+        .unwrap_or(default_kind);
+    let row_module = row.get("module").filter(|m| !m.is_empty()).map(|m| m.as_str()).or(module_name);
+
+    let module = match row_module {
+        Some(module_name) => a2l
+            .project
+            .module
+            .iter_mut()
+            .find(|m| m.get_name() == module_name)
+            .ok_or(format!("Module {module_name} not found"))?,
+        None => a2l.project.module.first_mut().ok_or("No modules in project")?,
+    };
+    if !IMPORT_KINDS.contains(&kind) {
+        return Err(format!("Unsupported kind for import: {kind}"));
+    }
+    if mode != "patch" && !object_exists(module, kind, name) && name_taken(module, kind, name) {
+        return Err(format!("Name '{name}' is already used in module {}", module.get_name()));
+    }
+
+    let (action, changes) = match kind {
+        "Measurement" => match module.measurement.iter_mut().find(|m| m.get_name() == name) {
+            Some(m) if mode != "create" => {
+                // Patch a copy so that a row failing halfway leaves the object as it was.
+                let mut patched = m.clone();
+                let changes = patch_measurement(&mut patched, row)?;
+                *m = patched;
+                ("patch", changes)
+            }
+            Some(_) => return Err(format!("Measurement '{name}' already exists")),
+            None if mode == "patch" => return Err(format!("Measurement '{name}' not found")),
+            None => {
+                let mut m = new_measurement(name, row)?;
+                let changes = patch_measurement(&mut m, row)?;
+                module.measurement.push(m);
+                ("create", changes)
+            }
+        },
+        "Characteristic" => match module.characteristic.iter_mut().find(|c| c.get_name() == name) {
+            Some(c) if mode != "create" => {
+                let mut patched = c.clone();
+                let changes = patch_characteristic(&mut patched, row)?;
+                *c = patched;
+                ("patch", changes)
+            }
+            Some(_) => return Err(format!("Characteristic '{name}' already exists")),
+            None if mode == "patch" => return Err(format!("Characteristic '{name}' not found")),
+            None => {
+                let mut c = new_characteristic(name, row)?;
+                let changes = patch_characteristic(&mut c, row)?;
+                module.characteristic.push(c);
+                ("create", changes)
+            }
+        },
+        "AxisPts" => match module.axis_pts.iter_mut().find(|a| a.get_name() == name) {
+            Some(a) if mode != "create" => {
+                let mut patched = a.clone();
+                let changes = patch_axis_pts(&mut patched, row)?;
+                *a = patched;
+                ("patch", changes)
+            }
+            Some(_) => return Err(format!("AxisPts '{name}' already exists")),
+            None if mode == "patch" => return Err(format!("AxisPts '{name}' not found")),
+            None => {
+                let mut a = new_axis_pts(name, row)?;
+                let changes = patch_axis_pts(&mut a, row)?;
+                module.axis_pts.push(a);
+                ("create", changes)
+            }
+        },
+        other => return Err(format!("Unsupported kind for import: {other}")),
+    };
+
+    if action == "patch" && changes.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(PlannedChange {
+        action: action.to_string(),
+        kind: kind.to_string(),
+        name: name.clone(),
+        changes,
+    }))
+}
+
+#[tauri::command]
+pub fn import_entities_table(
+    path: String,
+    mode: String,
+    kind: Option<String>,
+    module_name: Option<String>,
+    mapping: Option<HashMap<String, String>>,
+    dry_run: bool,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<TableImportResult, String> {
+    if !matches!(mode.as_str(), "create" | "patch" | "upsert") {
+        return Err(format!("Unknown import mode: {mode}"));
+    }
+
+    let table = if path.to_lowercase().ends_with(".csv") {
+        parse_csv(&fs::read_to_string(&path).map_err(|e| e.to_string())?)
+    } else {
+        read_xlsx(&path)?
+    };
+    let records = rows_to_records(table, &mapping.unwrap_or_default())?;
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+
+    // Dry runs are planned against a scratch copy so that creations inside the
+    // same table (e.g. duplicate rows) are reported exactly as a real run would.
+    let mut scratch;
+    let mut edit;
+    let target = if dry_run {
+        scratch = guard.get(doc_id.as_deref())?.clone();
+        &mut scratch
+    } else {
+        edit = guard.edit("import_entities_table", doc_id.as_deref())?;
+        edit.touch_all();
+        edit.a2l_mut()
+    };
+
+    let default_kind = kind.as_deref().unwrap_or("Measurement");
+    let mut planned = Vec::new();
+    let mut errors = Vec::new();
+    for (index, record) in records.iter().enumerate() {
+        match apply_row(target, module_name.as_deref(), default_kind, &mode, record) {
+            Ok(Some(change)) => planned.push(change),
+            Ok(None) => {}
+            Err(error) => errors.push(format!("Row {}: {error}", index + 2)),
+        }
+    }
+
+    Ok(TableImportResult {
+        dry_run,
+        planned,
+        errors,
+    })
+}
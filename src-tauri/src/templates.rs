@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::mod_common::{byte_order_to_string, string_to_byte_order};
+use crate::references::object_exists;
+use crate::session::settings_path;
+use crate::AppState;
+
+const TEMPLATES_FILE: &str = "templates.json";
+
+/// Optional fields captured from an object; `None` fields are left untouched
+/// when the template is applied.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct EntityTemplate {
+    /// Measurement, Characteristic or AxisPts.
+    kind: String,
+    byte_order: Option<String>,
+    format: Option<String>,
+    phys_unit: Option<String>,
+    ref_memory_segment: Option<String>,
+    ecu_address_extension: Option<i16>,
+}
+
+#[derive(Serialize)]
+pub struct TemplateInfo {
+    name: String,
+    template: EntityTemplate,
+}
+
+fn load_templates(app: &tauri::AppHandle) -> Result<BTreeMap<String, EntityTemplate>, String> {
+    let path = settings_path(app, TEMPLATES_FILE)?;
+    match fs::read_to_string(&path) {
+        Ok(text) => serde_json::from_str(&text).map_err(|e| format!("{TEMPLATES_FILE}: {e}")),
+        Err(_) => Ok(BTreeMap::new()),
+    }
+}
+
+fn store_templates(app: &tauri::AppHandle, templates: &BTreeMap<String, EntityTemplate>) -> Result<(), String> {
+    let path = settings_path(app, TEMPLATES_FILE)?;
+    let text = serde_json::to_string_pretty(templates).map_err(|e| e.to_string())?;
+    fs::write(path, text).map_err(|e| e.to_string())
+}
+
+/// The template `name`, which must have been saved for `kind`. `None` when no
+/// template was requested.
+pub(crate) fn load_template(
+    app: &tauri::AppHandle,
+    name: Option<&str>,
+    kind: &str,
+) -> Result<Option<EntityTemplate>, String> {
+    let Some(name) = name.filter(|n| !n.is_empty()) else {
+        return Ok(None);
+    };
+    let template = load_templates(app)?
+        .remove(name)
+        .ok_or_else(|| format!("Template '{name}' not found"))?;
+    if template.kind != kind {
+        return Err(format!("Template '{name}' is for {} objects, not {kind}", template.kind));
+    }
+    Ok(Some(template))
+}
+
+macro_rules! capture {
+    ($item:expr, $kind:expr) => {
+        EntityTemplate {
+            kind: $kind.to_string(),
+            byte_order: $item.byte_order.as_ref().map(|bo| byte_order_to_string(&bo.byte_order)),
+            format: $item.format.as_ref().map(|f| f.format_string.clone()),
+            phys_unit: $item.phys_unit.as_ref().map(|u| u.unit.clone()),
+            ref_memory_segment: $item.ref_memory_segment.as_ref().map(|s| s.name.clone()),
+            ecu_address_extension: $item.ecu_address_extension.as_ref().map(|e| e.extension),
+        }
+    };
+}
+
+macro_rules! apply {
+    ($template:expr, $item:expr) => {{
+        let template = $template;
+        let item = $item;
+        if let Some(byte_order) = template.byte_order.as_deref().and_then(string_to_byte_order) {
+            item.byte_order = Some(a2lfile::ByteOrder::new(byte_order));
+        }
+        if let Some(format) = &template.format {
+            item.format = Some(a2lfile::Format::new(format.clone()));
+        }
+        if let Some(unit) = &template.phys_unit {
+            item.phys_unit = Some(a2lfile::PhysUnit::new(unit.clone()));
+        }
+        if let Some(segment) = &template.ref_memory_segment {
+            item.ref_memory_segment = Some(a2lfile::RefMemorySegment::new(segment.clone()));
+        }
+        if let Some(extension) = template.ecu_address_extension {
+            item.ecu_address_extension = Some(a2lfile::EcuAddressExtension::new(extension));
+        }
+    }};
+}
+
+impl EntityTemplate {
+    pub(crate) fn apply_to_measurement(&self, measurement: &mut a2lfile::Measurement) {
+        apply!(self, measurement);
+    }
+
+    pub(crate) fn apply_to_characteristic(&self, characteristic: &mut a2lfile::Characteristic) {
+        apply!(self, characteristic);
+    }
+}
+
+/// Saves the optional fields of `kind`/`name` under `template_name`,
+/// replacing a template of the same name.
+#[tauri::command]
+pub fn save_entity_template(
+    kind: String,
+    name: String,
+    template_name: String,
+    doc_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<AppState>,
+) -> Result<TemplateInfo, String> {
+    let template_name = template_name.trim().to_string();
+    if template_name.is_empty() {
+        return Err("Template name must not be empty".to_string());
+    }
+    let template = {
+        let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+        let a2l = guard.get(doc_id.as_deref())?;
+        let module = a2l
+            .project
+            .module
+            .iter()
+            .find(|module| object_exists(module, &kind, &name))
+            .ok_or_else(|| format!("{kind} '{name}' not found"))?;
+        match kind.as_str() {
+            "Measurement" => module.measurement.get(&name).map(|m| capture!(m, kind)),
+            "Characteristic" => module.characteristic.get(&name).map(|c| capture!(c, kind)),
+            "AxisPts" => module.axis_pts.get(&name).map(|a| capture!(a, kind)),
+            _ => return Err(format!("Templates are not supported for {kind}")),
+        }
+        .ok_or_else(|| format!("{kind} '{name}' not found"))?
+    };
+
+    let mut templates = load_templates(&app)?;
+    templates.insert(template_name.clone(), template.clone());
+    store_templates(&app, &templates)?;
+    Ok(TemplateInfo {
+        name: template_name,
+        template,
+    })
+}
+
+#[tauri::command]
+pub fn list_entity_templates(app: tauri::AppHandle) -> Result<Vec<TemplateInfo>, String> {
+    Ok(load_templates(&app)?
+        .into_iter()
+        .map(|(name, template)| TemplateInfo { name, template })
+        .collect())
+}
+
+#[tauri::command]
+pub fn delete_entity_template(template_name: String, app: tauri::AppHandle) -> Result<(), String> {
+    let mut templates = load_templates(&app)?;
+    if templates.remove(&template_name).is_none() {
+        return Err(format!("Template '{template_name}' not found"));
+    }
+    store_templates(&app, &templates)
+}
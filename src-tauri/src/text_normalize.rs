@@ -0,0 +1,220 @@
+use a2lfile::A2lObjectName;
+use serde::Serialize;
+
+use crate::AppState;
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum NormalizationMode {
+    Report,
+    Escapes,
+    Transliterate,
+    Strip,
+}
+
+impl NormalizationMode {
+    pub(crate) fn parse(mode: &str) -> Result<Self, String> {
+        match mode {
+            "report" => Ok(Self::Report),
+            "escapes" => Ok(Self::Escapes),
+            "transliterate" => Ok(Self::Transliterate),
+            "strip" => Ok(Self::Strip),
+            other => Err(format!("Unknown normalization mode: {other}")),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TextIssue {
+    kind: String,
+    name: String,
+    field: String,
+    problems: Vec<String>,
+    original: String,
+    normalized: String,
+}
+
+impl TextIssue {
+    /// One-line description reported as a load diagnostic.
+    pub(crate) fn message(&self, normalized: bool) -> String {
+        let action = if normalized { "normalized" } else { "found" };
+        format!("{} {} ({}): {} {action}", self.kind, self.name, self.field, self.problems.join(", "))
+    }
+}
+
+fn transliterate(ch: char) -> Option<&'static str> {
+    Some(match ch {
+        'ä' => "ae",
+        'ö' => "oe",
+        'ü' => "ue",
+        'Ä' => "Ae",
+        'Ö' => "Oe",
+        'Ü' => "Ue",
+        'ß' => "ss",
+        'à' | 'á' | 'â' | 'ã' | 'å' => "a",
+        'À' | 'Á' | 'Â' | 'Ã' | 'Å' => "A",
+        'è' | 'é' | 'ê' | 'ë' => "e",
+        'È' | 'É' | 'Ê' | 'Ë' => "E",
+        'ì' | 'í' | 'î' | 'ï' => "i",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ø' => "o",
+        'ù' | 'ú' | 'û' => "u",
+        'ç' => "c",
+        'Ç' => "C",
+        'ñ' => "n",
+        'Ñ' => "N",
+        'µ' | 'μ' => "u",
+        '°' => "deg",
+        '±' => "+/-",
+        '²' => "2",
+        '³' => "3",
+        '‘' | '’' | '´' | '`' => "'",
+        '“' | '”' | '„' => "\"",
+        '–' | '—' => "-",
+        '…' => "...",
+        '€' => "EUR",
+        'Ω' => "Ohm",
+        '×' => "x",
+        '\u{a0}' => " ",
+        _ => return None,
+    })
+}
+
+/// Returns the problems found in `text` and its normalized form under `mode`.
+pub(crate) fn normalize(text: &str, mode: NormalizationMode) -> (Vec<String>, String) {
+    let mut problems = Vec::new();
+    if text.contains("\\\"") || text.contains("\\\\") {
+        problems.push("escape sequences left in text".to_string());
+    }
+    if text.chars().any(|ch| ch.is_control()) {
+        problems.push("control characters".to_string());
+    }
+    if !text.is_ascii() {
+        problems.push("non-ASCII characters".to_string());
+    }
+    if mode == NormalizationMode::Report || problems.is_empty() {
+        return (problems, text.to_string());
+    }
+
+    let unescaped = text.replace("\\\"", "\"").replace("\\\\", "\\");
+    let mut out = String::with_capacity(unescaped.len());
+    for ch in unescaped.chars() {
+        if ch.is_control() {
+            if !out.ends_with(' ') {
+                out.push(' ');
+            }
+        } else if ch.is_ascii() || mode == NormalizationMode::Escapes {
+            out.push(ch);
+        } else if mode == NormalizationMode::Transliterate {
+            if let Some(replacement) = transliterate(ch) {
+                out.push_str(replacement);
+            }
+        }
+    }
+    (problems, out.trim_end().to_string())
+}
+
+fn annotations_mut(
+    annotations: &mut [a2lfile::Annotation],
+    visit: &mut dyn FnMut(&str, &mut String),
+) {
+    for (index, annotation) in annotations.iter_mut().enumerate() {
+        if let Some(label) = &mut annotation.annotation_label {
+            visit(&format!("annotation[{index}].label"), &mut label.label);
+        }
+        if let Some(origin) = &mut annotation.annotation_origin {
+            visit(&format!("annotation[{index}].origin"), &mut origin.origin);
+        }
+        if let Some(text) = &mut annotation.annotation_text {
+            for (line, value) in text.annotation_text.iter_mut().enumerate() {
+                visit(&format!("annotation[{index}].text[{line}]"), value);
+            }
+        }
+    }
+}
+
+/// Visits every free-text string of the file: long identifiers, comments
+/// and annotation contents.
+pub(crate) fn for_each_text_mut(
+    a2l: &mut a2lfile::A2lFile,
+    visit: &mut dyn FnMut(&str, &str, &str, &mut String),
+) {
+    let project_name = a2l.project.get_name().to_string();
+    visit("Project", &project_name, "long_identifier", &mut a2l.project.long_identifier);
+    if let Some(header) = &mut a2l.project.header {
+        visit("Project", &project_name, "header_comment", &mut header.comment);
+    }
+
+    macro_rules! long_identifiers {
+        ($list:expr, $kind:literal) => {
+            for item in $list.iter_mut() {
+                let name = item.get_name().to_string();
+                visit($kind, &name, "long_identifier", &mut item.long_identifier);
+            }
+        };
+    }
+    macro_rules! annotated {
+        ($list:expr, $kind:literal) => {
+            for item in $list.iter_mut() {
+                let name = item.get_name().to_string();
+                visit($kind, &name, "long_identifier", &mut item.long_identifier);
+                annotations_mut(&mut item.annotation, &mut |field, value| visit($kind, &name, field, value));
+            }
+        };
+    }
+
+    for module in a2l.project.module.iter_mut() {
+        let module_name = module.get_name().to_string();
+        visit("Module", &module_name, "long_identifier", &mut module.long_identifier);
+        annotated!(module.measurement, "Measurement");
+        annotated!(module.characteristic, "Characteristic");
+        annotated!(module.axis_pts, "AxisPts");
+        annotated!(module.function, "Function");
+        annotated!(module.group, "Group");
+        annotated!(module.blob, "Blob");
+        annotated!(module.instance, "Instance");
+        long_identifiers!(module.compu_method, "CompuMethod");
+        long_identifiers!(module.compu_tab, "CompuTab");
+        long_identifiers!(module.compu_vtab, "CompuVtab");
+        long_identifiers!(module.compu_vtab_range, "CompuVtabRange");
+        long_identifiers!(module.unit, "Unit");
+        long_identifiers!(module.frame, "Frame");
+        long_identifiers!(module.typedef_axis, "TypedefAxis");
+        long_identifiers!(module.typedef_blob, "TypedefBlob");
+        long_identifiers!(module.typedef_characteristic, "TypedefCharacteristic");
+        long_identifiers!(module.typedef_measurement, "TypedefMeasurement");
+        long_identifiers!(module.typedef_structure, "TypedefStructure");
+    }
+}
+
+pub(crate) fn normalize_file(a2l: &mut a2lfile::A2lFile, mode: NormalizationMode) -> Vec<TextIssue> {
+    let mut issues = Vec::new();
+    for_each_text_mut(a2l, &mut |kind, name, field, value| {
+        let (problems, normalized) = normalize(value, mode);
+        if problems.is_empty() {
+            return;
+        }
+        issues.push(TextIssue {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            field: field.to_string(),
+            problems,
+            original: value.clone(),
+            normalized: normalized.clone(),
+        });
+        if mode != NormalizationMode::Report {
+            *value = normalized;
+        }
+    });
+    issues
+}
+
+#[tauri::command]
+pub fn normalize_text_fields(mode: String, doc_id: Option<String>, state: tauri::State<AppState>) -> Result<Vec<TextIssue>, String> {
+    let mode = NormalizationMode::parse(&mode)?;
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("normalize_text_fields", doc_id.as_deref())?;
+    if mode != NormalizationMode::Report {
+        edit.touch_all();
+    }
+    let a2l = edit.a2l_mut();
+    Ok(normalize_file(a2l, mode))
+}
@@ -0,0 +1,304 @@
+use std::collections::HashSet;
+
+use a2lfile::A2lObjectName;
+use serde::Serialize;
+
+use crate::references::{name_taken, namespace, object_names, rename_object};
+use crate::{find_module_mut, AppState};
+
+const CHECKED_KINDS: &[&str] = &[
+    "Measurement",
+    "Characteristic",
+    "AxisPts",
+    "Blob",
+    "Instance",
+    "CompuMethod",
+    "CompuTab",
+    "CompuVtab",
+    "CompuVtabRange",
+    "RecordLayout",
+    "Unit",
+    "Function",
+    "Group",
+    "Frame",
+    "TypedefStructure",
+    "TypedefMeasurement",
+    "TypedefCharacteristic",
+    "TypedefAxis",
+    "TypedefBlob",
+];
+
+#[derive(Serialize, Clone, Copy)]
+pub struct ToolProfile {
+    id: &'static str,
+    name: &'static str,
+    max_identifier_length: usize,
+    ascii_only: bool,
+    /// False when the tool rejects keywords introduced with ASAP2 1.70.
+    supports_v17: bool,
+}
+
+const PROFILES: &[ToolProfile] = &[
+    ToolProfile {
+        id: "inca7",
+        name: "INCA 7.x",
+        max_identifier_length: 128,
+        ascii_only: true,
+        supports_v17: true,
+    },
+    ToolProfile {
+        id: "canape",
+        name: "CANape",
+        max_identifier_length: 1024,
+        ascii_only: true,
+        supports_v17: true,
+    },
+    ToolProfile {
+        id: "vision",
+        name: "Vision",
+        max_identifier_length: 32,
+        ascii_only: true,
+        supports_v17: false,
+    },
+];
+
+#[derive(Serialize)]
+pub struct CompatibilityIssue {
+    module: String,
+    kind: String,
+    name: String,
+    problems: Vec<String>,
+    /// Shortened ASCII name, if the name is the problem and it is free.
+    suggestion: Option<String>,
+    renamed: bool,
+}
+
+#[derive(Serialize)]
+pub struct CompatibilityReport {
+    profile: ToolProfile,
+    checked: usize,
+    issues: Vec<CompatibilityIssue>,
+    references_updated: usize,
+}
+
+fn name_problems(profile: &ToolProfile, name: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+    if name.chars().count() > profile.max_identifier_length {
+        problems.push(format!("longer than {} characters", profile.max_identifier_length));
+    }
+    if profile.ascii_only && !name.is_ascii() {
+        problems.push("contains non-ASCII characters".to_string());
+    }
+    problems
+}
+
+/// Keywords of the object that only exist since ASAP2 1.70.
+fn v17_keywords(module: &a2lfile::Module, kind: &str, name: &str) -> Vec<&'static str> {
+    let mut keywords = Vec::new();
+    match kind {
+        "Instance" => keywords.push("INSTANCE"),
+        "TypedefStructure" => keywords.push("TYPEDEF_STRUCTURE"),
+        "TypedefMeasurement" => keywords.push("TYPEDEF_MEASUREMENT"),
+        "TypedefCharacteristic" => keywords.push("TYPEDEF_CHARACTERISTIC"),
+        "TypedefAxis" => keywords.push("TYPEDEF_AXIS"),
+        "TypedefBlob" => keywords.push("TYPEDEF_BLOB"),
+        "Measurement" => {
+            if module.measurement.get(name).is_some_and(|m| m.model_link.is_some()) {
+                keywords.push("MODEL_LINK");
+            }
+        }
+        "Characteristic" => {
+            if let Some(c) = module.characteristic.get(name) {
+                if c.model_link.is_some() {
+                    keywords.push("MODEL_LINK");
+                }
+                if c.encoding.is_some() {
+                    keywords.push("ENCODING");
+                }
+            }
+        }
+        "AxisPts" => {
+            if module.axis_pts.get(name).is_some_and(|a| a.model_link.is_some()) {
+                keywords.push("MODEL_LINK");
+            }
+        }
+        "Blob" => {
+            if module.blob.get(name).is_some_and(|b| b.model_link.is_some()) {
+                keywords.push("MODEL_LINK");
+            }
+        }
+        _ => {}
+    }
+    keywords
+}
+
+/// `name` with non-ASCII characters replaced by '_'.
+fn to_ascii(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii() { c } else { '_' }).collect()
+}
+
+/// `name` made ASCII and cut to `max` characters; a numeric suffix keeps it
+/// unique.
+fn shorten(name: &str, max: usize, taken: impl Fn(&str) -> bool) -> Option<String> {
+    let ascii = to_ascii(name);
+    let base: String = ascii.chars().take(max).collect();
+    if !taken(&base) {
+        return Some(base);
+    }
+    (1..1000).find_map(|n| {
+        let suffix = format!("_{n}");
+        let stem: String = ascii.chars().take(max.checked_sub(suffix.len())?).collect();
+        let candidate = format!("{stem}{suffix}");
+        (!taken(&candidate)).then_some(candidate)
+    })
+}
+
+/// Keeps the original name visible in tools after shortening, made ASCII.
+/// Skipped if that is still no valid identifier for the profile or equals the
+/// new name. Only objects that have a DISPLAY_IDENTIFIER and none set yet are
+/// updated.
+fn preserve_display_identifier(
+    module: &mut a2lfile::Module,
+    profile: &ToolProfile,
+    kind: &str,
+    name: &str,
+    original: &str,
+) {
+    let display_name = to_ascii(original);
+    if display_name == name || !name_problems(profile, &display_name).is_empty() {
+        return;
+    }
+    let display_identifier = match kind {
+        "Measurement" => module.measurement.get_mut(name).map(|m| &mut m.display_identifier),
+        "Characteristic" => module.characteristic.get_mut(name).map(|c| &mut c.display_identifier),
+        "AxisPts" => module.axis_pts.get_mut(name).map(|a| &mut a.display_identifier),
+        _ => None,
+    };
+    if let Some(slot) = display_identifier.filter(|slot| slot.is_none()) {
+        *slot = Some(a2lfile::DisplayIdentifier::new(display_name));
+    }
+}
+
+#[tauri::command]
+pub fn list_tool_profiles() -> Vec<ToolProfile> {
+    PROFILES.to_vec()
+}
+
+/// Checks names and keywords against a downstream tool profile ("inca7",
+/// "canape", "vision"). `max_length` overrides the profile's identifier
+/// limit. With `apply_fixes`, offending names are shortened and made ASCII,
+/// references are updated and the original name is kept as DISPLAY_IDENTIFIER
+/// where the profile accepts it.
+/// Keyword problems are only reported; see `convert_a2l_version`.
+#[tauri::command]
+pub fn check_tool_compatibility(
+    profile: String,
+    max_length: Option<usize>,
+    apply_fixes: Option<bool>,
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<CompatibilityReport, String> {
+    let mut profile = *PROFILES
+        .iter()
+        .find(|p| p.id.eq_ignore_ascii_case(profile.trim()))
+        .ok_or_else(|| format!("Unknown tool profile: {profile}"))?;
+    if let Some(max_length) = max_length {
+        if max_length == 0 {
+            return Err("Maximum identifier length must be at least 1".to_string());
+        }
+        profile.max_identifier_length = max_length;
+    }
+    let apply_fixes = apply_fixes.unwrap_or(false);
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let mut report = CompatibilityReport {
+        profile,
+        checked: 0,
+        issues: Vec::new(),
+        references_updated: 0,
+    };
+
+    // Issue index, module, kind, old and new name of each suggested rename.
+    let mut renames = Vec::new();
+    for module in a2l.project.module.iter() {
+        if module_name.as_deref().is_some_and(|name| module.get_name() != name) {
+            continue;
+        }
+        let module_id = module.get_name().to_string();
+        let mut claimed = HashSet::new();
+        for kind in CHECKED_KINDS {
+            for name in object_names(module, kind) {
+                report.checked += 1;
+                let name_issue = name_problems(&profile, &name);
+                let mut problems = name_issue.clone();
+                if !profile.supports_v17 {
+                    problems.extend(
+                        v17_keywords(module, kind, &name)
+                            .into_iter()
+                            .map(|keyword| format!("{keyword} requires ASAP2 1.70")),
+                    );
+                }
+                if problems.is_empty() {
+                    continue;
+                }
+
+                let suggestion = if name_issue.is_empty() {
+                    None
+                } else {
+                    shorten(&name, profile.max_identifier_length, |candidate| {
+                        name_taken(module, kind, candidate)
+                            || claimed.contains(&(namespace(kind).to_string(), candidate.to_string()))
+                    })
+                };
+                if let Some(new_name) = &suggestion {
+                    claimed.insert((namespace(kind).to_string(), new_name.clone()));
+                    renames.push((report.issues.len(), module_id.clone(), *kind, name.clone(), new_name.clone()));
+                }
+                report.issues.push(CompatibilityIssue {
+                    module: module_id.clone(),
+                    kind: kind.to_string(),
+                    name,
+                    problems,
+                    suggestion,
+                    renamed: false,
+                });
+            }
+        }
+        for transformer in module.transformer.iter() {
+            report.checked += 1;
+            let mut problems = name_problems(&profile, transformer.get_name());
+            if !profile.supports_v17 {
+                problems.push("TRANSFORMER requires ASAP2 1.70".to_string());
+            }
+            if !problems.is_empty() {
+                report.issues.push(CompatibilityIssue {
+                    module: module_id.clone(),
+                    kind: "Transformer".to_string(),
+                    name: transformer.get_name().to_string(),
+                    problems,
+                    suggestion: None,
+                    renamed: false,
+                });
+            }
+        }
+    }
+
+    if apply_fixes && !renames.is_empty() {
+        let mut edit = guard.edit("check_tool_compatibility", doc_id.as_deref())?;
+        let modules: HashSet<&String> = renames.iter().map(|(_, module_id, ..)| module_id).collect();
+        for module_id in modules {
+            edit.touch_module_objects(module_id);
+        }
+        for (index, module_id, kind, name, new_name) in renames {
+            let module = find_module_mut(edit.a2l_mut(), Some(&module_id))?;
+            if let Some(count) = rename_object(module, kind, &name, &new_name) {
+                preserve_display_identifier(module, &profile, kind, &new_name, &name);
+                report.references_updated += count;
+                report.issues[index].renamed = true;
+            }
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,127 @@
+use std::fs;
+
+use a2lfile::A2lObjectName;
+use serde::Serialize;
+
+use crate::entity_source::parse_snippet;
+use crate::export_options::render_a2l;
+use crate::AppState;
+
+#[derive(Serialize)]
+pub struct ProtectedObject {
+    module: String,
+    kind: String,
+    name: String,
+}
+
+#[derive(Serialize)]
+pub struct ToolExportReport {
+    /// Write-protected objects whose exported copy received READ_ONLY or the
+    /// IF_DATA block.
+    protected_objects: Vec<ProtectedObject>,
+}
+
+fn is_write_protected(
+    calibration_access: &Option<a2lfile::CalibrationAccess>,
+    read_only: &Option<a2lfile::ReadOnly>,
+) -> bool {
+    if read_only.is_some() {
+        return true;
+    }
+    matches!(
+        calibration_access.as_ref().map(|access| access.calibration_access),
+        Some(a2lfile::CalibrationAccessEnum::NoCalibration)
+            | Some(a2lfile::CalibrationAccessEnum::NotInMcdSystem)
+    )
+}
+
+/// Parses the vendor IF_DATA block with the module's A2ML. Blocks that the
+/// A2ML does not declare are rejected, since the tools would ignore them.
+fn protection_block(module: &a2lfile::Module, text: &str) -> Result<a2lfile::IfData, String> {
+    let a2ml = module
+        .a2ml
+        .as_ref()
+        .ok_or_else(|| format!("Module {} has no A2ML to validate the IF_DATA block against", module.get_name()))?;
+    let parsed = parse_snippet(text, Some(&a2ml.a2ml_text))?;
+    let mut blocks = parsed.if_data.into_iter();
+    let block = match (blocks.next(), blocks.next()) {
+        (Some(block), None) => block,
+        _ => return Err("IF_DATA text must contain exactly one IF_DATA block".to_string()),
+    };
+    if !block.ifdata_valid {
+        return Err(format!(
+            "IF_DATA block does not conform to the A2ML of module {}",
+            module.get_name()
+        ));
+    }
+    Ok(block)
+}
+
+/// Marks every write-protected CHARACTERISTIC / AXIS_PTS of `module` with
+/// READ_ONLY, which both tools evaluate, and appends `block` unless the object
+/// already carries an identical one.
+fn protect_module(module: &mut a2lfile::Module, block: Option<&a2lfile::IfData>) -> Vec<ProtectedObject> {
+    let module_name = module.get_name().to_string();
+    let mut protected = Vec::new();
+    macro_rules! protect {
+        ($list:ident, $kind:literal) => {
+            for item in module.$list.iter_mut() {
+                if !is_write_protected(&item.calibration_access, &item.read_only) {
+                    continue;
+                }
+                let mut changed = false;
+                if item.read_only.is_none() {
+                    item.read_only = Some(a2lfile::ReadOnly::new());
+                    changed = true;
+                }
+                if let Some(block) = block.filter(|block| !item.if_data.contains(block)) {
+                    item.if_data.push(block.clone());
+                    changed = true;
+                }
+                if changed {
+                    protected.push(ProtectedObject {
+                        module: module_name.clone(),
+                        kind: $kind.to_string(),
+                        name: item.get_name().to_string(),
+                    });
+                }
+            }
+        };
+    }
+    protect!(characteristic, "Characteristic");
+    protect!(axis_pts, "AxisPts");
+    protected
+}
+
+/// Writes the document with write protection made explicit: every
+/// CHARACTERISTIC / AXIS_PTS whose CALIBRATION_ACCESS forbids calibration gets
+/// READ_ONLY, which INCA and CANape both honour. Vendor-specific IF_DATA flags
+/// are not generated, since their layout depends on the A2ML of the project;
+/// pass the block to add as `if_data_block` instead. The flags are set on a
+/// copy of the model, which is then rendered with the current export options;
+/// the open document is not changed.
+#[tauri::command]
+pub fn export_a2l_for_tool(
+    path: String,
+    if_data_block: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<ToolExportReport, String> {
+    let options = state.export_options.lock().map_err(|_| "State lock poisoned")?.clone();
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut copy = guard.get(doc_id.as_deref())?.clone();
+    drop(guard);
+
+    let block_text = if_data_block.filter(|block| !block.trim().is_empty());
+    let mut protected_objects = Vec::new();
+    for module in copy.project.module.iter_mut() {
+        let block = match &block_text {
+            Some(text) => Some(protection_block(module, text)?),
+            None => None,
+        };
+        protected_objects.extend(protect_module(module, block.as_ref()));
+    }
+    fs::write(&path, render_a2l(&copy, &options)).map_err(|e| e.to_string())?;
+
+    Ok(ToolExportReport { protected_objects })
+}
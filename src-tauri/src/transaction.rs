@@ -0,0 +1,229 @@
+use std::collections::{BTreeSet, HashSet};
+
+use a2lfile::A2lObjectName;
+use serde::{Deserialize, Serialize};
+
+use crate::cleanup::remove_object;
+use crate::entity_copy::transfer;
+use crate::entity_source::{parse_snippet, replace_object, single_object_name};
+use crate::references::{
+    check_module_references, name_taken, object_exists, object_names, rename_object, target_candidates, ReferenceIssue,
+};
+use crate::{build_metadata, find_module_mut, A2lMetadata, AppState};
+
+/// One step of `apply_transaction`. Objects are given as A2L source snippets,
+/// as in the source editor. Without `module_name`, update, delete and rename
+/// act on the first module that has the object and create uses the first module.
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Operation {
+    Create {
+        module_name: Option<String>,
+        kind: String,
+        source: String,
+    },
+    /// Replaces `name`; a different name in `source` renames the object and
+    /// its references.
+    Update {
+        module_name: Option<String>,
+        kind: String,
+        name: String,
+        source: String,
+    },
+    Delete {
+        module_name: Option<String>,
+        kind: String,
+        name: String,
+    },
+    Rename {
+        module_name: Option<String>,
+        kind: String,
+        name: String,
+        new_name: String,
+    },
+}
+
+#[derive(Serialize)]
+pub struct TransactionError {
+    /// Index of the failed operation; `None` for validation of the result.
+    operation: Option<usize>,
+    message: String,
+}
+
+#[derive(Serialize)]
+pub struct TransactionResult {
+    committed: bool,
+    errors: Vec<TransactionError>,
+    metadata: Option<A2lMetadata>,
+}
+
+fn module_with<'a>(
+    a2l: &'a mut a2lfile::A2lFile,
+    module_name: Option<&str>,
+    kind: &str,
+    name: &str,
+) -> Result<&'a mut a2lfile::Module, String> {
+    let module = match module_name {
+        Some(_) => find_module_mut(a2l, module_name)?,
+        None => a2l
+            .project
+            .module
+            .iter_mut()
+            .find(|module| object_exists(module, kind, name))
+            .ok_or_else(|| format!("{kind} '{name}' not found"))?,
+    };
+    if !object_exists(module, kind, name) {
+        return Err(format!("{kind} '{name}' not found"));
+    }
+    Ok(module)
+}
+
+fn module_a2ml(module: &a2lfile::Module) -> Option<String> {
+    module.a2ml.as_ref().map(|a2ml| a2ml.a2ml_text.clone())
+}
+
+fn apply_operation(a2l: &mut a2lfile::A2lFile, operation: &Operation) -> Result<(), String> {
+    match operation {
+        Operation::Create {
+            module_name,
+            kind,
+            source,
+        } => {
+            let module = find_module_mut(a2l, module_name.as_deref())?;
+            let parsed = parse_snippet(source, module_a2ml(module).as_deref())?;
+            let name = single_object_name(&parsed, kind)?;
+            if name_taken(module, kind, &name) {
+                return Err(format!("Name '{name}' is already used in module {}", module.get_name()));
+            }
+            transfer(&parsed, module, kind, &name);
+        }
+        Operation::Update {
+            module_name,
+            kind,
+            name,
+            source,
+        } => {
+            let module = module_with(a2l, module_name.as_deref(), kind, name)?;
+            let parsed = parse_snippet(source, module_a2ml(module).as_deref())?;
+            let new_name = single_object_name(&parsed, kind)?;
+            if new_name != *name {
+                if name_taken(module, kind, &new_name) {
+                    return Err(format!("Name '{new_name}' is already used in module {}", module.get_name()));
+                }
+                rename_object(module, kind, name, &new_name).ok_or_else(|| format!("{kind} cannot be renamed"))?;
+            }
+            replace_object(module, parsed, kind, &new_name);
+        }
+        Operation::Delete { module_name, kind, name } => {
+            let module = module_with(a2l, module_name.as_deref(), kind, name)?;
+            remove_object(module, kind, name);
+            if object_exists(module, kind, name) {
+                return Err(format!("{kind} objects cannot be deleted"));
+            }
+        }
+        Operation::Rename {
+            module_name,
+            kind,
+            name,
+            new_name,
+        } => {
+            if new_name.trim().is_empty() {
+                return Err("New name must not be empty".to_string());
+            }
+            let module = module_with(a2l, module_name.as_deref(), kind, name)?;
+            if name_taken(module, kind, new_name) {
+                return Err(format!("Name '{new_name}' is already used in module {}", module.get_name()));
+            }
+            rename_object(module, kind, name, new_name).ok_or_else(|| format!("{kind} cannot be renamed"))?;
+        }
+    }
+    Ok(())
+}
+
+fn issue_key(issue: &ReferenceIssue) -> String {
+    let site = &issue.site;
+    format!("{}/{}/{}/{}/{}", site.module, site.kind, site.name, site.field, site.target)
+}
+
+/// `module/name` of each name that several objects of the shared
+/// MEASUREMENT / CHARACTERISTIC / AXIS_PTS / BLOB / INSTANCE namespace use.
+fn namespace_clashes(a2l: &a2lfile::A2lFile) -> BTreeSet<String> {
+    let mut clashes = BTreeSet::new();
+    for module in a2l.project.module.iter() {
+        let mut seen = HashSet::new();
+        for kind in target_candidates("Object") {
+            for name in object_names(module, kind) {
+                if !seen.insert(name.clone()) {
+                    clashes.insert(format!("{}/{}", module.get_name(), name));
+                }
+            }
+        }
+    }
+    clashes
+}
+
+/// Applies all operations to a copy of the document and commits the copy only
+/// if every operation succeeded and no new reference errors or name clashes
+/// appeared. All errors are reported together; on failure the document is
+/// left untouched.
+#[tauri::command]
+pub fn apply_transaction(
+    operations: Vec<Operation>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<TransactionResult, String> {
+    let config = state.reference_config.lock().map_err(|_| "State lock poisoned")?.clone();
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let original = guard.get(doc_id.as_deref())?;
+    let mut working = original.clone();
+
+    let mut errors = Vec::new();
+    for (index, operation) in operations.iter().enumerate() {
+        if let Err(message) = apply_operation(&mut working, operation) {
+            errors.push(TransactionError {
+                operation: Some(index),
+                message,
+            });
+        }
+    }
+
+    // Broken references that existed before the transaction do not block it.
+    let existing: HashSet<String> = check_module_references(original, &config).iter().map(issue_key).collect();
+    for issue in check_module_references(&working, &config) {
+        if !existing.contains(&issue_key(&issue)) {
+            errors.push(TransactionError {
+                operation: None,
+                message: format!(
+                    "{} '{}' ({}): {}",
+                    issue.site.kind, issue.site.name, issue.site.field, issue.message
+                ),
+            });
+        }
+    }
+
+    let clashes_before = namespace_clashes(original);
+    for clash in namespace_clashes(&working).difference(&clashes_before) {
+        errors.push(TransactionError {
+            operation: None,
+            message: format!("Name {clash} is used by more than one object"),
+        });
+    }
+
+    if !errors.is_empty() {
+        return Ok(TransactionResult {
+            committed: false,
+            errors,
+            metadata: None,
+        });
+    }
+    let metadata = build_metadata(&working, 0);
+    let mut edit = guard.edit("apply_transaction", doc_id.as_deref())?;
+    if working != *edit.a2l() {
+        edit.replace(working);
+    }
+    Ok(TransactionResult {
+        committed: true,
+        errors,
+        metadata: Some(metadata),
+    })
+}
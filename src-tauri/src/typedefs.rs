@@ -0,0 +1,294 @@
+use std::collections::HashSet;
+
+use a2lfile::{A2lObjectName, A2lObjectNameSetter};
+use serde::{Deserialize, Serialize};
+
+use crate::layout::typedef_size;
+use crate::{find_module, find_module_mut, AppState};
+
+/// Upper bound for `expand_instance`, so that huge arrays of structures do
+/// not flood the frontend.
+const MAX_LEAVES: usize = 100_000;
+const MAX_DEPTH: usize = 32;
+
+#[derive(Serialize, Deserialize)]
+pub struct StructureComponentData {
+    name: String,
+    type_ref: String,
+    offset: u32,
+    matrix_dim: Vec<u16>,
+    symbol_type_link: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct InstanceLeaf {
+    path: String,
+    kind: String,
+    type_ref: String,
+    address: String,
+    size: Option<u32>,
+}
+
+fn leaf_kind(module: &a2lfile::Module, type_ref: &str) -> Option<&'static str> {
+    if module.typedef_measurement.get(type_ref).is_some() {
+        Some("Measurement")
+    } else if module.typedef_characteristic.get(type_ref).is_some() {
+        Some("Characteristic")
+    } else if module.typedef_axis.get(type_ref).is_some() {
+        Some("AxisPts")
+    } else if module.typedef_blob.get(type_ref).is_some() {
+        Some("Blob")
+    } else {
+        None
+    }
+}
+
+/// Size of one array element of `type_ref`. Only needed to address the
+/// elements after the first, so an unknown size is an error only then.
+fn array_stride(module: &a2lfile::Module, type_ref: &str, count: usize, path: &str) -> Result<u32, String> {
+    match typedef_size(module, type_ref) {
+        Some(size) => Ok(size),
+        None if count <= 1 => Ok(0),
+        None => Err(format!("Size of typedef {type_ref} is unknown (at {path})")),
+    }
+}
+
+/// Whether `type_ref` is `target` or a structure that contains `target`,
+/// directly or through other structures.
+fn contains_typedef(module: &a2lfile::Module, type_ref: &str, target: &str) -> bool {
+    let mut visited = HashSet::new();
+    let mut pending = vec![type_ref.to_string()];
+    while let Some(current) = pending.pop() {
+        if current == target {
+            return true;
+        }
+        if !visited.insert(current.clone()) {
+            continue;
+        }
+        if let Some(structure) = module.typedef_structure.get(&current) {
+            pending.extend(
+                structure
+                    .structure_component
+                    .iter()
+                    .map(|component| component.component_type.clone()),
+            );
+        }
+    }
+    false
+}
+
+/// Index suffixes (`[i][j]...`) of all elements of an array, last index
+/// varying fastest.
+fn array_indices(matrix_dim: &Option<a2lfile::MatrixDim>) -> Vec<String> {
+    let dims: Vec<u16> = matrix_dim
+        .as_ref()
+        .map(|dim| dim.dim_list.iter().copied().filter(|&d| d > 1).collect())
+        .unwrap_or_default();
+    let mut indices = vec![String::new()];
+    for dim in dims {
+        indices = indices
+            .into_iter()
+            .flat_map(|prefix| (0..dim).map(move |i| format!("{prefix}[{i}]")))
+            .collect();
+    }
+    indices
+}
+
+fn expand(
+    module: &a2lfile::Module,
+    path: &str,
+    type_ref: &str,
+    address: u32,
+    depth: usize,
+    leaves: &mut Vec<InstanceLeaf>,
+) -> Result<(), String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("Typedef nesting too deep at {path}"));
+    }
+    if leaves.len() >= MAX_LEAVES {
+        return Err(format!("Instance expands to more than {MAX_LEAVES} elements"));
+    }
+    if let Some(structure) = module.typedef_structure.get(type_ref) {
+        for component in structure.structure_component.iter() {
+            if contains_typedef(module, &component.component_type, type_ref) {
+                return Err(format!("Typedef {type_ref} contains itself (at {path})"));
+            }
+            let indices = array_indices(&component.matrix_dim);
+            let element_size = array_stride(module, &component.component_type, indices.len(), path)?;
+            for (index, suffix) in indices.iter().enumerate() {
+                let component_address = address
+                    .wrapping_add(component.address_offset)
+                    .wrapping_add(element_size * index as u32);
+                expand(
+                    module,
+                    &format!("{path}.{}{suffix}", component.get_name()),
+                    &component.component_type,
+                    component_address,
+                    depth + 1,
+                    leaves,
+                )?;
+            }
+        }
+        return Ok(());
+    }
+    let kind = leaf_kind(module, type_ref).ok_or_else(|| format!("Typedef {type_ref} not found (at {path})"))?;
+    leaves.push(InstanceLeaf {
+        path: path.to_string(),
+        kind: kind.to_string(),
+        type_ref: type_ref.to_string(),
+        address: format!("0x{:X}", address),
+        size: typedef_size(module, type_ref),
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_structure_components(
+    module_name: Option<String>,
+    typedef: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<StructureComponentData>, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let module = find_module(a2l, module_name.as_deref())?;
+    let structure = module
+        .typedef_structure
+        .get(&typedef)
+        .ok_or_else(|| format!("Typedef structure '{}' not found", typedef))?;
+    Ok(structure
+        .structure_component
+        .iter()
+        .map(|component| StructureComponentData {
+            name: component.get_name().to_string(),
+            type_ref: component.component_type.clone(),
+            offset: component.address_offset,
+            matrix_dim: component
+                .matrix_dim
+                .as_ref()
+                .map(|dim| dim.dim_list.clone())
+                .unwrap_or_default(),
+            symbol_type_link: component.symbol_type_link.as_ref().map(|link| link.symbol_type.clone()),
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub fn upsert_structure_component(
+    module_name: Option<String>,
+    typedef: String,
+    original_name: Option<String>,
+    data: StructureComponentData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("upsert_structure_component", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch(&module_id, "TypedefStructure", &typedef);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+    if contains_typedef(module, &data.type_ref, &typedef) {
+        return Err("A structure cannot contain itself".to_string());
+    }
+    if leaf_kind(module, &data.type_ref).is_none() && module.typedef_structure.get(&data.type_ref).is_none() {
+        return Err(format!("Typedef '{}' not found", data.type_ref));
+    }
+    let structure = module
+        .typedef_structure
+        .get_mut(&typedef)
+        .ok_or_else(|| format!("Typedef structure '{}' not found", typedef))?;
+
+    let lookup = original_name.clone().unwrap_or_else(|| data.name.clone());
+    if lookup != data.name && structure.structure_component.get(&data.name).is_some() {
+        return Err(format!("Component '{}' already exists", data.name));
+    }
+    if structure.structure_component.get(&lookup).is_none() {
+        if original_name.is_some() {
+            return Err(format!("Component '{}' not found", lookup));
+        }
+        structure.structure_component.push(a2lfile::StructureComponent::new(
+            lookup.clone(),
+            data.type_ref.clone(),
+            data.offset,
+        ));
+    }
+    let component = structure
+        .structure_component
+        .get_mut(&lookup)
+        .ok_or_else(|| format!("Component '{}' not found", lookup))?;
+    component.set_name(data.name);
+    component.component_type = data.type_ref;
+    component.address_offset = data.offset;
+    component.matrix_dim = (!data.matrix_dim.is_empty()).then(|| {
+        let mut matrix_dim = a2lfile::MatrixDim::new();
+        matrix_dim.dim_list = data.matrix_dim;
+        matrix_dim
+    });
+    component.symbol_type_link = data
+        .symbol_type_link
+        .map(|link| link.trim().to_string())
+        .filter(|link| !link.is_empty())
+        .map(a2lfile::SymbolTypeLink::new);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_structure_component(
+    module_name: Option<String>,
+    typedef: String,
+    name: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("delete_structure_component", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch(&module_id, "TypedefStructure", &typedef);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+    let structure = module
+        .typedef_structure
+        .get_mut(&typedef)
+        .ok_or_else(|| format!("Typedef structure '{}' not found", typedef))?;
+    let before = structure.structure_component.len();
+    structure.structure_component.retain(|component| component.get_name() != name);
+    if structure.structure_component.len() == before {
+        return Err(format!("Component '{}' not found", name));
+    }
+    Ok(())
+}
+
+/// Resolves an INSTANCE through its typedef tree into leaf elements with
+/// absolute addresses. Arrays (MATRIX_DIM on the instance or a component) are
+/// expanded element by element.
+#[tauri::command]
+pub fn expand_instance(
+    module_name: Option<String>,
+    instance_name: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<InstanceLeaf>, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let module = find_module(a2l, module_name.as_deref())?;
+    let instance = module
+        .instance
+        .get(&instance_name)
+        .ok_or_else(|| format!("Instance '{}' not found", instance_name))?;
+
+    let indices = array_indices(&instance.matrix_dim);
+    let element_size = array_stride(module, &instance.type_ref, indices.len(), &instance_name)?;
+    let mut leaves = Vec::new();
+    for (index, suffix) in indices.iter().enumerate() {
+        expand(
+            module,
+            &format!("{}{suffix}", instance.get_name()),
+            &instance.type_ref,
+            instance.start_address.wrapping_add(element_size * index as u32),
+            0,
+            &mut leaves,
+        )?;
+    }
+    Ok(leaves)
+}
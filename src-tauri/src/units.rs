@@ -0,0 +1,235 @@
+use a2lfile::A2lObjectName;
+use serde::{Deserialize, Serialize};
+
+use crate::references::{for_each_reference, rename_object};
+use crate::{find_module, find_module_mut, AppState};
+
+#[derive(Serialize, Deserialize)]
+pub struct UnitData {
+    name: String,
+    long_identifier: String,
+    display: String,
+    /// DERIVED or EXTENDED_SI.
+    unit_type: String,
+    ref_unit: Option<String>,
+    /// Length, mass, time, current, temperature, amount of substance, luminous intensity.
+    si_exponents: Option<[i16; 7]>,
+    /// (gradient, offset) relative to REF_UNIT or the SI base.
+    unit_conversion: Option<(f64, f64)>,
+}
+
+#[derive(Serialize)]
+pub struct UnitUsageIssue {
+    module: String,
+    kind: String,
+    name: String,
+    unit: String,
+}
+
+fn unit_type_to_string(unit_type: &a2lfile::UnitType) -> String {
+    match unit_type {
+        a2lfile::UnitType::Derived => "DERIVED",
+        a2lfile::UnitType::ExtendedSi => "EXTENDED_SI",
+    }
+    .to_string()
+}
+
+fn string_to_unit_type(s: &str) -> Option<a2lfile::UnitType> {
+    match s.to_uppercase().as_str() {
+        "DERIVED" => Some(a2lfile::UnitType::Derived),
+        "EXTENDED_SI" => Some(a2lfile::UnitType::ExtendedSi),
+        _ => None,
+    }
+}
+
+fn unit_data(unit: &a2lfile::Unit) -> UnitData {
+    UnitData {
+        name: unit.get_name().to_string(),
+        long_identifier: unit.long_identifier.clone(),
+        display: unit.display.clone(),
+        unit_type: unit_type_to_string(&unit.unit_type),
+        ref_unit: unit.ref_unit.as_ref().map(|r| r.unit.clone()),
+        si_exponents: unit.si_exponents.as_ref().map(|si| {
+            [
+                si.length,
+                si.mass,
+                si.time,
+                si.electric_current,
+                si.temperature,
+                si.amount_of_substance,
+                si.luminous_intensity,
+            ]
+        }),
+        unit_conversion: unit.unit_conversion.as_ref().map(|c| (c.gradient, c.offset)),
+    }
+}
+
+/// Checks `data` against the module; `current` is the unit being updated.
+fn validate(module: &a2lfile::Module, data: &UnitData, current: Option<&str>) -> Result<a2lfile::UnitType, String> {
+    if data.name.trim().is_empty() {
+        return Err("Unit name must not be empty".to_string());
+    }
+    if current != Some(data.name.as_str()) && module.unit.get(&data.name).is_some() {
+        return Err(format!("Unit '{}' already exists", data.name));
+    }
+    let unit_type =
+        string_to_unit_type(&data.unit_type).ok_or_else(|| format!("Invalid unit type: {}", data.unit_type))?;
+    if unit_type == a2lfile::UnitType::ExtendedSi && data.si_exponents.is_none() {
+        return Err("EXTENDED_SI units need SI_EXPONENTS".to_string());
+    }
+    if let Some(ref_unit) = data.ref_unit.as_deref().filter(|r| !r.is_empty()) {
+        if ref_unit == data.name || Some(ref_unit) == current {
+            return Err("A unit cannot refer to itself".to_string());
+        }
+        if module.unit.get(ref_unit).is_none() {
+            return Err(format!("Reference unit '{ref_unit}' not found"));
+        }
+    }
+    Ok(unit_type)
+}
+
+fn apply(unit: &mut a2lfile::Unit, data: UnitData, unit_type: a2lfile::UnitType) {
+    unit.long_identifier = data.long_identifier;
+    unit.display = data.display;
+    unit.unit_type = unit_type;
+    unit.ref_unit = data.ref_unit.filter(|r| !r.is_empty()).map(a2lfile::RefUnit::new);
+    unit.si_exponents = data
+        .si_exponents
+        .map(|[length, mass, time, current, temperature, amount, luminous]| {
+            a2lfile::SiExponents::new(length, mass, time, current, temperature, amount, luminous)
+        });
+    unit.unit_conversion = data
+        .unit_conversion
+        .map(|(gradient, offset)| a2lfile::UnitConversion::new(gradient, offset));
+}
+
+#[tauri::command]
+pub fn list_units(
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<UnitData>, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let module = find_module(a2l, module_name.as_deref())?;
+    Ok(module.unit.iter().map(unit_data).collect())
+}
+
+#[tauri::command]
+pub fn create_unit(
+    module_name: Option<String>,
+    data: UnitData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("create_unit", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch(&module_id, "Unit", &data.name);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+    let unit_type = validate(module, &data, None)?;
+
+    let mut unit = a2lfile::Unit::new(data.name.clone(), String::new(), String::new(), unit_type);
+    apply(&mut unit, data, unit_type);
+    module.unit.push(unit);
+    Ok(())
+}
+
+/// Updates a unit; a rename is propagated to REF_UNIT references.
+#[tauri::command]
+pub fn update_unit(
+    module_name: Option<String>,
+    name: String,
+    data: UnitData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("update_unit", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_with_referrers(&module_id, "Unit", &name);
+    edit.touch(&module_id, "Unit", &data.name);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+    if module.unit.get(&name).is_none() {
+        return Err(format!("Unit '{}' not found", name));
+    }
+    let unit_type = validate(module, &data, Some(&name))?;
+
+    let new_name = data.name.clone();
+    if new_name != name {
+        rename_object(module, "Unit", &name, &new_name);
+    }
+    if let Some(unit) = module.unit.get_mut(&new_name) {
+        apply(unit, data, unit_type);
+    }
+    Ok(())
+}
+
+/// Deletes a unit that is not referenced by another unit or conversion method.
+#[tauri::command]
+pub fn delete_unit(
+    module_name: Option<String>,
+    name: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("delete_unit", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch(&module_id, "Unit", &name);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+
+    let mut users = Vec::new();
+    for_each_reference(module, &mut |kind, source, _, target_kind, target| {
+        if target_kind == "Unit" && target == name {
+            users.push(format!("{kind} '{source}'"));
+        }
+    });
+    if !users.is_empty() {
+        return Err(format!("Unit '{}' is still referenced by {}", name, users.join(", ")));
+    }
+
+    let before = module.unit.len();
+    module.unit.retain(|unit| unit.get_name() != name);
+    if module.unit.len() == before {
+        return Err(format!("Unit '{}' not found", name));
+    }
+    Ok(())
+}
+
+/// PHYS_UNIT strings that match neither the name nor the display string of a
+/// UNIT in the same module.
+#[tauri::command]
+pub fn check_unit_usage(doc_id: Option<String>, state: tauri::State<AppState>) -> Result<Vec<UnitUsageIssue>, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let mut issues = Vec::new();
+    for module in a2l.project.module.iter() {
+        let defined = |unit: &str| module.unit.iter().any(|u| u.get_name() == unit || u.display == unit);
+        let mut check = |kind: &str, name: &str, phys_unit: &Option<a2lfile::PhysUnit>| {
+            if let Some(phys_unit) = phys_unit {
+                if !phys_unit.unit.is_empty() && !defined(&phys_unit.unit) {
+                    issues.push(UnitUsageIssue {
+                        module: module.get_name().to_string(),
+                        kind: kind.to_string(),
+                        name: name.to_string(),
+                        unit: phys_unit.unit.clone(),
+                    });
+                }
+            }
+        };
+        for m in module.measurement.iter() {
+            check("Measurement", m.get_name(), &m.phys_unit);
+        }
+        for c in module.characteristic.iter() {
+            check("Characteristic", c.get_name(), &c.phys_unit);
+        }
+        for a in module.axis_pts.iter() {
+            check("AxisPts", a.get_name(), &a.phys_unit);
+        }
+    }
+    Ok(issues)
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::subset::group_members;
+use crate::{find_module, find_module_mut, AppState};
+
+#[derive(Serialize, Deserialize)]
+pub struct UserRightsData {
+    user_level: String,
+    read_only: bool,
+    /// Groups of all REF_GROUP blocks; written back as a single REF_GROUP.
+    groups: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct EffectiveRights {
+    user_level: String,
+    read_only: bool,
+    /// Referenced groups and all of their sub groups.
+    groups: Vec<String>,
+    characteristics: Vec<String>,
+    axis_pts: Vec<String>,
+    measurements: Vec<String>,
+}
+
+fn user_rights_data(rights: &a2lfile::UserRights) -> UserRightsData {
+    UserRightsData {
+        user_level: rights.user_level_id.clone(),
+        read_only: rights.read_only.is_some(),
+        groups: rights
+            .ref_group
+            .iter()
+            .flat_map(|ref_group| ref_group.identifier_list.iter().cloned())
+            .collect(),
+    }
+}
+
+#[tauri::command]
+pub fn list_user_rights(
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Vec<UserRightsData>, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let module = find_module(a2l, module_name.as_deref())?;
+    Ok(module.user_rights.iter().map(user_rights_data).collect())
+}
+
+/// Creates a user level, or replaces `original_name` when given.
+#[tauri::command]
+pub fn upsert_user_rights(
+    module_name: Option<String>,
+    original_name: Option<String>,
+    data: UserRightsData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    if data.user_level.trim().is_empty() {
+        return Err("User level must not be empty".to_string());
+    }
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("upsert_user_rights", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_module(&module_id);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+    let missing: Vec<&str> = data
+        .groups
+        .iter()
+        .filter(|group| module.group.get(group).is_none())
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("Unknown groups: {}", missing.join(", ")));
+    }
+
+    let lookup = original_name.as_deref().unwrap_or(&data.user_level);
+    if lookup != data.user_level
+        && module.user_rights.iter().any(|r| r.user_level_id == data.user_level)
+    {
+        return Err(format!("User level '{}' already exists", data.user_level));
+    }
+    let index = match module.user_rights.iter().position(|r| r.user_level_id == lookup) {
+        Some(index) => index,
+        None if original_name.is_some() => return Err(format!("User level '{}' not found", lookup)),
+        None => {
+            module.user_rights.push(a2lfile::UserRights::new(data.user_level.clone()));
+            module.user_rights.len() - 1
+        }
+    };
+    let rights = &mut module.user_rights[index];
+    rights.user_level_id = data.user_level;
+    rights.read_only = data.read_only.then(a2lfile::ReadOnly::new);
+    rights.ref_group = if data.groups.is_empty() {
+        Vec::new()
+    } else {
+        let mut ref_group = a2lfile::RefGroup::new();
+        ref_group.identifier_list = data.groups;
+        vec![ref_group]
+    };
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_user_rights(
+    module_name: Option<String>,
+    user_level: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("delete_user_rights", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_module(&module_id);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+    let before = module.user_rights.len();
+    module.user_rights.retain(|r| r.user_level_id != user_level);
+    if module.user_rights.len() == before {
+        return Err(format!("User level '{}' not found", user_level));
+    }
+    Ok(())
+}
+
+/// Resolves the groups of a user level, including sub groups, into the
+/// objects it can access.
+#[tauri::command]
+pub fn get_effective_rights(
+    user_level: String,
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<EffectiveRights, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let module = find_module(a2l, module_name.as_deref())?;
+    let rights = module
+        .user_rights
+        .iter()
+        .find(|r| r.user_level_id == user_level)
+        .ok_or_else(|| format!("User level '{}' not found", user_level))?;
+
+    let mut members = Vec::new();
+    for ref_group in &rights.ref_group {
+        for group in &ref_group.identifier_list {
+            group_members(module, group, &mut members)?;
+        }
+    }
+    let collect = |kind: &str| -> Vec<String> {
+        members
+            .iter()
+            .filter(|(k, _)| k == kind)
+            .map(|(_, name)| name.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    };
+    Ok(EffectiveRights {
+        user_level,
+        read_only: rights.read_only.is_some(),
+        groups: collect("Group"),
+        characteristics: collect("Characteristic"),
+        axis_pts: collect("AxisPts"),
+        measurements: collect("Measurement"),
+    })
+}
@@ -0,0 +1,542 @@
+use std::collections::BTreeMap;
+
+use a2lfile::{A2lObjectName, A2lObjectNameSetter};
+use serde::{Deserialize, Serialize};
+
+use crate::{find_module, find_module_mut, parse_hex_address, AppState};
+
+#[derive(Serialize, Deserialize)]
+pub struct VarCriterionData {
+    name: String,
+    long_identifier: String,
+    values: Vec<String>,
+    var_measurement: Option<String>,
+    var_selection_characteristic: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct VarCharacteristicData {
+    name: String,
+    criteria: Vec<String>,
+    addresses: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct VariantCodingData {
+    separator: Option<String>,
+    naming: Option<String>,
+    criteria: Vec<VarCriterionData>,
+    characteristics: Vec<VarCharacteristicData>,
+    forbidden_combinations: Vec<BTreeMap<String, String>>,
+}
+
+#[derive(Serialize)]
+pub struct ResolvedVariant {
+    variant_name: String,
+    address: String,
+    index: usize,
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+fn variant_coding_mut(module: &mut a2lfile::Module) -> &mut a2lfile::VariantCoding {
+    module.variant_coding.get_or_insert_with(a2lfile::VariantCoding::new)
+}
+
+/// VARIANT_CODING of `module` for deleting or clearing entries, which must not
+/// create an empty block when there is none.
+fn existing_variant_coding_mut(module: &mut a2lfile::Module) -> Result<&mut a2lfile::VariantCoding, String> {
+    let name = module.get_name().to_string();
+    module
+        .variant_coding
+        .as_mut()
+        .ok_or_else(|| format!("Module {name} has no VARIANT_CODING"))
+}
+
+fn is_forbidden(coding: &a2lfile::VariantCoding, selection: &BTreeMap<&str, &str>) -> bool {
+    coding.var_forbidden_comb.iter().any(|forbidden| {
+        !forbidden.combination.is_empty()
+            && forbidden.combination.iter().all(|entry| {
+                selection.get(entry.criterion_name.as_str()) == Some(&entry.criterion_value.as_str())
+            })
+    })
+}
+
+/// All allowed value combinations of `criteria`, in VAR_ADDRESS order: the
+/// last criterion varies fastest and forbidden combinations are skipped.
+fn valid_combinations<'a>(
+    coding: &'a a2lfile::VariantCoding,
+    criteria: &'a [String],
+) -> Result<Vec<Vec<(usize, &'a str)>>, String> {
+    let value_lists = criteria
+        .iter()
+        .map(|name| {
+            coding
+                .var_criterion
+                .get(name)
+                .map(|criterion| &criterion.value_list)
+                .ok_or_else(|| format!("Variant criterion '{name}' not found"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut combinations: Vec<Vec<(usize, &str)>> = vec![Vec::new()];
+    for values in &value_lists {
+        combinations = combinations
+            .into_iter()
+            .flat_map(|prefix| {
+                values.iter().enumerate().map(move |(index, value)| {
+                    let mut combination = prefix.clone();
+                    combination.push((index, value.as_str()));
+                    combination
+                })
+            })
+            .collect();
+    }
+    Ok(combinations
+        .into_iter()
+        .filter(|combination| {
+            let selection: BTreeMap<&str, &str> = criteria
+                .iter()
+                .map(String::as_str)
+                .zip(combination.iter().map(|(_, value)| *value))
+                .collect();
+            !is_forbidden(coding, &selection)
+        })
+        .collect())
+}
+
+/// Variant name as defined by VAR_NAMING: value indices as numbers (NUMERIC)
+/// or letters (ALPHA), joined with VAR_SEPARATOR.
+fn variant_name(coding: &a2lfile::VariantCoding, characteristic: &str, combination: &[(usize, &str)]) -> String {
+    let separator = coding
+        .var_separator
+        .as_ref()
+        .map(|separator| separator.separator.as_str())
+        .unwrap_or(".");
+    let alpha = matches!(
+        coding.var_naming.as_ref().map(|naming| &naming.tag),
+        Some(a2lfile::VarNamingTag::Alpha)
+    );
+    let mut name = characteristic.to_string();
+    for (index, _) in combination {
+        name.push_str(separator);
+        if alpha {
+            name.push(char::from(b'A' + (*index % 26) as u8));
+        } else {
+            name.push_str(&index.to_string());
+        }
+    }
+    name
+}
+
+#[tauri::command]
+pub fn get_variant_coding(
+    module_name: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<Option<VariantCodingData>, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let module = find_module(a2l, module_name.as_deref())?;
+    let Some(coding) = module.variant_coding.as_ref() else {
+        return Ok(None);
+    };
+
+    Ok(Some(VariantCodingData {
+        separator: coding.var_separator.as_ref().map(|s| s.separator.clone()),
+        naming: coding.var_naming.as_ref().map(|naming| {
+            match naming.tag {
+                a2lfile::VarNamingTag::Alpha => "ALPHA",
+                a2lfile::VarNamingTag::Numeric => "NUMERIC",
+            }
+            .to_string()
+        }),
+        criteria: coding
+            .var_criterion
+            .iter()
+            .map(|criterion| VarCriterionData {
+                name: criterion.get_name().to_string(),
+                long_identifier: criterion.long_identifier.clone(),
+                values: criterion.value_list.clone(),
+                var_measurement: criterion.var_measurement.as_ref().map(|m| m.name.clone()),
+                var_selection_characteristic: criterion
+                    .var_selection_characteristic
+                    .as_ref()
+                    .map(|c| c.name.clone()),
+            })
+            .collect(),
+        characteristics: coding
+            .var_characteristic
+            .iter()
+            .map(|characteristic| VarCharacteristicData {
+                name: characteristic.get_name().to_string(),
+                criteria: characteristic.criterion_name_list.clone(),
+                addresses: characteristic
+                    .var_address
+                    .as_ref()
+                    .map(|var_address| {
+                        var_address
+                            .address_list
+                            .iter()
+                            .map(|address| format!("0x{:X}", address))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+            .collect(),
+        forbidden_combinations: coding
+            .var_forbidden_comb
+            .iter()
+            .map(|forbidden| {
+                forbidden
+                    .combination
+                    .iter()
+                    .map(|entry| (entry.criterion_name.clone(), entry.criterion_value.clone()))
+                    .collect()
+            })
+            .collect(),
+    }))
+}
+
+/// Checks that the VAR_FORBIDDEN_COMB entries and VAR_ADDRESS lists that use
+/// `criterion` still fit its values.
+fn check_criterion_users(coding: &a2lfile::VariantCoding, criterion: &str) -> Result<(), String> {
+    let values = &coding
+        .var_criterion
+        .get(criterion)
+        .ok_or_else(|| format!("Variant criterion '{}' not found", criterion))?
+        .value_list;
+    for forbidden in &coding.var_forbidden_comb {
+        for entry in forbidden.combination.iter().filter(|entry| entry.criterion_name == criterion) {
+            if !values.contains(&entry.criterion_value) {
+                return Err(format!(
+                    "A forbidden combination uses value '{}' of variant criterion '{}'",
+                    entry.criterion_value, criterion
+                ));
+            }
+        }
+    }
+    for characteristic in coding.var_characteristic.iter() {
+        let Some(var_address) = &characteristic.var_address else {
+            continue;
+        };
+        if !characteristic.criterion_name_list.iter().any(|name| name == criterion) {
+            continue;
+        }
+        let expected = valid_combinations(coding, &characteristic.criterion_name_list)?.len();
+        if var_address.address_list.len() != expected {
+            return Err(format!(
+                "Variant characteristic '{}' has {} addresses, but the new values need {}; clear its addresses first",
+                characteristic.get_name(),
+                var_address.address_list.len(),
+                expected
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn upsert_var_criterion(
+    module_name: Option<String>,
+    original_name: Option<String>,
+    data: VarCriterionData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    if data.values.is_empty() {
+        return Err("A variant criterion needs at least one value".to_string());
+    }
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("upsert_var_criterion", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    // Changes go to a copy that is only stored once it is consistent.
+    let mut coding = find_module(edit.a2l(), Some(&module_id))?
+        .variant_coding
+        .clone()
+        .unwrap_or_else(a2lfile::VariantCoding::new);
+
+    let lookup = original_name.as_deref().unwrap_or(&data.name).to_string();
+    if lookup != data.name && coding.var_criterion.get(&data.name).is_some() {
+        return Err(format!("Variant criterion '{}' already exists", data.name));
+    }
+    if coding.var_criterion.get(&lookup).is_none() {
+        if original_name.is_some() {
+            return Err(format!("Variant criterion '{}' not found", lookup));
+        }
+        coding
+            .var_criterion
+            .push(a2lfile::VarCriterion::new(lookup.clone(), String::new()));
+    }
+    let criterion = coding
+        .var_criterion
+        .get_mut(&lookup)
+        .ok_or_else(|| format!("Variant criterion '{}' not found", lookup))?;
+    criterion.set_name(data.name.clone());
+    criterion.long_identifier = data.long_identifier;
+    criterion.value_list = data.values;
+    criterion.var_measurement = non_empty(data.var_measurement).map(a2lfile::VarMeasurement::new);
+    criterion.var_selection_characteristic =
+        non_empty(data.var_selection_characteristic).map(a2lfile::VarSelectionCharacteristic::new);
+
+    // Keep references of variant characteristics in sync with a rename.
+    if lookup != data.name {
+        for characteristic in coding.var_characteristic.iter_mut() {
+            for name in characteristic.criterion_name_list.iter_mut() {
+                if *name == lookup {
+                    *name = data.name.clone();
+                }
+            }
+        }
+        for forbidden in coding.var_forbidden_comb.iter_mut() {
+            for entry in forbidden.combination.iter_mut() {
+                if entry.criterion_name == lookup {
+                    entry.criterion_name = data.name.clone();
+                }
+            }
+        }
+    }
+    check_criterion_users(&coding, &data.name)?;
+
+    edit.touch_module(&module_id);
+    find_module_mut(edit.a2l_mut(), Some(&module_id))?.variant_coding = Some(coding);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_var_criterion(
+    module_name: Option<String>,
+    name: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("delete_var_criterion", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_module(&module_id);
+    let a2l = edit.a2l_mut();
+    let coding = existing_variant_coding_mut(find_module_mut(a2l, module_name.as_deref())?)?;
+    if let Some(user) = coding
+        .var_characteristic
+        .iter()
+        .find(|characteristic| characteristic.criterion_name_list.contains(&name))
+    {
+        return Err(format!(
+            "Variant criterion '{}' is used by variant characteristic '{}'",
+            name,
+            user.get_name()
+        ));
+    }
+    let before = coding.var_criterion.len();
+    coding.var_criterion.retain(|criterion| criterion.get_name() != name);
+    if coding.var_criterion.len() == before {
+        return Err(format!("Variant criterion '{}' not found", name));
+    }
+    for forbidden in coding.var_forbidden_comb.iter_mut() {
+        forbidden.combination.retain(|entry| entry.criterion_name != name);
+    }
+    coding.var_forbidden_comb.retain(|forbidden| !forbidden.combination.is_empty());
+    Ok(())
+}
+
+/// Creates or replaces the variant coding of a characteristic. The address
+/// list, if given, must have one entry per allowed criterion combination.
+#[tauri::command]
+pub fn upsert_var_characteristic(
+    module_name: Option<String>,
+    data: VarCharacteristicData,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let addresses = data
+        .addresses
+        .iter()
+        .map(|address| parse_hex_address(address))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("upsert_var_characteristic", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    let module = find_module(edit.a2l(), Some(&module_id))?;
+    if module.characteristic.get(&data.name).is_none() {
+        return Err(format!("Characteristic '{}' not found", data.name));
+    }
+    let empty = a2lfile::VariantCoding::new();
+    let expected = valid_combinations(module.variant_coding.as_ref().unwrap_or(&empty), &data.criteria)?.len();
+    if !addresses.is_empty() && addresses.len() != expected {
+        return Err(format!(
+            "Expected one address per allowed combination ({}), got {}",
+            expected,
+            addresses.len()
+        ));
+    }
+
+    edit.touch_module(&module_id);
+    let coding = variant_coding_mut(find_module_mut(edit.a2l_mut(), Some(&module_id))?);
+    let var_address = (!addresses.is_empty()).then(|| {
+        let mut var_address = a2lfile::VarAddress::new();
+        var_address.address_list = addresses;
+        var_address
+    });
+    if let Some(characteristic) = coding.var_characteristic.get_mut(&data.name) {
+        characteristic.criterion_name_list = data.criteria;
+        characteristic.var_address = var_address;
+    } else {
+        let mut characteristic = a2lfile::VarCharacteristic::new(data.name);
+        characteristic.criterion_name_list = data.criteria;
+        characteristic.var_address = var_address;
+        coding.var_characteristic.push(characteristic);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_var_characteristic(
+    module_name: Option<String>,
+    name: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("delete_var_characteristic", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_module(&module_id);
+    let a2l = edit.a2l_mut();
+    let coding = existing_variant_coding_mut(find_module_mut(a2l, module_name.as_deref())?)?;
+    let before = coding.var_characteristic.len();
+    coding.var_characteristic.retain(|characteristic| characteristic.get_name() != name);
+    if coding.var_characteristic.len() == before {
+        return Err(format!("Variant characteristic '{}' not found", name));
+    }
+    Ok(())
+}
+
+/// Replaces all VAR_FORBIDDEN_COMB entries. Each map lists criterion name to
+/// value for one forbidden combination.
+#[tauri::command]
+pub fn set_var_forbidden_combinations(
+    module_name: Option<String>,
+    combinations: Vec<BTreeMap<String, String>>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("set_var_forbidden_combinations", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_module(&module_id);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+    if combinations.iter().all(|combination| combination.is_empty()) {
+        if let Some(coding) = module.variant_coding.as_mut() {
+            coding.var_forbidden_comb.clear();
+        }
+        return Ok(());
+    }
+    let coding = existing_variant_coding_mut(module)?;
+    for combination in &combinations {
+        for (criterion, value) in combination {
+            let known = coding
+                .var_criterion
+                .get(criterion)
+                .ok_or_else(|| format!("Variant criterion '{}' not found", criterion))?;
+            if !known.value_list.contains(value) {
+                return Err(format!("'{}' is not a value of variant criterion '{}'", value, criterion));
+            }
+        }
+    }
+    coding.var_forbidden_comb = combinations
+        .into_iter()
+        .filter(|combination| !combination.is_empty())
+        .map(|combination| {
+            let mut forbidden = a2lfile::VarForbiddenComb::new();
+            forbidden.combination = combination
+                .into_iter()
+                .map(|(criterion, value)| a2lfile::CombinationStruct::new(criterion, value))
+                .collect();
+            forbidden
+        })
+        .collect();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_var_naming(
+    module_name: Option<String>,
+    naming: Option<String>,
+    separator: Option<String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    let tag = match non_empty(naming).as_deref() {
+        Some("ALPHA") => Some(a2lfile::VarNamingTag::Alpha),
+        Some("NUMERIC") => Some(a2lfile::VarNamingTag::Numeric),
+        Some(other) => return Err(format!("Invalid variant naming: {}", other)),
+        None => None,
+    };
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("set_var_naming", doc_id.as_deref())?;
+    let module_id = edit.module_id(module_name.as_deref())?;
+    edit.touch_module(&module_id);
+    let a2l = edit.a2l_mut();
+    let module = find_module_mut(a2l, module_name.as_deref())?;
+    let separator = non_empty(separator);
+    if module.variant_coding.is_none() && tag.is_none() && separator.is_none() {
+        return Ok(());
+    }
+    let coding = variant_coding_mut(module);
+    coding.var_naming = tag.map(a2lfile::VarNaming::new);
+    coding.var_separator = separator.map(a2lfile::VarSeparator::new);
+    Ok(())
+}
+
+/// Effective address of a variant-coded characteristic for the given
+/// criterion values, looked up in its VAR_ADDRESS list.
+#[tauri::command]
+pub fn resolve_variant_addresses(
+    module_name: Option<String>,
+    characteristic: String,
+    criterion_values: BTreeMap<String, String>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<ResolvedVariant, String> {
+    let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let a2l = guard.get(doc_id.as_deref())?;
+    let module = find_module(a2l, module_name.as_deref())?;
+    let coding = module
+        .variant_coding
+        .as_ref()
+        .ok_or_else(|| "Module has no variant coding".to_string())?;
+    let var_characteristic = coding
+        .var_characteristic
+        .get(&characteristic)
+        .ok_or_else(|| format!("Characteristic '{}' is not variant coded", characteristic))?;
+    let criteria = &var_characteristic.criterion_name_list;
+
+    let selected = criteria
+        .iter()
+        .map(|criterion| {
+            criterion_values
+                .get(criterion)
+                .map(String::as_str)
+                .ok_or_else(|| format!("No value given for variant criterion '{}'", criterion))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let combinations = valid_combinations(coding, criteria)?;
+    let index = combinations
+        .iter()
+        .position(|combination| combination.iter().map(|(_, value)| *value).eq(selected.iter().copied()))
+        .ok_or_else(|| "The selected combination is forbidden or uses unknown values".to_string())?;
+    let address = var_characteristic
+        .var_address
+        .as_ref()
+        .and_then(|var_address| var_address.address_list.get(index))
+        .ok_or_else(|| format!("VAR_ADDRESS of '{}' has no entry for combination {}", characteristic, index))?;
+
+    Ok(ResolvedVariant {
+        variant_name: variant_name(coding, &characteristic, &combinations[index]),
+        address: format!("0x{:X}", address),
+        index,
+    })
+}
@@ -0,0 +1,360 @@
+use a2lfile::{A2lObjectName, ItemList};
+use serde::Serialize;
+
+use crate::AppState;
+
+#[derive(Serialize, Clone, Copy, PartialEq, PartialOrd)]
+struct Asap2Version {
+    version_no: u16,
+    upgrade_no: u16,
+}
+
+#[derive(Serialize)]
+struct VersionChange {
+    module: String,
+    kind: String,
+    name: String,
+    action: String,
+    detail: String,
+}
+
+#[derive(Serialize)]
+pub struct VersionConversionReport {
+    from_version: Option<String>,
+    to_version: String,
+    changes: Vec<VersionChange>,
+}
+
+const V1_60: Asap2Version = Asap2Version { version_no: 1, upgrade_no: 60 };
+const V1_70: Asap2Version = Asap2Version { version_no: 1, upgrade_no: 70 };
+
+fn parse_target_version(target: &str) -> Result<Asap2Version, String> {
+    let upgrade_no = match target.trim() {
+        "1.50" | "1.5" => 50,
+        "1.51" => 51,
+        "1.60" | "1.6" => 60,
+        "1.61" => 61,
+        "1.70" | "1.7" => 70,
+        "1.71" => 71,
+        other => return Err(format!("Unsupported ASAP2 version: {other}")),
+    };
+    Ok(Asap2Version { version_no: 1, upgrade_no })
+}
+
+struct ChangeLog<'a> {
+    module: &'a str,
+    changes: &'a mut Vec<VersionChange>,
+}
+
+impl ChangeLog<'_> {
+    fn record(&mut self, kind: &str, name: &str, action: &str, detail: impl ToString) {
+        self.changes.push(VersionChange {
+            module: self.module.to_string(),
+            kind: kind.to_string(),
+            name: name.to_string(),
+            action: action.to_string(),
+            detail: detail.to_string(),
+        });
+    }
+}
+
+/// SYMBOL_LINK was introduced with ASAP2 1.60; older consumers reject it.
+fn strip_symbol_links(module: &mut a2lfile::Module, log: &mut ChangeLog) {
+    for measurement in module.measurement.iter_mut() {
+        if measurement.symbol_link.take().is_some() {
+            log.record("Measurement", measurement.get_name(), "dropped", "SYMBOL_LINK");
+        }
+    }
+    for characteristic in module.characteristic.iter_mut() {
+        if characteristic.symbol_link.take().is_some() {
+            log.record("Characteristic", characteristic.get_name(), "dropped", "SYMBOL_LINK");
+        }
+    }
+    for axis_pts in module.axis_pts.iter_mut() {
+        if axis_pts.symbol_link.take().is_some() {
+            log.record("AxisPts", axis_pts.get_name(), "dropped", "SYMBOL_LINK");
+        }
+    }
+    for blob in module.blob.iter_mut() {
+        if blob.symbol_link.take().is_some() {
+            log.record("Blob", blob.get_name(), "dropped", "SYMBOL_LINK");
+        }
+    }
+}
+
+/// ARRAY_SIZE is deprecated since 1.60 in favour of MATRIX_DIM.
+fn upgrade_array_size(module: &mut a2lfile::Module, log: &mut ChangeLog) {
+    for measurement in module.measurement.iter_mut() {
+        if let Some(array_size) = measurement.array_size.take() {
+            if measurement.matrix_dim.is_none() {
+                let mut matrix_dim = a2lfile::MatrixDim::new();
+                matrix_dim.dim_list = vec![array_size.number];
+                measurement.matrix_dim = Some(matrix_dim);
+            }
+            log.record(
+                "Measurement",
+                measurement.get_name(),
+                "transformed",
+                format!("ARRAY_SIZE {} -> MATRIX_DIM", array_size.number),
+            );
+        }
+    }
+}
+
+/// MODEL_LINK, ENCODING, ADDRESS_TYPE and LAYOUT only exist in 1.70+. Runs
+/// after `emulate_instances`, whose objects may have copied them.
+fn strip_v17_attributes(module: &mut a2lfile::Module, log: &mut ChangeLog) {
+    for measurement in module.measurement.iter_mut() {
+        if measurement.model_link.take().is_some() {
+            log.record("Measurement", measurement.get_name(), "dropped", "MODEL_LINK");
+        }
+        if measurement.address_type.take().is_some() {
+            log.record("Measurement", measurement.get_name(), "dropped", "ADDRESS_TYPE");
+        }
+        if measurement.layout.take().is_some() {
+            log.record("Measurement", measurement.get_name(), "dropped", "LAYOUT");
+        }
+    }
+    for characteristic in module.characteristic.iter_mut() {
+        if characteristic.model_link.take().is_some() {
+            log.record("Characteristic", characteristic.get_name(), "dropped", "MODEL_LINK");
+        }
+        if characteristic.encoding.take().is_some() {
+            log.record("Characteristic", characteristic.get_name(), "dropped", "ENCODING");
+        }
+    }
+    for axis_pts in module.axis_pts.iter_mut() {
+        if axis_pts.model_link.take().is_some() {
+            log.record("AxisPts", axis_pts.get_name(), "dropped", "MODEL_LINK");
+        }
+    }
+    for blob in module.blob.iter_mut() {
+        if blob.model_link.take().is_some() {
+            log.record("Blob", blob.get_name(), "dropped", "MODEL_LINK");
+        }
+        if blob.address_type.take().is_some() {
+            log.record("Blob", blob.get_name(), "dropped", "ADDRESS_TYPE");
+        }
+    }
+}
+
+fn measurement_from_typedef(
+    instance: &a2lfile::Instance,
+    typedef: &a2lfile::TypedefMeasurement,
+) -> a2lfile::Measurement {
+    let mut m = a2lfile::Measurement::new(instance.get_name().to_string(), typedef.datatype);
+    m.long_identifier = instance.long_identifier.clone();
+    m.conversion = typedef.conversion.clone();
+    m.resolution = typedef.resolution;
+    m.accuracy = typedef.accuracy;
+    m.lower_limit = typedef.lower_limit;
+    m.upper_limit = typedef.upper_limit;
+    m.ecu_address = Some(a2lfile::EcuAddress::new(instance.start_address));
+    m.ecu_address_extension = instance.ecu_address_extension.clone();
+    m.address_type = instance.address_type.clone().or_else(|| typedef.address_type.clone());
+    m.bit_mask = typedef.bit_mask.clone();
+    m.bit_operation = typedef.bit_operation.clone();
+    m.byte_order = typedef.byte_order.clone();
+    m.discrete = typedef.discrete.clone();
+    m.error_mask = typedef.error_mask.clone();
+    m.format = typedef.format.clone();
+    m.layout = instance.layout.clone().or_else(|| typedef.layout.clone());
+    m.matrix_dim = instance.matrix_dim.clone().or_else(|| typedef.matrix_dim.clone());
+    m.phys_unit = typedef.phys_unit.clone();
+    m.display_identifier = instance.display_identifier.clone();
+    m.max_refresh = instance.max_refresh.clone();
+    m.read_write = instance.read_write.clone();
+    m.symbol_link = instance.symbol_link.clone();
+    m.annotation = instance.annotation.clone();
+    m.if_data = instance.if_data.clone();
+    m
+}
+
+fn characteristic_from_typedef(
+    instance: &a2lfile::Instance,
+    typedef: &a2lfile::TypedefCharacteristic,
+) -> a2lfile::Characteristic {
+    let mut c = a2lfile::Characteristic::new(
+        instance.get_name().to_string(),
+        instance.long_identifier.clone(),
+        typedef.characteristic_type,
+        instance.start_address,
+        typedef.record_layout.clone(),
+        typedef.max_diff,
+        typedef.conversion.clone(),
+        typedef.lower_limit,
+        typedef.upper_limit,
+    );
+    c.axis_descr = typedef.axis_descr.clone();
+    c.bit_mask = typedef.bit_mask.clone();
+    c.byte_order = typedef.byte_order.clone();
+    c.discrete = typedef.discrete.clone();
+    c.extended_limits = typedef.extended_limits.clone();
+    c.format = typedef.format.clone();
+    c.matrix_dim = instance.matrix_dim.clone().or_else(|| typedef.matrix_dim.clone());
+    c.number = typedef.number.clone();
+    c.phys_unit = typedef.phys_unit.clone();
+    c.step_size = typedef.step_size.clone();
+    c.calibration_access = instance.calibration_access.clone();
+    c.display_identifier = instance.display_identifier.clone();
+    c.ecu_address_extension = instance.ecu_address_extension.clone();
+    c.max_refresh = instance.max_refresh.clone();
+    c.symbol_link = instance.symbol_link.clone();
+    c.annotation = instance.annotation.clone();
+    c.if_data = instance.if_data.clone();
+    c
+}
+
+fn axis_pts_from_typedef(
+    instance: &a2lfile::Instance,
+    typedef: &a2lfile::TypedefAxis,
+) -> a2lfile::AxisPts {
+    let mut a = a2lfile::AxisPts::new(
+        instance.get_name().to_string(),
+        instance.long_identifier.clone(),
+        instance.start_address,
+        typedef.input_quantity.clone(),
+        typedef.record_layout.clone(),
+        typedef.max_diff,
+        typedef.conversion.clone(),
+        typedef.max_axis_points,
+        typedef.lower_limit,
+        typedef.upper_limit,
+    );
+    a.byte_order = typedef.byte_order.clone();
+    a.deposit = typedef.deposit.clone();
+    a.extended_limits = typedef.extended_limits.clone();
+    a.format = typedef.format.clone();
+    a.monotony = typedef.monotony.clone();
+    a.phys_unit = typedef.phys_unit.clone();
+    a.step_size = typedef.step_size.clone();
+    a.calibration_access = instance.calibration_access.clone();
+    a.display_identifier = instance.display_identifier.clone();
+    a.ecu_address_extension = instance.ecu_address_extension.clone();
+    a.max_refresh = instance.max_refresh.clone();
+    a.symbol_link = instance.symbol_link.clone();
+    a.annotation = instance.annotation.clone();
+    a.if_data = instance.if_data.clone();
+    a
+}
+
+fn blob_from_typedef(instance: &a2lfile::Instance, typedef: &a2lfile::TypedefBlob) -> a2lfile::Blob {
+    let mut b = a2lfile::Blob::new(
+        instance.get_name().to_string(),
+        instance.long_identifier.clone(),
+        instance.start_address,
+        typedef.size,
+    );
+    b.address_type = instance.address_type.clone().or_else(|| typedef.address_type.clone());
+    b.calibration_access = instance.calibration_access.clone();
+    b.display_identifier = instance.display_identifier.clone();
+    b.ecu_address_extension = instance.ecu_address_extension.clone();
+    b.max_refresh = instance.max_refresh.clone();
+    b.symbol_link = instance.symbol_link.clone();
+    b.annotation = instance.annotation.clone();
+    b.if_data = instance.if_data.clone();
+    b
+}
+
+/// Pre-1.70 consumers know nothing about INSTANCE/TYPEDEF_*. Instances of
+/// scalar typedefs are emulated by concrete objects; structure instances and
+/// the typedefs themselves cannot be represented and are dropped.
+fn emulate_instances(module: &mut a2lfile::Module, log: &mut ChangeLog) {
+    let instances: Vec<a2lfile::Instance> = module.instance.iter().cloned().collect();
+    for instance in &instances {
+        let name = instance.get_name();
+        let type_ref = instance.type_ref.as_str();
+        if let Some(typedef) = module.typedef_measurement.get(type_ref) {
+            let measurement = measurement_from_typedef(instance, typedef);
+            module.measurement.push(measurement);
+            log.record("Instance", name, "transformed", format!("MEASUREMENT from {type_ref}"));
+        } else if let Some(typedef) = module.typedef_characteristic.get(type_ref) {
+            let characteristic = characteristic_from_typedef(instance, typedef);
+            module.characteristic.push(characteristic);
+            log.record("Instance", name, "transformed", format!("CHARACTERISTIC from {type_ref}"));
+        } else if let Some(typedef) = module.typedef_axis.get(type_ref) {
+            let axis_pts = axis_pts_from_typedef(instance, typedef);
+            module.axis_pts.push(axis_pts);
+            log.record("Instance", name, "transformed", format!("AXIS_PTS from {type_ref}"));
+        } else if let Some(typedef) = module.typedef_blob.get(type_ref) {
+            let blob = blob_from_typedef(instance, typedef);
+            module.blob.push(blob);
+            log.record("Instance", name, "transformed", format!("BLOB from {type_ref}"));
+        } else {
+            log.record("Instance", name, "dropped", format!("type {type_ref} has no pre-1.70 equivalent"));
+        }
+    }
+    module.instance = ItemList::new();
+
+    for item in module.typedef_measurement.iter() {
+        log.record("TypedefMeasurement", item.get_name(), "dropped", "TYPEDEF_MEASUREMENT");
+    }
+    for item in module.typedef_characteristic.iter() {
+        log.record("TypedefCharacteristic", item.get_name(), "dropped", "TYPEDEF_CHARACTERISTIC");
+    }
+    for item in module.typedef_axis.iter() {
+        log.record("TypedefAxis", item.get_name(), "dropped", "TYPEDEF_AXIS");
+    }
+    for item in module.typedef_blob.iter() {
+        log.record("TypedefBlob", item.get_name(), "dropped", "TYPEDEF_BLOB");
+    }
+    for item in module.typedef_structure.iter() {
+        log.record("TypedefStructure", item.get_name(), "dropped", "TYPEDEF_STRUCTURE");
+    }
+    for item in module.transformer.iter() {
+        log.record("Transformer", item.get_name(), "dropped", "TRANSFORMER");
+    }
+    module.typedef_measurement = ItemList::new();
+    module.typedef_characteristic = ItemList::new();
+    module.typedef_axis = ItemList::new();
+    module.typedef_blob = ItemList::new();
+    module.typedef_structure = ItemList::new();
+    module.transformer = ItemList::new();
+}
+
+pub(crate) fn convert_version(a2l: &mut a2lfile::A2lFile, target: &str) -> Result<VersionConversionReport, String> {
+    let target = parse_target_version(target)?;
+    let from_version = a2l.asap2_version.as_ref().map(|version| Asap2Version {
+        version_no: version.version_no,
+        upgrade_no: version.upgrade_no,
+    });
+
+    let mut changes = Vec::new();
+    for module in a2l.project.module.iter_mut() {
+        let module_name = module.get_name().to_string();
+        let mut log = ChangeLog {
+            module: &module_name,
+            changes: &mut changes,
+        };
+        if target < V1_70 {
+            emulate_instances(module, &mut log);
+            strip_v17_attributes(module, &mut log);
+        }
+        if target < V1_60 {
+            strip_symbol_links(module, &mut log);
+        } else {
+            upgrade_array_size(module, &mut log);
+        }
+    }
+
+    a2l.asap2_version = Some(a2lfile::Asap2Version::new(target.version_no, target.upgrade_no));
+
+    Ok(VersionConversionReport {
+        from_version: from_version.map(|version| format!("{}.{}", version.version_no, version.upgrade_no)),
+        to_version: format!("{}.{}", target.version_no, target.upgrade_no),
+        changes,
+    })
+}
+
+#[tauri::command]
+pub fn convert_a2l_version(
+    target: String,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<VersionConversionReport, String> {
+    let mut guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+    let mut edit = guard.edit("convert_a2l_version", doc_id.as_deref())?;
+    edit.touch_all();
+    let a2l = edit.a2l_mut();
+    convert_version(a2l, &target)
+}
@@ -0,0 +1,394 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+use crate::a2l_json::{block_tree, object_key, render_items, Block, Item};
+use crate::elf_symbols::ElfIndex;
+use crate::hex::MemoryImage;
+use crate::symbol_sources::read_symbol_file;
+use crate::{build_metadata, diagnostics, load_a2l_file, parse_a2l, read_elf_symbols, A2lMetadata, AppState};
+
+const CHANGED_EVENT: &str = "external-file-changed";
+/// How often the watched directories are matched against the loaded files.
+const SYNC_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Watches the directories of the loaded files rather than the files, so
+/// files that build tools replace (delete and recreate) keep being tracked.
+#[derive(Default)]
+pub(crate) struct FileWatcher {
+    watcher: Option<RecommendedWatcher>,
+    dirs: HashSet<PathBuf>,
+    /// Loaded files changed on disk since they were last reloaded.
+    changed: HashSet<PathBuf>,
+    /// Files written by the app, with their size and modification time after
+    /// the write; `None` while the write is in progress.
+    own_writes: HashMap<PathBuf, Option<FileStamp>>,
+}
+
+type FileStamp = (u64, Option<SystemTime>);
+
+fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.len(), metadata.modified().ok()))
+}
+
+impl FileWatcher {
+    /// Whether the file still is as the app wrote it. A file that changed
+    /// since is forgotten, so later events are reported again.
+    fn is_own_write(&mut self, path: &Path) -> bool {
+        match self.own_writes.get(path) {
+            Some(None) => true,
+            Some(Some(stamp)) if file_stamp(path).as_ref() == Some(stamp) => true,
+            Some(Some(_)) => {
+                self.own_writes.remove(path);
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+/// Runs `write`, which writes `path`, without the watcher reporting the write
+/// as an external change.
+pub(crate) fn own_write(
+    state: &AppState,
+    path: &str,
+    write: impl FnOnce() -> Result<(), String>,
+) -> Result<(), String> {
+    let Some(path) = normalize(path) else {
+        return write();
+    };
+    let mut watcher = state.watcher.lock().map_err(|_| "State lock poisoned")?;
+    watcher.own_writes.insert(path.clone(), None);
+    drop(watcher);
+    let result = write();
+    let mut watcher = state.watcher.lock().map_err(|_| "State lock poisoned")?;
+    match file_stamp(&path).filter(|_| result.is_ok()) {
+        Some(stamp) => {
+            watcher.own_writes.insert(path.clone(), Some(stamp));
+            // The file on disk is now the saved document.
+            watcher.changed.remove(&path);
+        }
+        None => {
+            watcher.own_writes.remove(&path);
+        }
+    }
+    result
+}
+
+#[derive(Serialize, Clone)]
+struct FileChanged {
+    path: String,
+    /// "a2l", "hex" or "elf".
+    kind: String,
+    /// Documents that use the file; empty for the ELF.
+    doc_ids: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct ReloadReport {
+    reloaded: Vec<String>,
+    /// `KEYWORD name` of objects changed both locally and on disk; the local
+    /// version was kept.
+    conflicts: Vec<String>,
+    metadata: Option<A2lMetadata>,
+}
+
+struct LoadedFile {
+    path: PathBuf,
+    kind: &'static str,
+    doc_ids: Vec<String>,
+}
+
+/// Absolute form of `path` with its directory resolved, matching the paths
+/// notify reports for the watched directory.
+fn normalize(path: &str) -> Option<PathBuf> {
+    let path = Path::new(path);
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    Some(fs::canonicalize(dir).ok()?.join(path.file_name()?))
+}
+
+fn loaded_files(state: &AppState) -> Result<Vec<LoadedFile>, String> {
+    let mut files: Vec<LoadedFile> = Vec::new();
+    let mut add = |path: &str, kind: &'static str, doc_id: Option<&str>| {
+        let Some(path) = normalize(path) else { return };
+        match files.iter_mut().find(|f| f.path == path) {
+            Some(file) => file.doc_ids.extend(doc_id.map(str::to_string)),
+            None => files.push(LoadedFile {
+                path,
+                kind,
+                doc_ids: doc_id.map(str::to_string).into_iter().collect(),
+            }),
+        }
+    };
+    {
+        let documents = state.documents.lock().map_err(|_| "State lock poisoned")?;
+        for (id, document) in documents.iter() {
+            if let Some(path) = &document.path {
+                add(path, "a2l", Some(id));
+            }
+            if let Some((path, _)) = &document.hex_image {
+                add(path, "hex", Some(id));
+            }
+        }
+    }
+    if let Some(elf) = state.elf.lock().map_err(|_| "State lock poisoned")?.as_ref() {
+        add(elf.path(), "elf", None);
+    }
+    Ok(files)
+}
+
+fn on_event(app: &tauri::AppHandle, event: notify::Event) {
+    if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+        return;
+    }
+    let state = app.state::<AppState>();
+    let Ok(files) = loaded_files(&state) else { return };
+    for path in event.paths {
+        let Some(file) = files.iter().find(|f| f.path == path) else { continue };
+        // Only the first event of a burst is reported; the flag is cleared on
+        // reload. The app's own saves are not reported at all.
+        let first = match state.watcher.lock() {
+            Ok(mut watcher) => !watcher.is_own_write(&path) && watcher.changed.insert(path.clone()),
+            Err(_) => return,
+        };
+        if first {
+            let _ = app.emit(
+                CHANGED_EVENT,
+                FileChanged {
+                    path: path.to_string_lossy().to_string(),
+                    kind: file.kind.to_string(),
+                    doc_ids: file.doc_ids.clone(),
+                },
+            );
+        }
+    }
+}
+
+/// Watches the directories of the loaded files and drops those no longer needed.
+fn sync(app: &tauri::AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let files = loaded_files(&state)?;
+    let wanted: HashSet<PathBuf> = files
+        .iter()
+        .filter_map(|f| f.path.parent().map(Path::to_path_buf))
+        .collect();
+
+    let mut guard = state.watcher.lock().map_err(|_| "State lock poisoned")?;
+    let FileWatcher {
+        watcher,
+        dirs,
+        changed,
+        own_writes,
+    } = &mut *guard;
+    let Some(watcher) = watcher.as_mut() else {
+        return Ok(());
+    };
+    for dir in dirs.difference(&wanted) {
+        let _ = watcher.unwatch(dir);
+    }
+    dirs.retain(|dir| wanted.contains(dir));
+    for dir in wanted {
+        if !dirs.contains(&dir) && watcher.watch(&dir, RecursiveMode::NonRecursive).is_ok() {
+            dirs.insert(dir);
+        }
+    }
+    changed.retain(|path| files.iter().any(|f| &f.path == path));
+    own_writes.retain(|path, stamp| stamp.is_none() || files.iter().any(|f| &f.path == path));
+    Ok(())
+}
+
+/// Creates the watcher and the thread that keeps it in line with the loaded files.
+pub(crate) fn start(app: tauri::AppHandle) {
+    let handler_app = app.clone();
+    let watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        if let Ok(event) = result {
+            on_event(&handler_app, event);
+        }
+    });
+    match (watcher, app.state::<AppState>().watcher.lock()) {
+        (Ok(watcher), Ok(mut state)) => state.watcher = Some(watcher),
+        _ => return,
+    }
+    std::thread::spawn(move || loop {
+        let _ = sync(&app);
+        std::thread::sleep(SYNC_INTERVAL);
+    });
+}
+
+fn find<'a>(items: &'a [Item], key: &str) -> Option<&'a Item> {
+    items.iter().find(|item| object_key(item).as_deref() == Some(key))
+}
+
+/// Three-way merge of the named children of one block: objects edited only on
+/// disk take the disk version, objects edited locally keep the local version.
+fn merge_items(base: &[Item], local: &[Item], disk: &[Item], conflicts: &mut Vec<String>) -> Vec<Item> {
+    let mut merged = Vec::new();
+    for item in disk {
+        let Some(key) = object_key(item) else {
+            merged.push(item.clone());
+            continue;
+        };
+        match (find(base, &key), find(local, &key)) {
+            (Some(b), Some(l)) if l == b => merged.push(item.clone()),
+            (Some(b), Some(l)) => {
+                if item != b {
+                    conflicts.push(key);
+                }
+                merged.push(l.clone());
+            }
+            // Deleted locally; kept if it also changed on disk.
+            (Some(b), None) => {
+                if item != b {
+                    conflicts.push(key);
+                    merged.push(item.clone());
+                }
+            }
+            (None, Some(l)) => {
+                if l != item {
+                    conflicts.push(key);
+                }
+                merged.push(l.clone());
+            }
+            (None, None) => merged.push(item.clone()),
+        }
+    }
+    for item in local {
+        let Some(key) = object_key(item) else { continue };
+        if find(disk, &key).is_some() {
+            continue;
+        }
+        match find(base, &key) {
+            // Added locally.
+            None => merged.push(item.clone()),
+            // Deleted on disk but edited locally.
+            Some(b) if b != item => {
+                conflicts.push(key);
+                merged.push(item.clone());
+            }
+            Some(_) => {}
+        }
+    }
+    merged
+}
+
+fn project(items: &[Item]) -> Option<&Block> {
+    items.iter().find_map(|item| match item {
+        Item::Block(block) if block.block == "PROJECT" => Some(block),
+        _ => None,
+    })
+}
+
+/// Applies the merge to the objects of every module; everything outside the
+/// modules is taken from disk.
+fn merge_modules(base: &[Item], local: &[Item], disk: &mut [Item], conflicts: &mut Vec<String>) {
+    let (Some(base_project), Some(local_project)) = (project(base), project(local)) else {
+        return;
+    };
+    for item in disk.iter_mut() {
+        let Item::Block(project) = item else { continue };
+        if project.block != "PROJECT" {
+            continue;
+        }
+        for module_item in project.items.iter_mut() {
+            let Some(key) = object_key(module_item) else { continue };
+            let Item::Block(module) = module_item else { continue };
+            if module.block != "MODULE" {
+                continue;
+            }
+            let (Some(Item::Block(base_module)), Some(Item::Block(local_module))) =
+                (find(&base_project.items, &key), find(&local_project.items, &key))
+            else {
+                continue;
+            };
+            module.items = merge_items(&base_module.items, &local_module.items, &module.items, conflicts);
+        }
+    }
+}
+
+/// Reloads the files of `doc_id` (or the active document) and the ELF that
+/// changed on disk. With `merge_local_edits`, unsaved edits are merged into the
+/// new A2L per object; otherwise they are discarded.
+#[tauri::command]
+pub fn reload_external_changes(
+    merge_local_edits: Option<bool>,
+    doc_id: Option<String>,
+    state: tauri::State<AppState>,
+) -> Result<ReloadReport, String> {
+    let merge_local_edits = merge_local_edits.unwrap_or(false);
+    let changed = state.watcher.lock().map_err(|_| "State lock poisoned")?.changed.clone();
+    let is_changed = |path: &str| normalize(path).is_some_and(|p| changed.contains(&p));
+    let mut report = ReloadReport {
+        reloaded: Vec::new(),
+        conflicts: Vec::new(),
+        metadata: None,
+    };
+
+    {
+        let mut documents = state.documents.lock().map_err(|_| "State lock poisoned")?;
+        let document = documents.document(doc_id.as_deref())?;
+
+        if let Some(path) = document.path.clone().filter(|p| is_changed(p)) {
+            let (disk, warnings) = load_a2l_file(&path, None)?;
+            let keep_local = merge_local_edits && document.is_dirty();
+            let a2l = if keep_local {
+                let base = document
+                    .base
+                    .as_ref()
+                    .ok_or("The document has no saved version to merge against")?;
+                let base_tree = block_tree(base)?;
+                let local_tree = block_tree(&document.a2l)?;
+                let mut tree = block_tree(&disk)?;
+                merge_modules(&base_tree, &local_tree, &mut tree, &mut report.conflicts);
+                let mut text = String::new();
+                render_items(&tree, 0, &mut text);
+                parse_a2l(&text, None)?.0
+            } else {
+                disk.clone()
+            };
+
+            report.metadata = Some(build_metadata(&a2l, warnings.len()));
+            let mut edit = documents.edit("reload_external_changes", doc_id.as_deref())?;
+            if a2l != *edit.a2l() {
+                edit.replace(a2l);
+            }
+            edit.finish();
+            let document = documents.document_mut(doc_id.as_deref())?;
+            document.base = Some(disk);
+            document.diagnostics = diagnostics::from_warnings(&warnings);
+            if !keep_local {
+                document.saved_revision = document.revision;
+            }
+            report.reloaded.push(path);
+        }
+
+        let document = documents.document_mut(doc_id.as_deref())?;
+        if let Some(path) = document.hex_image.as_ref().map(|(p, _)| p.clone()).filter(|p| is_changed(p)) {
+            let text = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+            document.hex_image = Some((path.clone(), MemoryImage::parse(&text)?));
+            report.reloaded.push(path);
+        }
+    }
+
+    let mut elf = state.elf.lock().map_err(|_| "State lock poisoned")?;
+    if let Some(path) = elf.as_ref().map(|index| index.path().to_string()).filter(|p| is_changed(p)) {
+        // The symbol source may be a linker map or PDB loaded through `load_symbol_file`.
+        let symbols = read_elf_symbols(&path).or_else(|_| read_symbol_file(&path, None))?;
+        *elf = Some(ElfIndex::new(path.clone(), symbols));
+        report.reloaded.push(path);
+    }
+    drop(elf);
+
+    let mut watcher = state.watcher.lock().map_err(|_| "State lock poisoned")?;
+    for path in &report.reloaded {
+        if let Some(path) = normalize(path) {
+            watcher.changed.remove(&path);
+        }
+    }
+    Ok(report)
+}
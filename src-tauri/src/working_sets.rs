@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::references::object_exists;
+use crate::session::settings_path;
+use crate::AppState;
+
+const WORKING_SETS_FILE: &str = "working_sets.json";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct WorkingSetEntry {
+    kind: String,
+    name: String,
+}
+
+#[derive(Serialize)]
+pub struct WorkingSetInfo {
+    name: String,
+    entries: Vec<WorkingSetEntry>,
+}
+
+fn load_sets(app: &tauri::AppHandle) -> Result<BTreeMap<String, Vec<WorkingSetEntry>>, String> {
+    let path = settings_path(app, WORKING_SETS_FILE)?;
+    match fs::read_to_string(&path) {
+        Ok(text) => serde_json::from_str(&text).map_err(|e| format!("{WORKING_SETS_FILE}: {e}")),
+        Err(_) => Ok(BTreeMap::new()),
+    }
+}
+
+fn store_sets(app: &tauri::AppHandle, sets: &BTreeMap<String, Vec<WorkingSetEntry>>) -> Result<(), String> {
+    let path = settings_path(app, WORKING_SETS_FILE)?;
+    let text = serde_json::to_string_pretty(sets).map_err(|e| e.to_string())?;
+    fs::write(path, text).map_err(|e| e.to_string())
+}
+
+/// `(kind, name)` members of the working set `name`; `None` when no working
+/// set was requested.
+pub(crate) fn working_set_members(
+    app: &tauri::AppHandle,
+    name: Option<&str>,
+) -> Result<Option<Vec<(String, String)>>, String> {
+    let Some(name) = name.filter(|n| !n.is_empty()) else {
+        return Ok(None);
+    };
+    let entries = load_sets(app)?
+        .remove(name)
+        .ok_or_else(|| format!("Working set '{name}' not found"))?;
+    Ok(Some(entries.into_iter().map(|entry| (entry.kind, entry.name)).collect()))
+}
+
+#[tauri::command]
+pub fn create_working_set(name: String, app: tauri::AppHandle) -> Result<(), String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Working set name must not be empty".to_string());
+    }
+    let mut sets = load_sets(&app)?;
+    if sets.contains_key(&name) {
+        return Err(format!("Working set '{name}' already exists"));
+    }
+    sets.insert(name, Vec::new());
+    store_sets(&app, &sets)
+}
+
+#[tauri::command]
+pub fn delete_working_set(name: String, app: tauri::AppHandle) -> Result<(), String> {
+    let mut sets = load_sets(&app)?;
+    if sets.remove(&name).is_none() {
+        return Err(format!("Working set '{name}' not found"));
+    }
+    store_sets(&app, &sets)
+}
+
+/// Adds `kind`/`name`, which must exist in the document, to the working set.
+/// Adding an object twice has no effect.
+#[tauri::command]
+pub fn add_to_working_set(
+    set: String,
+    kind: String,
+    name: String,
+    doc_id: Option<String>,
+    app: tauri::AppHandle,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    {
+        let guard = state.documents.lock().map_err(|_| "State lock poisoned")?;
+        let a2l = guard.get(doc_id.as_deref())?;
+        if !a2l.project.module.iter().any(|module| object_exists(module, &kind, &name)) {
+            return Err(format!("{kind} '{name}' not found"));
+        }
+    }
+    let mut sets = load_sets(&app)?;
+    let entries = sets.get_mut(&set).ok_or_else(|| format!("Working set '{set}' not found"))?;
+    let entry = WorkingSetEntry { kind, name };
+    if !entries.contains(&entry) {
+        entries.push(entry);
+    }
+    store_sets(&app, &sets)
+}
+
+#[tauri::command]
+pub fn remove_from_working_set(set: String, kind: String, name: String, app: tauri::AppHandle) -> Result<(), String> {
+    let mut sets = load_sets(&app)?;
+    let entries = sets.get_mut(&set).ok_or_else(|| format!("Working set '{set}' not found"))?;
+    let before = entries.len();
+    entries.retain(|entry| !(entry.kind == kind && entry.name == name));
+    if entries.len() == before {
+        return Err(format!("{kind} '{name}' is not in working set '{set}'"));
+    }
+    store_sets(&app, &sets)
+}
+
+#[tauri::command]
+pub fn list_working_sets(app: tauri::AppHandle) -> Result<Vec<WorkingSetInfo>, String> {
+    Ok(load_sets(&app)?
+        .into_iter()
+        .map(|(name, entries)| WorkingSetInfo { name, entries })
+        .collect())
+}
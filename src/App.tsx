@@ -1,5 +1,6 @@
 import { useEffect, useMemo, useRef, useState, type Dispatch, type SetStateAction } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import {
   Box,
@@ -71,6 +72,21 @@ type A2lMetadata = {
   warning_count: number;
 };
 
+type LoadProgress = {
+  job_id: string;
+  stage: string;
+  bytes_read: number;
+  total_bytes: number;
+};
+
+type LoadFinished = {
+  job_id: string;
+  doc_id?: string | null;
+  metadata?: A2lMetadata | null;
+  error?: string | null;
+  cancelled: boolean;
+};
+
 type CoreEntity = {
   kind: string;
   name: string;
@@ -128,6 +144,14 @@ type ElfSymbol = {
   section: string;
 };
 
+type ElfSymbolPage = {
+  total: number;
+  offset: number;
+  symbols: ElfSymbol[];
+};
+
+const ELF_PAGE_SIZE = 500;
+
 // --- Theme ---
 
 const ideTheme = createTheme({
@@ -262,7 +286,10 @@ function App() {
   const [recentElfFiles, setRecentElfFiles] = useState<RecentFile[]>([]);
   const [isEditing, setIsEditing] = useState(false);
   const [elfSymbols, setElfSymbols] = useState<ElfSymbol[]>([]);
-  const [selectedElfSymbols, setSelectedElfSymbols] = useState<Set<string>>(new Set());
+  const [elfTotal, setElfTotal] = useState(0);
+  const [elfOffset, setElfOffset] = useState(0);
+  const [elfFilter, setElfFilter] = useState("");
+  const [selectedElfSymbols, setSelectedElfSymbols] = useState<Map<string, ElfSymbol>>(new Map());
   const statusTimeoutRef = useRef<number | null>(null);
 
   const refreshTree = async () => {
@@ -376,6 +403,37 @@ function App() {
     setSectionItemLimit({});
   }, [a2lTree]);
 
+  // Loading by path runs as a background job; wait for its finished event.
+  async function loadPathInBackground(path: string): Promise<A2lMetadata> {
+    let jobId: string | null = null;
+    const early: LoadFinished[] = [];
+    let resolveFinished: (result: LoadFinished) => void = () => {};
+    const finished = new Promise<LoadFinished>((resolve) => {
+      resolveFinished = resolve;
+    });
+    const unlistenProgress = await listen<LoadProgress>("a2l-load-progress", ({ payload }) => {
+      if (payload.job_id !== jobId) return;
+      const percent = payload.total_bytes > 0 ? Math.floor((payload.bytes_read / payload.total_bytes) * 100) : 0;
+      pushStatus("info", payload.stage === "parsing" ? "Parsing ..." : `Reading ... ${percent}%`, false);
+    });
+    const unlistenFinished = await listen<LoadFinished>("a2l-load-finished", ({ payload }) => {
+      if (jobId === null) early.push(payload);
+      else if (payload.job_id === jobId) resolveFinished(payload);
+    });
+    try {
+      jobId = await invoke<string>("load_a2l_from_path", { path });
+      const earlyResult = early.find((result) => result.job_id === jobId);
+      if (earlyResult) resolveFinished(earlyResult);
+      const result = await finished;
+      if (result.error) throw new Error(result.error);
+      if (result.cancelled || !result.metadata) throw new Error("Load cancelled");
+      return result.metadata;
+    } finally {
+      unlistenProgress();
+      unlistenFinished();
+    }
+  }
+
   function pushStatus(type: StatusType, message: string, autoClear = true) {
     if (statusTimeoutRef.current) {
       window.clearTimeout(statusTimeoutRef.current);
@@ -409,7 +467,7 @@ function App() {
              const contents = await (fileInput as File).text();
              metadata = await invoke<A2lMetadata>("load_a2l_from_string", { contents });
         } else if (filePath) {
-             metadata = await invoke<A2lMetadata>("load_a2l_from_path", { path: filePath });
+             metadata = await loadPathInBackground(filePath);
         } else {
              throw new Error("Cannot load file: missing path or content source.");
         }
@@ -515,24 +573,33 @@ function App() {
       setElfFileName(file.name);
       
       try {
-          const symbols = await invoke<ElfSymbol[]>("load_elf_symbols", { path: filePath });
-          setElfSymbols(symbols);
-          setSelectedElfSymbols(new Set());
+          const summary = await invoke<{ symbol_count: number }>("load_elf_symbols", { path: filePath });
+          setElfFilter("");
+          await queryElfPage("", 0);
+          setSelectedElfSymbols(new Map());
           
           // Add to recents
           addRecentFile(RECENT_ELF_KEY, recentElfFiles, setRecentElfFiles, {
              name: file.name, path: filePath, lastOpened: Date.now()
           });
 
-          pushStatus("success", `Loaded ${symbols.length} symbols.`);
+          pushStatus("success", `Loaded ${summary.symbol_count} symbols.`);
       } catch (e) {
           pushStatus("error", `ELF load failed: ${e}`);
           setElfSymbols([]);
+          setElfTotal(0);
       } finally {
           setIsBusy(false);
       }
   }
 
+  async function queryElfPage(filter: string, offset: number) {
+      const page = await invoke<ElfSymbolPage>("query_elf_symbols", { filter, offset, limit: ELF_PAGE_SIZE });
+      setElfSymbols(page.symbols);
+      setElfTotal(page.total);
+      setElfOffset(page.offset);
+  }
+
   async function handleAddSymbols() {
       if (selectedElfSymbols.size === 0) return;
       if (!metadata) {
@@ -542,7 +609,7 @@ function App() {
       
       setIsBusy(true);
       try {
-          const toAdd = elfSymbols.filter(s => selectedElfSymbols.has(s.name));
+          const toAdd = Array.from(selectedElfSymbols.values());
           
           // We assume update_project_metadata etc returns EntityUpdateResult, but create_measurements_from_elf matches signature
           // But wait, my previous code for update_project_metadata returned A2lMetadata, not EntityUpdateResult.
@@ -566,7 +633,7 @@ function App() {
           setA2lTree(tree);
           
           pushStatus("success", `Added ${toAdd.length} measurements.`);
-          setSelectedElfSymbols(new Set());
+          setSelectedElfSymbols(new Map());
       } catch (e) {
           pushStatus("error", `Failed to add symbols: ${e}`);
       } finally {
@@ -802,10 +869,36 @@ function App() {
                       <Stack direction="row" spacing={2} alignItems="center">
                           <MemoryIcon sx={{ color: "#4ec9b0" }} />
                           <Typography variant="h6" sx={{ fontSize: 14 }}>ELF Symbols</Typography> 
-                          {elfSymbols.length > 0 && <Chip label={`${elfSymbols.length} Found`} size="small" variant="outlined" sx={{ height: 20 }} />}
+                          {elfTotal > 0 && <Chip label={`${elfTotal} Found`} size="small" variant="outlined" sx={{ height: 20 }} />}
                           {selectedElfSymbols.size > 0 && <Chip label={`${selectedElfSymbols.size} Selected`} size="small" color="primary" sx={{ height: 20 }} />}
                       </Stack>
-                      <Stack direction="row" spacing={1}>
+                      <Stack direction="row" spacing={1} alignItems="center">
+                          <InputBase
+                              placeholder="Filter symbols..."
+                              value={elfFilter}
+                              onChange={(e) => {
+                                  setElfFilter(e.target.value);
+                                  queryElfPage(e.target.value, 0).catch((err) => pushStatus("error", `${err}`));
+                              }}
+                              sx={{ fontSize: 12, px: 1, bgcolor: "#3c3c3c", borderRadius: 1, width: 200 }}
+                          />
+                          <Button
+                              size="small"
+                              disabled={elfOffset === 0}
+                              onClick={() => queryElfPage(elfFilter, Math.max(0, elfOffset - ELF_PAGE_SIZE))}
+                          >
+                              Prev
+                          </Button>
+                          <Typography variant="caption" color="text.secondary">
+                              {elfTotal === 0 ? "0" : `${elfOffset + 1}-${elfOffset + elfSymbols.length}`} of {elfTotal}
+                          </Typography>
+                          <Button
+                              size="small"
+                              disabled={elfOffset + ELF_PAGE_SIZE >= elfTotal}
+                              onClick={() => queryElfPage(elfFilter, elfOffset + ELF_PAGE_SIZE)}
+                          >
+                              Next
+                          </Button>
                           <Button 
                               variant="contained" 
                               disabled={selectedElfSymbols.size === 0 || !metadata}
@@ -818,18 +911,23 @@ function App() {
                       </Stack>
                   </Box>
                   
-                  {elfSymbols.length > 0 ? (
+                  {elfTotal > 0 || elfFilter ? (
                       <TableContainer sx={{ flex: 1, overflow: "auto" }}>
                           <Table stickyHeader size="small">
                               <TableHead>
                                   <TableRow>
                                       <TableCell padding="checkbox" sx={{ bgcolor: "#1e1e1e" }}>
                                           <Checkbox 
-                                              checked={selectedElfSymbols.size === elfSymbols.length && elfSymbols.length > 0}
-                                              indeterminate={selectedElfSymbols.size > 0 && selectedElfSymbols.size < elfSymbols.length}
+                                              checked={elfSymbols.length > 0 && elfSymbols.every(s => selectedElfSymbols.has(s.name))}
+                                              indeterminate={elfSymbols.some(s => selectedElfSymbols.has(s.name)) && !elfSymbols.every(s => selectedElfSymbols.has(s.name))}
                                               onChange={(e) => {
-                                                  if (e.target.checked) setSelectedElfSymbols(new Set(elfSymbols.map(s => s.name)));
-                                                  else setSelectedElfSymbols(new Set());
+                                                  // Select or clear the current page; other pages keep their selection.
+                                                  const next = new Map(selectedElfSymbols);
+                                                  for (const s of elfSymbols) {
+                                                      if (e.target.checked) next.set(s.name, s);
+                                                      else next.delete(s.name);
+                                                  }
+                                                  setSelectedElfSymbols(next);
                                               }}
                                               size="small"
                                           />
@@ -844,9 +942,9 @@ function App() {
                               <TableBody>
                                   {elfSymbols.map((row) => (
                                       <TableRow key={row.name} hover selected={selectedElfSymbols.has(row.name)} onClick={() => {
-                                           const next = new Set(selectedElfSymbols);
+                                           const next = new Map(selectedElfSymbols);
                                            if (next.has(row.name)) next.delete(row.name);
-                                           else next.add(row.name);
+                                           else next.set(row.name, row);
                                            setSelectedElfSymbols(next);
                                       }} sx={{ cursor: "pointer" }}>
                                           <TableCell padding="checkbox">
@@ -9,8 +9,16 @@ import {
   Typography,
   Grid,
   Alert,
+  Checkbox,
+  FormControlLabel,
 } from "@mui/material";
 
+type BitOperation = {
+  left_shift?: number | null;
+  right_shift?: number | null;
+  sign_extend: boolean;
+};
+
 type MeasurementData = {
   name: string;
   long_identifier: string;
@@ -21,6 +29,8 @@ type MeasurementData = {
   lower_limit: number;
   upper_limit: number;
   ecu_address?: string | null;
+  bit_mask?: string | null;
+  bit_operation?: BitOperation | null;
 };
 
 type MeasurementEditorProps = {
@@ -43,6 +53,8 @@ const DATA_TYPES = [
   "FLOAT64_IEEE",
 ];
 
+const SHIFTS = ["NONE", "LEFT_SHIFT", "RIGHT_SHIFT"];
+
 export function MeasurementEditor({
   initialName,
   onSave,
@@ -82,6 +94,26 @@ export function MeasurementEditor({
     }
   };
 
+  const bitOperation: BitOperation = data?.bit_operation ?? { sign_extend: false };
+  const shiftKind =
+    bitOperation.left_shift != null
+      ? "LEFT_SHIFT"
+      : bitOperation.right_shift != null
+        ? "RIGHT_SHIFT"
+        : "NONE";
+  const shiftCount = bitOperation.left_shift ?? bitOperation.right_shift ?? 0;
+  const setBitOperation = (kind: string, count: number, signExtend: boolean) => {
+    if (!data) return;
+    setData({
+      ...data,
+      bit_operation: {
+        left_shift: kind === "LEFT_SHIFT" ? count : null,
+        right_shift: kind === "RIGHT_SHIFT" ? count : null,
+        sign_extend: signExtend,
+      },
+    });
+  };
+
   if (loading) return <Typography variant="caption">Loading editor...</Typography>;
   if (error && !data) return <Alert severity="error">{error}</Alert>;
   if (!data) return <Alert severity="warning">No data available</Alert>;
@@ -191,6 +223,57 @@ export function MeasurementEditor({
             fullWidth
           />
         </Grid>
+
+        <Grid size={{ xs: 12, sm: 6 }}>
+          <TextField
+            label="Bit Mask (Hex)"
+            value={data.bit_mask ?? ""}
+            onChange={(e) => setData({ ...data, bit_mask: e.target.value })}
+            size="small"
+            placeholder="0x..."
+            fullWidth
+          />
+        </Grid>
+        <Grid size={{ xs: 12, sm: 6 }}>
+          <FormControlLabel
+            control={
+              <Checkbox
+                checked={bitOperation.sign_extend}
+                onChange={(e) => setBitOperation(shiftKind, shiftCount, e.target.checked)}
+              />
+            }
+            label="Sign Extend"
+          />
+        </Grid>
+        <Grid size={{ xs: 12, sm: 6 }}>
+          <TextField
+            select
+            label="Bit Operation"
+            value={shiftKind}
+            onChange={(e) => setBitOperation(e.target.value, shiftCount, bitOperation.sign_extend)}
+            size="small"
+            fullWidth
+          >
+            {SHIFTS.map((shift) => (
+              <MenuItem key={shift} value={shift}>
+                {shift}
+              </MenuItem>
+            ))}
+          </TextField>
+        </Grid>
+        <Grid size={{ xs: 12, sm: 6 }}>
+          <TextField
+            label="Shift Count"
+            type="number"
+            value={shiftCount}
+            disabled={shiftKind === "NONE"}
+            onChange={(e) =>
+              setBitOperation(shiftKind, parseInt(e.target.value, 10) || 0, bitOperation.sign_extend)
+            }
+            size="small"
+            fullWidth
+          />
+        </Grid>
       </Grid>
 
       <Stack direction="row" spacing={2} justifyContent="flex-end">
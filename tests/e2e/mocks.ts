@@ -34,7 +34,35 @@ export const setupTauriMock = (data: { initialState: MockState, persistenceKey?:
         }
     }
 
+    // Event plumbing used by `listen` from @tauri-apps/api/event.
+    const callbacks = new Map<number, (data: any) => void>();
+    const listeners: Record<string, number[]> = {};
+    let nextCallbackId = 1;
+    let nextJobId = 1;
+    const emit = (event: string, payload: any) => {
+        for (const id of listeners[event] || []) {
+            callbacks.get(id)?.({ event, id, payload });
+        }
+    };
+
+    (window as any).__TAURI_EVENT_PLUGIN_INTERNALS__ = {
+        unregisterListener: (event: string, id: number) => {
+            listeners[event] = (listeners[event] || []).filter((listener) => listener !== id);
+        },
+    };
+
     (window as any).__TAURI_INTERNALS__ = {
+      transformCallback: (callback: (data: any) => void, once = false) => {
+        const id = nextCallbackId++;
+        callbacks.set(id, (data) => {
+            if (once) callbacks.delete(id);
+            callback?.(data);
+        });
+        return id;
+      },
+      unregisterCallback: (id: number) => {
+        callbacks.delete(id);
+      },
       invoke: async (cmd: string, args: any) => {
         // Reduced latency
         // await new Promise(r => setTimeout(r, 5));
@@ -47,12 +75,31 @@ export const setupTauriMock = (data: { initialState: MockState, persistenceKey?:
         };
 
         switch (cmd) {
+            case "plugin:event|listen": {
+                (listeners[args.event] ||= []).push(args.handler);
+                return args.handler;
+            }
+            case "plugin:event|unlisten":
+                return;
+
             case "load_a2l_from_string":
-            case "load_a2l_from_path":
-                // If loading from disk, we might want to reload state? 
                 // For simplified mock, we assume 'load' just returns current metadata
                 return state.metadata;
 
+            case "load_a2l_from_path": {
+                // Loading by path runs as a background job that reports its
+                // result with an event once the job ID has been returned.
+                const jobId = `load-${nextJobId++}`;
+                setTimeout(() => emit("a2l-load-finished", {
+                    job_id: jobId,
+                    doc_id: "doc-1",
+                    metadata: state.metadata,
+                    error: null,
+                    cancelled: false,
+                }), 0);
+                return jobId;
+            }
+
             case "list_a2l_tree":
                 return {
                     modules: [{
@@ -34,7 +34,35 @@ const mockBackendLogic = () => {
   // We attach it to window so we can inspect it if needed, but primarily it's closure-scoped
   let memoryState: any = null;
 
+  // Event plumbing used by `listen` from @tauri-apps/api/event.
+  const callbacks = new Map<number, (data: any) => void>();
+  const listeners: Record<string, number[]> = {};
+  let nextCallbackId = 1;
+  let nextJobId = 1;
+  const emit = (event: string, payload: any) => {
+    for (const id of listeners[event] || []) {
+      callbacks.get(id)?.({ event, id, payload });
+    }
+  };
+
+  (window as any).__TAURI_EVENT_PLUGIN_INTERNALS__ = {
+    unregisterListener: (event: string, id: number) => {
+      listeners[event] = (listeners[event] || []).filter((listener) => listener !== id);
+    },
+  };
+
   (window as any).__TAURI_INTERNALS__ = {
+    transformCallback: (callback: (data: any) => void, once = false) => {
+      const id = nextCallbackId++;
+      callbacks.set(id, (data) => {
+        if (once) callbacks.delete(id);
+        callback?.(data);
+      });
+      return id;
+    },
+    unregisterCallback: (id: number) => {
+      callbacks.delete(id);
+    },
     invoke: async (cmd: string, args: any) => {
       console.log(`[StatefulMock] ${cmd}`, args);
       
@@ -42,7 +70,14 @@ const mockBackendLogic = () => {
       await new Promise(r => setTimeout(r, 50));
 
       switch (cmd) {
-        case "load_a2l_from_path": 
+        case "plugin:event|listen": {
+          (listeners[args.event] ||= []).push(args.handler);
+          return args.handler;
+        }
+        case "plugin:event|unlisten":
+          return;
+
+        case "load_a2l_from_path":
         case "load_a2l_from_string": {
           // Read from "Disk"
           const rawDisk = localStorage.getItem(DISK_KEY);
@@ -51,7 +86,18 @@ const mockBackendLogic = () => {
           } else {
             memoryState = JSON.parse(JSON.stringify(INITIAL_STATE));
           }
-          return memoryState.metadata;
+          if (cmd === "load_a2l_from_string") return memoryState.metadata;
+          // Loading by path is a background job reporting through an event.
+          const jobId = `load-${nextJobId++}`;
+          const metadata = memoryState.metadata;
+          setTimeout(() => emit("a2l-load-finished", {
+            job_id: jobId,
+            doc_id: "doc-1",
+            metadata,
+            error: null,
+            cancelled: false,
+          }), 0);
+          return jobId;
         }
 
         case "list_a2l_tree": {